@@ -0,0 +1,132 @@
+use rome_rowan::{AstNode, BatchMutationExt, Language, SyntaxElement, SyntaxNode, SyntaxSlot};
+
+use crate::{AnalyzerAction, LanguageRoot};
+
+/// Applies all the non-overlapping `actions` to `root` in a single mutation
+/// of the tree.
+///
+/// Every action holds a modified copy of the whole tree, so once an action
+/// has been applied the others can't be applied by simply swapping the root.
+/// Instead, each action is reduced to the narrowest element of `root` it
+/// replaces and the replacements are all committed together. The actions are
+/// processed in the order of their range in the document, an action whose
+/// replaced element overlaps with an action already selected is skipped.
+///
+/// Returns the new root along with the list of actions that were applied, and
+/// the list of the skipped actions that should be computed again on the new
+/// root in a follow-up pass.
+pub fn apply_fixes<L: Language>(
+    root: LanguageRoot<L>,
+    mut actions: Vec<AnalyzerAction<L>>,
+) -> (
+    LanguageRoot<L>,
+    Vec<AnalyzerAction<L>>,
+    Vec<AnalyzerAction<L>>,
+) {
+    actions.sort_by_key(|action| (action.original_range.start(), action.original_range.end()));
+
+    let mut replacements: Vec<(SyntaxElement<L>, SyntaxElement<L>)> = Vec::new();
+    let mut applied = Vec::new();
+    let mut skipped = Vec::new();
+
+    for action in actions {
+        let replacement = find_replacement(root.syntax(), action.root.syntax());
+
+        let (prev, next) = match replacement {
+            Some(replacement) => replacement,
+            // The action doesn't change the tree
+            None => {
+                applied.push(action);
+                continue;
+            }
+        };
+
+        let conflicts = replacements
+            .iter()
+            .any(|(replaced, _)| is_overlapping(replaced, &prev));
+
+        if conflicts {
+            skipped.push(action);
+        } else {
+            replacements.push((prev, next));
+            applied.push(action);
+        }
+    }
+
+    let mut mutation = root.begin();
+    for (prev, next) in replacements {
+        mutation.replace_element(prev, next);
+    }
+
+    (mutation.commit(), applied, skipped)
+}
+
+/// Returns the narrowest element of `prev` that needs to be replaced with an
+/// element of `next` to turn `prev` into `next`, or [None] if the two trees
+/// are identical
+fn find_replacement<L: Language>(
+    prev: &SyntaxNode<L>,
+    next: &SyntaxNode<L>,
+) -> Option<(SyntaxElement<L>, SyntaxElement<L>)> {
+    if prev.key().0 == next.key().0 {
+        return None;
+    }
+
+    let mut prev = prev.clone();
+    let mut next = next.clone();
+
+    loop {
+        let prev_slots = prev.slots();
+        let next_slots = next.slots();
+
+        if prev.kind() != next.kind() || prev_slots.len() != next_slots.len() {
+            return Some((prev.into(), next.into()));
+        }
+
+        let mut changed = prev_slots.zip(next_slots).filter(|slots| match slots {
+            (SyntaxSlot::Empty, SyntaxSlot::Empty) => false,
+            (SyntaxSlot::Node(a), SyntaxSlot::Node(b)) => a.key().0 != b.key().0,
+            (SyntaxSlot::Token(a), SyntaxSlot::Token(b)) => a.key().0 != b.key().0,
+            _ => true,
+        });
+
+        match (changed.next(), changed.next()) {
+            // A single child node changed, look for the change inside of it
+            (Some((SyntaxSlot::Node(prev_child), SyntaxSlot::Node(next_child))), None) => {
+                prev = prev_child;
+                next = next_child;
+            }
+            (Some((SyntaxSlot::Token(prev_child), SyntaxSlot::Token(next_child))), None) => {
+                return Some((prev_child.into(), next_child.into()));
+            }
+            _ => return Some((prev.into(), next.into())),
+        }
+    }
+}
+
+/// Returns `true` if replacing both `a` and `b` would edit the same part of
+/// the document
+fn is_overlapping<L: Language>(a: &SyntaxElement<L>, b: &SyntaxElement<L>) -> bool {
+    let a_range = a.text_range();
+    let b_range = b.text_range();
+
+    if a_range.start() < b_range.end() && b_range.start() < a_range.end() {
+        return true;
+    }
+
+    // Empty elements don't overlap with any range, but they still can't be
+    // replaced along with one of their ancestors
+    is_ancestor_or_self(a, b) || is_ancestor_or_self(b, a)
+}
+
+fn is_ancestor_or_self<L: Language>(
+    ancestor: &SyntaxElement<L>,
+    element: &SyntaxElement<L>,
+) -> bool {
+    match ancestor {
+        SyntaxElement::Node(ancestor) => element.ancestors().any(|node| node == *ancestor),
+        SyntaxElement::Token(ancestor) => {
+            matches!(element, SyntaxElement::Token(token) if token == ancestor)
+        }
+    }
+}
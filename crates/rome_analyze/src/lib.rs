@@ -6,6 +6,7 @@ use std::ops;
 
 mod categories;
 pub mod context;
+mod fixes;
 mod matcher;
 mod query;
 mod registry;
@@ -16,6 +17,7 @@ mod syntax;
 mod visitor;
 
 pub use crate::categories::{ActionCategory, RuleCategories, RuleCategory};
+pub use crate::fixes::apply_fixes;
 pub use crate::matcher::{QueryMatcher, RuleKey, SignalEntry};
 pub use crate::query::{Ast, CannotCreateServicesError, QueryKey, QueryMatch, Queryable};
 pub use crate::registry::{LanguageRoot, Phase, Phases, RuleMetadata, RuleRegistry};
@@ -27,7 +29,7 @@ pub use crate::syntax::SyntaxVisitor;
 pub use crate::visitor::{NodeVisitor, Visitor, VisitorContext};
 use rome_console::markup;
 use rome_diagnostics::file::FileId;
-use rome_diagnostics::Diagnostic;
+use rome_diagnostics::{Diagnostic, Severity, SubDiagnostic};
 use rome_rowan::{
     AstNode, Direction, Language, SyntaxElement, SyntaxToken, TextRange, TextSize, TriviaPieceKind,
     WalkEvent,
@@ -54,6 +56,11 @@ pub struct Analyzer<'a, L: Language, Matcher, Break> {
     line_suppressions: Vec<LineSuppression>,
     /// Handles analyzer signals emitted by invidual rules
     emit_signal: SignalHandler<'a, L, Break>,
+    /// Whether identical diagnostics get grouped into a single diagnostic
+    group_diagnostics: bool,
+    /// Diagnostics waiting to be emitted at the end of the analysis when
+    /// `group_diagnostics` is enabled, in the order of their first occurrence
+    grouped_diagnostics: Vec<(RuleKey, Diagnostic)>,
 }
 
 /// Single entry for a suppression comment in the `line_suppressions` buffer
@@ -99,9 +106,23 @@ where
             line_index: 0,
             line_suppressions: Vec::new(),
             emit_signal,
+            group_diagnostics: false,
+            grouped_diagnostics: Vec::new(),
         }
     }
 
+    /// Groups the diagnostics emitted by the same rule with the same title
+    /// into a single diagnostic, to reduce the noise when many nodes trigger
+    /// the same rule.
+    ///
+    /// The grouped diagnostic is the first one emitted by the rule, with the
+    /// primary label of each following occurrence attached as a secondary label.
+    /// Grouped diagnostics are emitted at the end of the analysis, signals
+    /// carrying a code action are never grouped and get emitted immediately.
+    pub fn set_group_diagnostics(&mut self, group_diagnostics: bool) {
+        self.group_diagnostics = group_diagnostics;
+    }
+
     pub fn add_visitor<V>(&mut self, visitor: V)
     where
         V: Visitor<Language = L> + 'a,
@@ -146,6 +167,14 @@ where
             }
         }
 
+        for (_, diagnostic) in std::mem::take(&mut self.grouped_diagnostics) {
+            let signal = DiagnosticSignal::new(move || diagnostic.clone());
+
+            if let ControlFlow::Break(br) = (self.emit_signal)(&signal) {
+                return Some(br);
+            }
+        }
+
         None
     }
 
@@ -232,7 +261,22 @@ where
 
             // Emit the signal if the rule that created it is not currently being suppressed
             if !is_suppressed {
-                (self.emit_signal)(&*entry.signal)?;
+                if self.group_diagnostics && entry.signal.action().is_none() {
+                    if let Some(diagnostic) = entry.signal.diagnostic() {
+                        group_diagnostic(&mut self.grouped_diagnostics, entry.rule, diagnostic);
+                    }
+                } else {
+                    (self.emit_signal)(&*entry.signal)?;
+                }
+
+                for diagnostic in entry.signal.related() {
+                    if self.group_diagnostics {
+                        group_diagnostic(&mut self.grouped_diagnostics, entry.rule, diagnostic);
+                    } else {
+                        let signal = DiagnosticSignal::new(move || diagnostic.clone());
+                        (self.emit_signal)(&signal)?;
+                    }
+                }
             }
 
             // SAFETY: This removes `query` from the queue, it is known to
@@ -372,6 +416,31 @@ where
     }
 }
 
+/// Merges `diagnostic` into the diagnostic previously emitted by the same rule
+/// with the same title, or starts a new group if there's none
+fn group_diagnostic(
+    groups: &mut Vec<(RuleKey, Diagnostic)>,
+    rule: RuleKey,
+    diagnostic: Diagnostic,
+) {
+    let index = groups.iter().position(|(group_rule, group)| {
+        *group_rule == rule
+            && group.severity == diagnostic.severity
+            && group.code == diagnostic.code
+            && group.title == diagnostic.title
+    });
+
+    if let (Some(index), Some(primary)) = (index, &diagnostic.primary) {
+        let (_, group) = &mut groups[index];
+        group.children.push(SubDiagnostic {
+            severity: Severity::Note,
+            ..primary.clone()
+        });
+    } else {
+        groups.push((rule, diagnostic));
+    }
+}
+
 /// Signature for a suppression comment parser function
 ///
 /// This function receives the text content of a comment and returns a list of
@@ -388,11 +457,14 @@ type SuppressionParser = fn(&str) -> Vec<Option<&str>>;
 
 type SignalHandler<'a, L, Break> = &'a mut dyn FnMut(&dyn AnalyzerSignal<L>) -> ControlFlow<Break>;
 
-/// Allow filtering a single rule or group of rules by their names
+/// Allow filtering a single rule or group of rules by their names, or the
+/// preset of all the rules recommended by Rome
 #[derive(Debug, Clone, Copy)]
 pub enum RuleFilter<'a> {
     Group(&'a str),
     Rule(&'a str, &'a str),
+    /// Matches all the rules declared as `recommended`
+    Recommended,
 }
 
 impl RuleFilter<'_> {
@@ -405,6 +477,7 @@ impl RuleFilter<'_> {
         match self {
             RuleFilter::Group(group) => group == G::NAME,
             RuleFilter::Rule(group, rule) => group == G::NAME && rule == R::NAME,
+            RuleFilter::Recommended => R::RECOMMENDED,
         }
     }
 }
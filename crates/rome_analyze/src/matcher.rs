@@ -78,6 +78,9 @@ impl PartialEq<RuleKey> for RuleFilter<'static> {
         match *self {
             RuleFilter::Group(group) => group == other.group,
             RuleFilter::Rule(group, rule) => group == other.group && rule == other.rule,
+            // Filters compared to a key are created from suppression comments,
+            // which can only reference a group or a rule by name
+            RuleFilter::Recommended => false,
         }
     }
 }
@@ -115,15 +118,16 @@ impl<L: Language> PartialEq for SignalEntry<L> {
 
 #[cfg(test)]
 mod tests {
-    use rome_diagnostics::Diagnostic;
+    use rome_diagnostics::{Diagnostic, Severity};
     use rome_rowan::{
         raw_language::{RawLanguage, RawLanguageKind, RawLanguageRoot, RawSyntaxTreeBuilder},
         AstNode, TextRange, TextSize, TriviaPiece, TriviaPieceKind,
     };
 
     use crate::{
-        signals::DiagnosticSignal, Analyzer, AnalyzerContext, AnalyzerSignal, ControlFlow, Never,
-        Phases, QueryMatch, QueryMatcher, RuleKey, ServiceBag, SignalEntry, SyntaxVisitor,
+        signals::DiagnosticSignal, Analyzer, AnalyzerAction, AnalyzerContext, AnalyzerSignal,
+        ControlFlow, Never, Phases, QueryMatch, QueryMatcher, RuleKey, ServiceBag, SignalEntry,
+        SyntaxVisitor,
     };
 
     use super::{GroupKey, MatchQueryParams};
@@ -322,4 +326,197 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn grouped_diagnostics() {
+        let root = {
+            let mut builder = RawSyntaxTreeBuilder::new();
+
+            builder.start_node(RawLanguageKind::ROOT);
+            builder.start_node(RawLanguageKind::EXPRESSION_LIST);
+
+            for _ in 0..3 {
+                builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+                builder.token(RawLanguageKind::STRING_TOKEN, "\"warn_here\"");
+                builder.finish_node();
+
+                builder.token_with_trivia(
+                    RawLanguageKind::SEMICOLON_TOKEN,
+                    ";\n",
+                    &[],
+                    &[TriviaPiece::new(TriviaPieceKind::Newline, 1)],
+                );
+            }
+
+            builder.finish_node();
+            builder.finish_node();
+
+            RawLanguageRoot::unwrap_cast(builder.finish())
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut emit_signal = |signal: &dyn AnalyzerSignal<RawLanguage>| -> ControlFlow<Never> {
+            diagnostics.push(signal.diagnostic().expect("diagnostic"));
+            ControlFlow::Continue(())
+        };
+
+        let mut analyzer = Analyzer::new(SuppressionMatcher, |_| Vec::new(), &mut emit_signal);
+
+        analyzer.add_visitor(SyntaxVisitor::default());
+        analyzer.set_group_diagnostics(true);
+
+        let ctx: AnalyzerContext<RawLanguage> = AnalyzerContext {
+            phase: Phases::Syntax,
+            file_id: 0,
+            root,
+            range: None,
+            services: ServiceBag::default(),
+        };
+
+        let result: Option<Never> = analyzer.run(ctx);
+        assert!(result.is_none());
+
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = &diagnostics[0];
+        let labels: Vec<_> = diagnostic
+            .primary
+            .iter()
+            .chain(&diagnostic.children)
+            .map(|label| label.span.range)
+            .collect();
+
+        assert_eq!(
+            labels,
+            [
+                TextRange::new(TextSize::from(0), TextSize::from(11)),
+                TextRange::new(TextSize::from(13), TextSize::from(24)),
+                TextRange::new(TextSize::from(26), TextSize::from(37)),
+            ]
+        );
+    }
+
+    /// Signal emitting a diagnostic on a literal expression, and a related
+    /// diagnostic on the following token
+    struct RelatedSignal {
+        range: TextRange,
+        related_range: TextRange,
+    }
+
+    impl AnalyzerSignal<RawLanguage> for RelatedSignal {
+        fn diagnostic(&self) -> Option<Diagnostic> {
+            Some(Diagnostic::warning(0, "test_related", "primary").primary(self.range, ""))
+        }
+
+        fn action(&self) -> Option<AnalyzerAction<RawLanguage>> {
+            None
+        }
+
+        fn related(&self) -> Vec<Diagnostic> {
+            vec![Diagnostic::note(0, "test_related", "related").primary(self.related_range, "")]
+        }
+    }
+
+    struct RelatedMatcher;
+
+    impl QueryMatcher<RawLanguage> for RelatedMatcher {
+        fn find_group(&self, _: &str) -> Option<GroupKey> {
+            None
+        }
+
+        fn find_rule(&self, _: &str, _: &str) -> Option<RuleKey> {
+            None
+        }
+
+        fn match_query(&mut self, params: MatchQueryParams<RawLanguage>) {
+            let node = match params.query {
+                QueryMatch::Syntax(node) => node,
+                QueryMatch::ControlFlowGraph(..) => unreachable!(),
+            };
+
+            if node.kind() != RawLanguageKind::LITERAL_EXPRESSION {
+                return;
+            }
+
+            let range = node.text_trimmed_range();
+            let related_range = node
+                .last_token()
+                .and_then(|token| token.next_token())
+                .expect("next token")
+                .text_trimmed_range();
+
+            params.signal_queue.push(SignalEntry {
+                signal: Box::new(RelatedSignal {
+                    range,
+                    related_range,
+                }),
+                rule: RuleKey::new("group", "rule"),
+                text_range: range,
+            });
+        }
+    }
+
+    #[test]
+    fn related_diagnostics() {
+        let root = {
+            let mut builder = RawSyntaxTreeBuilder::new();
+
+            builder.start_node(RawLanguageKind::ROOT);
+            builder.start_node(RawLanguageKind::EXPRESSION_LIST);
+
+            builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+            builder.token(RawLanguageKind::STRING_TOKEN, "\"warn_here\"");
+            builder.finish_node();
+
+            builder.token_with_trivia(
+                RawLanguageKind::SEMICOLON_TOKEN,
+                ";\n",
+                &[],
+                &[TriviaPiece::new(TriviaPieceKind::Newline, 1)],
+            );
+
+            builder.finish_node();
+            builder.finish_node();
+
+            RawLanguageRoot::unwrap_cast(builder.finish())
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut emit_signal = |signal: &dyn AnalyzerSignal<RawLanguage>| -> ControlFlow<Never> {
+            let diag = signal.diagnostic().expect("diagnostic");
+            let label = diag.primary.expect("primary label");
+
+            diagnostics.push((diag.severity, label.span.range));
+            ControlFlow::Continue(())
+        };
+
+        let mut analyzer = Analyzer::new(RelatedMatcher, |_| Vec::new(), &mut emit_signal);
+
+        analyzer.add_visitor(SyntaxVisitor::default());
+
+        let ctx: AnalyzerContext<RawLanguage> = AnalyzerContext {
+            phase: Phases::Syntax,
+            file_id: 0,
+            root,
+            range: None,
+            services: ServiceBag::default(),
+        };
+
+        let result: Option<Never> = analyzer.run(ctx);
+        assert!(result.is_none());
+
+        assert_eq!(
+            diagnostics.as_slice(),
+            &[
+                (
+                    Severity::Warning,
+                    TextRange::new(TextSize::from(0), TextSize::from(11))
+                ),
+                (
+                    Severity::Note,
+                    TextRange::new(TextSize::from(11), TextSize::from(12))
+                ),
+            ]
+        );
+    }
 }
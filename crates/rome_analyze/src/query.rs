@@ -76,18 +76,3 @@ where
         }
     }
 }
-
-impl<L: Language> Queryable for ControlFlowGraph<L> {
-    type Output = Self;
-    type Language = L;
-    type Services = ();
-
-    const KEY: QueryKey<Self::Language> = QueryKey::ControlFlowGraph;
-
-    fn unwrap_match(query: &QueryMatch<Self::Language>) -> Self::Output {
-        match query {
-            QueryMatch::ControlFlowGraph(cfg, _) => cfg.clone(),
-            _ => panic!("tried to unwrap unsupported QueryMatch kind, expected Syntax"),
-        }
-    }
-}
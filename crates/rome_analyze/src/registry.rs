@@ -42,7 +42,7 @@ impl Phase for () {
 pub struct RuleRegistry<L: Language> {
     /// Stores metadata information for all the rules in the registry, sorted
     /// alphabetically
-    metadata: BTreeMap<MetadataKey, &'static str>,
+    metadata: BTreeMap<MetadataKey, RuleMetadata>,
     /// Holds a collection of rules for each phase.
     phase_rules: [PhaseRules<L>; 2],
 }
@@ -104,17 +104,20 @@ impl<L: Language> RuleRegistry<L> {
             MetadataKey {
                 inner: (G::NAME, R::NAME),
             },
-            R::DOCS,
+            RuleMetadata {
+                group: G::NAME,
+                name: R::NAME,
+                version: R::VERSION,
+                recommended: R::RECOMMENDED,
+                docs: R::DOCS,
+            },
         );
     }
 
-    /// Returns an iterator over the name and documentation of all active rules
-    /// in this instance of the registry
+    /// Returns an iterator over the metadata of all active rules in this
+    /// instance of the registry, sorted by group and name
     pub fn metadata(self) -> impl Iterator<Item = RuleMetadata> {
-        self.metadata.into_iter().map(|(key, docs)| {
-            let (group, name) = key.inner;
-            RuleMetadata { group, name, docs }
-        })
+        self.metadata.into_values()
     }
 }
 
@@ -199,12 +202,46 @@ impl<'a> Borrow<(&'a str, &'a str)> for MetadataKey {
 }
 
 /// Metadata entry for a rule in the registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RuleMetadata {
+    /// The name of the group this rule belongs to
     pub group: &'static str,
+    /// The name of the rule
     pub name: &'static str,
+    /// The version of Rome in which the rule was introduced
+    pub version: &'static str,
+    /// Whether the rule is part of the recommended rules
+    pub recommended: bool,
+    /// The content of the documentation comments for the rule
     pub docs: &'static str,
 }
 
+impl RuleMetadata {
+    /// Returns the first paragraph of the documentation of the rule, with
+    /// its lines joined by a space
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+
+        for line in self.docs.lines().map(str::trim) {
+            if line.is_empty() {
+                if summary.is_empty() {
+                    continue;
+                }
+
+                break;
+            }
+
+            if !summary.is_empty() {
+                summary.push(' ');
+            }
+
+            summary.push_str(line);
+        }
+
+        summary
+    }
+}
+
 /// Internal representation of a single rule in the registry
 pub struct RegistryRule<L: Language> {
     run: RuleExecutor<L>,
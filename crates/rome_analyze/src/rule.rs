@@ -13,6 +13,11 @@ use crate::{AnalysisFilter, LanguageRoot, Phase, Phases, Queryable, RuleRegistry
 pub trait RuleMeta {
     /// The name of this rule, displayed in the diagnostics it emits
     const NAME: &'static str;
+    /// The version of Rome in which this rule was introduced, or `"next"` if
+    /// it hasn't been released yet
+    const VERSION: &'static str;
+    /// Whether this rule is part of the set of rules recommended by Rome
+    const RECOMMENDED: bool;
     /// The content of the documentation comments for this rule
     const DOCS: &'static str;
 }
@@ -26,10 +31,18 @@ pub trait RuleMeta {
 /// ```ignore
 /// declare_rule! {
 ///     /// Documentation
-///     pub(crate) ExampleRule = "ruleName"
+///     pub(crate) ExampleRule {
+///         version: "0.7.0",
+///         name: "ruleName",
+///         recommended: false,
+///     }
 /// }
 /// ```
 ///
+/// The `version` is the version of Rome in which the rule was introduced, or
+/// `"next"` for rules that haven't been released yet, and `recommended`
+/// controls whether the rule is part of the set of rules recommended by Rome.
+///
 /// # Documentation
 ///
 /// The doc-comment for the rule is mandatory and is used to generate the
@@ -51,7 +64,11 @@ pub trait RuleMeta {
 ///     /// ```js
 ///     /// let a, b;
 ///     /// ```
-///     pub(crate) NoVar = "noVar"
+///     pub(crate) NoVar {
+///         version: "0.7.0",
+///         name: "noVar",
+///         recommended: false,
+///     }
 /// }
 /// ```
 ///
@@ -67,7 +84,11 @@ pub trait RuleMeta {
 ///     /// ```js,expect_diagnostic
 ///     /// var a, b;
 ///     /// ```
-///     pub(crate) NoVar = "noVar"
+///     pub(crate) NoVar {
+///         version: "0.7.0",
+///         name: "noVar",
+///         recommended: false,
+///     }
 /// }
 /// ```
 ///
@@ -76,12 +97,18 @@ pub trait RuleMeta {
 /// diagnostic in the resulting documentation page
 #[macro_export]
 macro_rules! declare_rule {
-    ( $( #[doc = $doc:literal] )+ $vis:vis $id:ident = $name:literal ) => {
+    ( $( #[doc = $doc:literal] )+ $vis:vis $id:ident {
+        version: $version:literal,
+        name: $name:literal,
+        recommended: $recommended:literal $(,)?
+    } ) => {
         $( #[doc = $doc] )*
         $vis enum $id {}
 
         impl $crate::RuleMeta for $id {
             const NAME: &'static str = $name;
+            const VERSION: &'static str = $version;
+            const RECOMMENDED: bool = $recommended;
             const DOCS: &'static str = concat!( $( $doc, "\n", )* );
         }
     };
@@ -250,6 +277,9 @@ impl RuleDiagnostic {
     }
 
     /// Creates a new [`RuleDiagnostic`] with the `Help` severity.
+    ///
+    /// This is meant for suggestions that shouldn't count as warnings, for example style nudges:
+    /// the language server reports these diagnostics as hints.
     pub fn help(span: impl Span, title: impl Display) -> Self {
         Self::new(Severity::Help, span, title)
     }
@@ -345,7 +375,41 @@ impl RuleDiagnostic {
     /// Convert this [`RuleDiagnostic`] into an instance of [`Diagnostic`] by
     /// injecting the name of the rule that emitted it and the ID of the file
     /// the rule was being run on
+    ///
+    /// Labels sharing the same range are merged to avoid printing the same code frame
+    /// several times: the primary label takes precedence over the secondary labels, and
+    /// secondary labels with a higher severity take precedence over the others.
     pub(crate) fn into_diagnostic(self, file_id: FileId, code: String) -> Diagnostic {
+        let mut primary = self.primary;
+        let mut children: Vec<SubDiagnostic> = Vec::with_capacity(self.secondaries.len());
+
+        for (severity, msg, range) in self.secondaries {
+            if range == self.span {
+                // Only keep the message of the secondary label if the primary label has none
+                if primary.is_none() {
+                    primary = Some(msg);
+                }
+                continue;
+            }
+
+            match children.iter_mut().find(|child| child.span.range == range) {
+                Some(child) => {
+                    if severity > child.severity {
+                        child.severity = severity;
+                        child.msg = msg;
+                    }
+                }
+                None => children.push(SubDiagnostic {
+                    severity,
+                    msg,
+                    span: FileSpan {
+                        file: file_id,
+                        range,
+                    },
+                }),
+            }
+        }
+
         Diagnostic {
             file_id,
             severity: self.severity,
@@ -355,24 +419,13 @@ impl RuleDiagnostic {
             tag: self.tag,
             primary: Some(SubDiagnostic {
                 severity: self.severity,
-                msg: self.primary.unwrap_or_default(),
+                msg: primary.unwrap_or_default(),
                 span: FileSpan {
                     file: file_id,
                     range: self.span,
                 },
             }),
-            children: self
-                .secondaries
-                .into_iter()
-                .map(|(severity, msg, range)| SubDiagnostic {
-                    severity,
-                    msg,
-                    span: FileSpan {
-                        file: file_id,
-                        range,
-                    },
-                })
-                .collect(),
+            children,
             suggestions: Vec::new(),
             footers: self.footers,
         }
@@ -386,3 +439,49 @@ pub struct RuleAction<L: Language> {
     pub message: MarkupBuf,
     pub root: LanguageRoot<L>,
 }
+
+#[cfg(test)]
+mod tests {
+    use rome_console::markup;
+    use rome_diagnostics::Severity;
+    use rome_rowan::{TextRange, TextSize};
+
+    use super::RuleDiagnostic;
+
+    #[test]
+    fn help_diagnostic_severity() {
+        let range = TextRange::new(TextSize::from(0), TextSize::from(4));
+
+        let help = RuleDiagnostic::help(range, "Consider a shorter name")
+            .into_diagnostic(0, String::from("help"));
+        let warning = RuleDiagnostic::warning(range, "This variable is unused")
+            .into_diagnostic(0, String::from("warning"));
+
+        assert_eq!(help.severity, Severity::Help);
+        assert_eq!(help.primary.unwrap().severity, Severity::Help);
+        assert!(help.severity < warning.severity);
+    }
+
+    #[test]
+    fn merge_labels_with_same_range() {
+        let primary = TextRange::new(TextSize::from(0), TextSize::from(4));
+        let secondary = TextRange::new(TextSize::from(8), TextSize::from(12));
+
+        let diagnostic = RuleDiagnostic::warning(primary, "This code is unreachable")
+            .primary("primary")
+            .secondary(primary, "same range as the primary label")
+            .label(Severity::Note, secondary, "note")
+            .label(Severity::Warning, secondary, "warning")
+            .label(Severity::Help, secondary, "help")
+            .into_diagnostic(0, String::from("merge"));
+
+        let primary_label = diagnostic.primary.unwrap();
+        assert_eq!(primary_label.span.range, primary);
+        assert_eq!(primary_label.msg, markup!("primary").to_owned());
+
+        assert_eq!(diagnostic.children.len(), 1);
+        assert_eq!(diagnostic.children[0].span.range, secondary);
+        assert_eq!(diagnostic.children[0].severity, Severity::Warning);
+        assert_eq!(diagnostic.children[0].msg, markup!("warning").to_owned());
+    }
+}
@@ -24,6 +24,14 @@ use crate::{
 pub trait AnalyzerSignal<L: Language> {
     fn diagnostic(&self) -> Option<Diagnostic>;
     fn action(&self) -> Option<AnalyzerAction<L>>;
+
+    /// Additional diagnostics emitted along with the main diagnostic of this
+    /// signal, for instance to point at other locations involved in the same
+    /// issue. The analyzer emits each of them as a separate signal right after
+    /// this one
+    fn related(&self) -> Vec<Diagnostic> {
+        Vec::new()
+    }
 }
 
 /// Simple implementation of [AnalyzerSignal] generating a diagnostic from a
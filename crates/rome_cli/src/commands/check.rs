@@ -6,7 +6,7 @@ use crate::{
 use rome_diagnostics::MAXIMUM_DISPLAYABLE_DIAGNOSTICS;
 use rome_service::configuration::Configuration;
 use rome_service::settings::{LinterSettings, WorkspaceSettings};
-use rome_service::workspace::UpdateSettingsParams;
+use rome_service::workspace::{FixFileMode, UpdateSettingsParams};
 use rome_service::{load_config, ConfigurationType};
 
 /// Handler for the "check" command of the Rome CLI
@@ -45,10 +45,21 @@ pub(crate) fn check(mut session: CliSession) -> Result<(), Termination> {
             settings: workspace_settings,
         })?;
 
+    let apply = session.args.contains("--apply");
+    let apply_unsafe = session.args.contains("--apply-unsafe");
+
+    let fix_file_mode = if apply_unsafe {
+        Some(FixFileMode::SafeAndUnsafeFixes)
+    } else if apply {
+        Some(FixFileMode::SafeFixes)
+    } else {
+        None
+    };
+
     traverse(
         TraversalMode::Check {
             max_diagnostics,
-            should_fix: session.args.contains("--apply"),
+            fix_file_mode,
         },
         session,
     )
@@ -50,7 +50,7 @@ pub(crate) fn parse_format_options(
         workspace_settings.merge_with_configuration(configuration);
     }
 
-    let size = session
+    let size: Option<u8> = session
         .args
         .opt_value_from_str("--indent-size")
         .map_err(|source| Termination::ParseError {
@@ -58,6 +58,21 @@ pub(crate) fn parse_format_options(
             source,
         })?;
 
+    let indent_size = match size {
+        Some(size) => {
+            Some(
+                IndentStyle::try_space(size).map_err(|error| Termination::ParseError {
+                    argument: "--indent-size",
+                    source: pico_args::Error::Utf8ArgumentParsingFailed {
+                        value: size.to_string(),
+                        cause: error.to_string(),
+                    },
+                })?,
+            )
+        }
+        None => None,
+    };
+
     let indent_style = session
         .args
         .opt_value_from_str("--indent-style")
@@ -72,7 +87,7 @@ pub(crate) fn parse_format_options(
         }
         Some(IndentStyle::Space(default_size)) => {
             workspace_settings.format.indent_style =
-                Some(IndentStyle::Space(size.unwrap_or(default_size)));
+                Some(indent_size.unwrap_or(IndentStyle::Space(default_size)));
         }
         None => {}
     }
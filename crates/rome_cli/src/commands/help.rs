@@ -19,6 +19,8 @@ const MAIN: Markup = markup! {
 
 "<Emphasis>"OPTIONS:"</Emphasis>"
     "<Dim>"--no-colors"</Dim>"      Disable the formatting of markup (print everything as plain text)
+    "<Dim>"--quiet"</Dim>"          Only print errors and the output of the command
+    "<Dim>"--verbose"</Dim>"        Print debug traces in addition to the informational messages
 "
 };
 
@@ -32,6 +34,7 @@ const CHECK: Markup = markup! {
 
 "<Emphasis>"OPTIONS:"</Emphasis>"
     "<Dim>"--apply"</Dim>"                       Apply safe fixes
+    "<Dim>"--apply-unsafe"</Dim>"                Apply safe fixes and unsafe fixes
     "<Dim>"--max-diagnostics"</Dim>"             Cap the amount of diagnostics displayed - default 20
 "
 };
@@ -1,6 +1,8 @@
 use pico_args::Arguments;
+use rome_console::{EnvConsole, Verbosity};
 use rome_flags::FeatureFlags;
-use rome_service::App;
+use rome_fs::OsFileSystem;
+use rome_service::{App, DynRef};
 
 mod commands;
 mod metrics;
@@ -24,8 +26,21 @@ impl CliSession<'static> {
         let mut args = Arguments::from_env();
         let no_colors = args.contains("--no-colors");
 
+        let verbosity = if args.contains("--quiet") {
+            Verbosity::Quiet
+        } else if args.contains("--verbose") {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        };
+
+        let console = EnvConsole::new(no_colors).with_verbosity(verbosity);
+
         Self {
-            app: App::from_env(no_colors),
+            app: App::with_filesystem_and_console(
+                DynRef::Owned(Box::new(OsFileSystem)),
+                DynRef::Owned(Box::new(console)),
+            ),
             args,
         }
     }
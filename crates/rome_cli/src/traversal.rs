@@ -24,7 +24,9 @@ use rome_formatter::IndentStyle;
 use rome_fs::{AtomicInterner, FileSystem, OpenOptions, PathInterner, RomePath};
 use rome_fs::{TraversalContext, TraversalScope};
 use rome_service::{
-    workspace::{FeatureName, FileGuard, OpenFileParams, RuleCategories, SupportsFeatureParams},
+    workspace::{
+        FeatureName, FileGuard, FixFileMode, OpenFileParams, RuleCategories, SupportsFeatureParams,
+    },
     Workspace,
 };
 
@@ -55,6 +57,12 @@ pub(crate) fn traverse(mode: TraversalMode, mut session: CliSession) -> Result<(
         });
     }
 
+    for input in &inputs {
+        session.app.console.debug(markup! {
+            "Traversing "{input.to_string_lossy()}
+        });
+    }
+
     let (interner, recv_files) = AtomicInterner::new();
     let (send_msgs, recv_msgs) = unbounded();
 
@@ -90,36 +98,36 @@ pub(crate) fn traverse(mode: TraversalMode, mut session: CliSession) -> Result<(
     let skipped = skipped.load(Ordering::Relaxed);
 
     match mode {
-        TraversalMode::Check { should_fix, .. } => {
-            if should_fix {
-                console.log(rome_console::markup! {
+        TraversalMode::Check { fix_file_mode, .. } => {
+            if fix_file_mode.is_some() {
+                console.info(rome_console::markup! {
                     <Info>"Fixed "{count}" files in "{duration}</Info>
                 });
             } else {
-                console.log(rome_console::markup! {
+                console.info(rome_console::markup! {
                     <Info>"Checked "{count}" files in "{duration}</Info>
                 });
             }
         }
         TraversalMode::CI { .. } => {
-            console.log(rome_console::markup! {
+            console.info(rome_console::markup! {
                 <Info>"Checked "{count}" files in "{duration}</Info>
             });
         }
         TraversalMode::Format { write: false, .. } => {
-            console.log(rome_console::markup! {
+            console.info(rome_console::markup! {
                 <Info>"Compared "{count}" files in "{duration}</Info>
             });
         }
         TraversalMode::Format { write: true, .. } => {
-            console.log(rome_console::markup! {
+            console.info(rome_console::markup! {
                 <Info>"Formatted "{count}" files in "{duration}</Info>
             });
         }
     }
 
     if skipped > 0 {
-        console.log(rome_console::markup! {
+        console.info(rome_console::markup! {
             <Warn>"Skipped "{skipped}" files"</Warn>
         });
     }
@@ -227,7 +235,7 @@ fn print_messages_to_console(
                 }
 
                 if !mode.is_ci() {
-                    console.log(markup! {
+                    console.info(markup! {
                         <Warn>"The number of diagnostics exceeds the number allowed by Rome."</Warn>
                         <Info>"Diagnostics not shown: "</Info><Emphasis>{not_printed_diagnostics}</Emphasis><Info>"."</Info>
                     })
@@ -289,8 +297,9 @@ pub(crate) enum TraversalMode {
     /// This mode is enabled when running the command `rome check`
     Check {
         max_diagnostics: u8,
-        /// `true` when running the command `check` with the `--apply` argument
-        should_fix: bool,
+        /// `Some` when running the command `check` with the `--apply` or
+        /// `--apply-unsafe` argument
+        fix_file_mode: Option<FixFileMode>,
     },
     /// This mode is enabled when running the command `rome ci`
     CI,
@@ -308,12 +317,13 @@ impl TraversalMode {
         }
     }
 
-    /// `true` only when running the traversal in [TraversalMode::Check] and `should_fix` is `true`
-    fn should_fix(&self) -> bool {
-        if let TraversalMode::Check { should_fix, .. } = self {
-            *should_fix
+    /// Returns the [FixFileMode] only when running the traversal in [TraversalMode::Check]
+    /// with fixes enabled
+    fn fix_file_mode(&self) -> Option<FixFileMode> {
+        if let TraversalMode::Check { fix_file_mode, .. } = self {
+            *fix_file_mode
         } else {
-            false
+            None
         }
     }
 
@@ -483,9 +493,9 @@ fn process_file(ctx: &TraversalOptions, path: &Path, file_id: FileId) -> FileRes
         )
         .with_file_id_and_code(file_id, "IO")?;
 
-        if ctx.mode.should_fix() {
+        if let Some(fix_file_mode) = ctx.mode.fix_file_mode() {
             let fixed = file_guard
-                .fix_file()
+                .fix_file(fix_file_mode)
                 .with_file_id_and_code(file_id, "Lint")?;
 
             if fixed.code != input {
@@ -216,6 +216,38 @@ mod check {
         assert!(result.is_ok(), "run_cli returned {result:?}");
     }
 
+    #[test]
+    fn apply_unsafe_ok() {
+        let mut fs = MemoryFileSystem::default();
+        let mut console = BufferConsole::default();
+
+        let file_path = Path::new("fix.js");
+        fs.insert(file_path.into(), LINT_ERROR.as_bytes());
+
+        let result = run_cli(CliSession {
+            app: App::with_filesystem_and_console(
+                DynRef::Borrowed(&mut fs),
+                DynRef::Borrowed(&mut console),
+            ),
+            args: Arguments::from_vec(vec![
+                OsString::from("check"),
+                OsString::from("--apply-unsafe"),
+                file_path.as_os_str().into(),
+            ]),
+        });
+
+        assert!(result.is_ok(), "run_cli returned {result:?}");
+
+        let mut buffer = String::new();
+        fs.open(file_path)
+            .unwrap()
+            .read_to_string(&mut buffer)
+            .unwrap();
+
+        // Both `useWhile` and `useBlockStatements` provide unsafe fixes
+        assert_eq!(buffer, "while (true) {}\n");
+    }
+
     #[test]
     fn no_lint_if_linter_is_disabled_when_run_apply() {
         let mut fs = MemoryFileSystem::default();
@@ -560,6 +592,31 @@ mod format {
         }
     }
 
+    #[test]
+    fn indent_size_parse_errors_zero() {
+        let result = run_cli(CliSession {
+            app: App::with_filesystem_and_console(
+                DynRef::Owned(Box::new(MemoryFileSystem::default())),
+                DynRef::Owned(Box::new(BufferConsole::default())),
+            ),
+            args: Arguments::from_vec(vec![
+                OsString::from("format"),
+                OsString::from("--indent-style"),
+                OsString::from("space"),
+                OsString::from("--indent-size"),
+                OsString::from("0"),
+                OsString::from("file.js"),
+            ]),
+        });
+
+        match result {
+            Err(Termination::ParseError { argument, .. }) => assert_eq!(argument, "--indent-size"),
+            _ => panic!(
+                "run_cli returned {result:?} for an invalid argument value, expected an error"
+            ),
+        }
+    }
+
     #[test]
     fn line_width_parse_errors_negative() {
         let result = run_cli(CliSession {
@@ -675,6 +732,35 @@ mod format {
     }
 }
 
+mod filesystem {
+    use super::*;
+    use rome_fs::OpenOptions;
+
+    #[test]
+    fn read_file_through_app() {
+        let mut fs = MemoryFileSystem::default();
+
+        let file_path = Path::new("file.js");
+        fs.insert(file_path.into(), UNFORMATTED.as_bytes());
+
+        let app = App::with_filesystem_and_console(
+            DynRef::Owned(Box::new(fs)),
+            DynRef::Owned(Box::new(BufferConsole::default())),
+        );
+
+        let mut file = app
+            .fs
+            .open_with_options(file_path, OpenOptions::default().read(true))
+            .expect("the file should exist in the memory file system");
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .expect("failed to read file from memory FS");
+
+        assert_eq!(content, UNFORMATTED);
+    }
+}
+
 mod help {
     use super::*;
 
@@ -924,7 +1010,7 @@ mod configuration {
             Err(error) => {
                 assert!(error
                     .to_string()
-                    .contains("The line width exceeds the maximum value (320)"),)
+                    .contains("The line width 500 is out of range, it must be between 1 and 320"),)
             }
             _ => panic!("expected an error, but found none"),
         }
@@ -1,13 +1,14 @@
-use std::io::Write;
-use std::panic::RefUnwindSafe;
+use std::io::{self, Write};
+use std::panic::{AssertUnwindSafe, RefUnwindSafe};
 
-use termcolor::{ColorChoice, StandardStream};
+use termcolor::{Buffer, ColorChoice, StandardStream};
 use write::Termcolor;
 
 pub mod codespan;
 pub mod diff;
 pub mod fmt;
 mod markup;
+pub mod table;
 mod write;
 
 pub use self::markup::{Markup, MarkupBuf, MarkupElement, MarkupNode};
@@ -24,11 +25,35 @@ pub enum LogLevel {
     Log,
 }
 
+/// Controls which messages get printed by the [ConsoleExt] methods, each
+/// level includes all the messages of the previous ones
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only print errors and the output of the command
+    Quiet,
+    /// Also print informational messages, this is the default level
+    Normal,
+    /// Also print debug traces
+    Verbose,
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
 /// Generic abstraction over printing markup and diagnostics to an output,
 /// which can be a terminal, a file, a memory buffer ...
 pub trait Console: Send + Sync + RefUnwindSafe {
     /// Prints a message (formatted using [markup!]) to the console
     fn print(&mut self, level: LogLevel, args: Markup);
+
+    /// Returns the [Verbosity] level of the console, the emission sites
+    /// should check it before printing optional messages
+    fn verbosity(&self) -> Verbosity {
+        Verbosity::Normal
+    }
 }
 
 /// Extension trait for [Console] providing convenience printing methods
@@ -38,6 +63,14 @@ pub trait ConsoleExt: Console {
 
     /// Prints a piece of markup with level [LogLevel::Log]
     fn log(&mut self, args: Markup);
+
+    /// Prints a piece of markup with level [LogLevel::Log], unless the
+    /// verbosity of the console is [Verbosity::Quiet]
+    fn info(&mut self, args: Markup);
+
+    /// Prints a piece of markup with level [LogLevel::Log], only if the
+    /// verbosity of the console is [Verbosity::Verbose]
+    fn debug(&mut self, args: Markup);
 }
 
 impl<T: Console + ?Sized> ConsoleExt for T {
@@ -48,12 +81,25 @@ impl<T: Console + ?Sized> ConsoleExt for T {
     fn log(&mut self, args: Markup) {
         self.print(LogLevel::Log, args);
     }
+
+    fn info(&mut self, args: Markup) {
+        if self.verbosity() >= Verbosity::Normal {
+            self.print(LogLevel::Log, args);
+        }
+    }
+
+    fn debug(&mut self, args: Markup) {
+        if self.verbosity() >= Verbosity::Verbose {
+            self.print(LogLevel::Log, args);
+        }
+    }
 }
 
 /// Implementation of [Console] printing messages to the standard output and standard error
 pub struct EnvConsole {
     out: StandardStream,
     err: StandardStream,
+    verbosity: Verbosity,
 }
 
 impl EnvConsole {
@@ -72,8 +118,15 @@ impl EnvConsole {
         Self {
             out: StandardStream::stdout(out_mode),
             err: StandardStream::stderr(err_mode),
+            verbosity: Verbosity::default(),
         }
     }
+
+    /// Changes the [Verbosity] level of the console
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
 }
 
 impl Console for EnvConsole {
@@ -89,12 +142,17 @@ impl Console for EnvConsole {
 
         writeln!(out).unwrap();
     }
+
+    fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
 }
 
 /// Implementation of [Console] storing all printed messages to a memory buffer
 #[derive(Default, Debug)]
 pub struct BufferConsole {
     pub buffer: Vec<Message>,
+    pub verbosity: Verbosity,
 }
 
 /// Individual message entry printed to a [BufferConsole]
@@ -111,4 +169,242 @@ impl Console for BufferConsole {
             content: args.to_owned(),
         });
     }
+
+    fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+}
+
+/// Implementation of [Console] buffering the printed messages in memory and
+/// writing them to the underlying writers in chunks of `capacity` bytes.
+///
+/// This avoids a write (and a lock) of the underlying stream for each message
+/// when printing a large number of diagnostics. The remaining content is
+/// written when calling [BufferedConsole::flush] or when dropping the console.
+///
+/// [Console::print] can't return the error of a failed chunk write: the error
+/// is instead returned by the next call to [BufferedConsole::flush], and the
+/// messages printed to that stream in the meantime are discarded.
+pub struct BufferedConsole<W: io::Write> {
+    out: BufferedStream<W>,
+    err: BufferedStream<W>,
+}
+
+impl<W: io::Write> BufferedConsole<W> {
+    /// Default size of the chunks written to the underlying writers
+    pub const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+    /// Creates a console printing [LogLevel::Log] messages to `out` and
+    /// [LogLevel::Error] messages to `err`
+    pub fn new(out: W, err: W, no_colors: bool) -> Self {
+        Self {
+            out: BufferedStream::new(out, no_colors),
+            err: BufferedStream::new(err, no_colors),
+        }
+    }
+
+    /// Changes the size of the chunks written to the underlying writers
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.out.capacity = capacity;
+        self.err.capacity = capacity;
+        self
+    }
+
+    /// Writes the buffered content to the underlying writers
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()?;
+        self.err.flush()
+    }
+
+    /// Returns the writer receiving the [LogLevel::Log] messages
+    pub fn out(&self) -> &W {
+        &self.out.writer
+    }
+
+    /// Returns the writer receiving the [LogLevel::Error] messages
+    pub fn err(&self) -> &W {
+        &self.err.writer
+    }
+}
+
+impl<W> Console for BufferedConsole<W>
+where
+    W: io::Write + Send + Sync + RefUnwindSafe,
+{
+    fn print(&mut self, level: LogLevel, args: Markup) {
+        let stream = match level {
+            LogLevel::Error => &mut self.err,
+            LogLevel::Log => &mut self.out,
+        };
+
+        if stream.error.is_some() {
+            return;
+        }
+
+        fmt::Formatter::new(&mut Termcolor(&mut stream.buffer))
+            .write_markup(args)
+            .unwrap();
+
+        writeln!(stream.buffer).unwrap();
+
+        if stream.buffer.len() >= stream.capacity {
+            if let Err(error) = stream.flush() {
+                stream.error = Some(AssertUnwindSafe(error));
+            }
+        }
+    }
+}
+
+impl<W: io::Write> Drop for BufferedConsole<W> {
+    fn drop(&mut self) {
+        // Errors are ignored, like the standard library's `BufWriter` does
+        let _ = self.flush();
+    }
+}
+
+struct BufferedStream<W> {
+    writer: W,
+    buffer: Buffer,
+    capacity: usize,
+    /// Error of a chunk write, returned by the next call to [BufferedStream::flush].
+    /// The error is only moved out of the stream, so it can't be observed in a broken state.
+    error: Option<AssertUnwindSafe<io::Error>>,
+}
+
+impl<W: io::Write> BufferedStream<W> {
+    fn new(writer: W, no_colors: bool) -> Self {
+        Self {
+            writer,
+            buffer: if no_colors {
+                Buffer::no_color()
+            } else {
+                Buffer::ansi()
+            },
+            capacity: BufferedConsole::<W>::DEFAULT_CAPACITY,
+            error: None,
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(AssertUnwindSafe(error)) = self.error.take() {
+            self.buffer.clear();
+            return Err(error);
+        }
+
+        self.writer.write_all(self.buffer.as_slice())?;
+        self.buffer.clear();
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as rome_console, markup, BufferConsole, BufferedConsole, ConsoleExt, LogLevel,
+        Verbosity,
+    };
+
+    #[test]
+    fn buffered_console_writes_in_chunks() {
+        let mut console = BufferedConsole::new(Vec::new(), Vec::new(), true).with_capacity(64);
+
+        for index in 0..1000 {
+            console.log(markup! { "Message "<Emphasis>{index}</Emphasis> });
+        }
+        console.error(markup! { "Failed" });
+
+        // The last chunk is still buffered, the error is smaller than the capacity
+        let expected: String = (0..1000)
+            .map(|index| format!("Message {index}\n"))
+            .collect();
+        assert!(expected.starts_with(std::str::from_utf8(console.out()).unwrap()));
+        assert!(console.out().len() > expected.len() - 64);
+        assert!(console.err().is_empty());
+
+        console.flush().unwrap();
+
+        assert_eq!(std::str::from_utf8(console.out()).unwrap(), expected);
+        assert_eq!(std::str::from_utf8(console.err()).unwrap(), "Failed\n");
+    }
+
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "write failed",
+            ))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn buffered_console_returns_chunk_write_errors_from_flush() {
+        let mut console =
+            BufferedConsole::new(FailingWriter, FailingWriter, true).with_capacity(16);
+
+        // The first chunk fails to write, the messages after it are discarded
+        for index in 0..100 {
+            console.log(markup! { "Message "{index} });
+        }
+
+        let error = console.flush().unwrap_err();
+        assert_eq!(error.to_string(), "write failed");
+    }
+
+    /// Prints a message with each method of [ConsoleExt] and returns the
+    /// level and text of the messages that were printed
+    fn print_messages(verbosity: Verbosity) -> Vec<(LogLevel, &'static str)> {
+        let mut console = BufferConsole {
+            verbosity,
+            ..BufferConsole::default()
+        };
+
+        console.error(markup! { "Error" });
+        console.log(markup! { "Output" });
+        console.info(markup! { "Info" });
+        console.debug(markup! { "Debug" });
+
+        console
+            .buffer
+            .iter()
+            .map(|message| {
+                let content = format!("{:?}", message.content);
+                let text = ["Error", "Output", "Info", "Debug"]
+                    .into_iter()
+                    .find(|text| content.contains(text))
+                    .unwrap();
+                (message.level, text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn console_verbosity() {
+        assert_eq!(
+            print_messages(Verbosity::Quiet),
+            [(LogLevel::Error, "Error"), (LogLevel::Log, "Output")]
+        );
+        assert_eq!(
+            print_messages(Verbosity::Normal),
+            [
+                (LogLevel::Error, "Error"),
+                (LogLevel::Log, "Output"),
+                (LogLevel::Log, "Info")
+            ]
+        );
+        assert_eq!(
+            print_messages(Verbosity::Verbose),
+            [
+                (LogLevel::Error, "Error"),
+                (LogLevel::Log, "Output"),
+                (LogLevel::Log, "Info"),
+                (LogLevel::Log, "Debug")
+            ]
+        );
+    }
 }
@@ -0,0 +1,180 @@
+use std::io;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    self as rome_console,
+    fmt::{Display, Formatter},
+    markup,
+};
+
+/// Utility struct to print a list of rows in aligned columns in the console
+///
+/// # Example
+/// ```text
+/// Name          Recommended
+/// noDeadCode    no
+/// noDebugger    yes
+/// useSingleCa…  no
+/// ```
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    /// Header of each column, printed in bold on the first line of the table
+    pub columns: &'a [&'a str],
+    /// Cells of each row of the table, in the same order as `columns`
+    pub rows: &'a [&'a [&'a str]],
+    /// Cells wider than this will be truncated and end with an ellipsis
+    pub max_column_width: usize,
+}
+
+/// Spacing inserted between two columns
+const COLUMN_SEPARATOR: &str = "  ";
+
+impl<'a> Display for Table<'a> {
+    fn fmt(&self, fmt: &mut Formatter) -> io::Result<()> {
+        // Determine the display width of each column
+        let mut widths: Vec<_> = self
+            .columns
+            .iter()
+            .map(|column| column.width().min(self.max_column_width))
+            .collect();
+
+        for row in self.rows {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.width().min(self.max_column_width));
+            }
+        }
+
+        for (index, (column, width)) in self.columns.iter().zip(&widths).enumerate() {
+            let (text, text_width) = truncate(column, self.max_column_width);
+            fmt.write_markup(markup! {
+                <Emphasis>{text}</Emphasis>
+            })?;
+
+            if index + 1 < widths.len() {
+                write_padding(fmt, width.saturating_sub(text_width))?;
+            }
+        }
+
+        writeln!(fmt)?;
+
+        for row in self.rows {
+            for (index, (cell, width)) in row.iter().zip(&widths).enumerate() {
+                let (text, text_width) = truncate(cell, self.max_column_width);
+                fmt.write_str(&text)?;
+
+                if index + 1 < widths.len() {
+                    write_padding(fmt, width.saturating_sub(text_width))?;
+                }
+            }
+
+            writeln!(fmt)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Print enough spaces to fill a column up to its width, followed by the
+/// [COLUMN_SEPARATOR]. The ellipsis of truncated cells may be wider than the
+/// column if `max_column_width` is 0, in which case no spaces are printed
+fn write_padding(fmt: &mut Formatter, padding: usize) -> io::Result<()> {
+    fmt.write_str(&" ".repeat(padding))?;
+    fmt.write_str(COLUMN_SEPARATOR)
+}
+
+/// Returns the content of `text` shortened to fit in `max_width` columns,
+/// along with its display width
+fn truncate(text: &str, max_width: usize) -> (String, usize) {
+    let width = text.width();
+    if width <= max_width {
+        return (text.to_string(), width);
+    }
+
+    // Keep one column for the ellipsis character
+    let mut result = String::new();
+    let mut result_width = 0;
+
+    for char in text.chars() {
+        let char_width = char.width().unwrap_or(0);
+        if result_width + char_width + 1 > max_width {
+            break;
+        }
+
+        result.push(char);
+        result_width += char_width;
+    }
+
+    result.push('…');
+    (result, result_width + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as rome_console, markup, table::Table, BufferConsole, ConsoleExt};
+
+    #[test]
+    fn test_table() {
+        let table = Table {
+            columns: &["Name", "Recommended"],
+            rows: &[
+                &["noDeadCode", "no"],
+                &["noDebugger", "yes"],
+                &["useSingleCaseStatement", "no"],
+            ],
+            max_column_width: 12,
+        };
+
+        let mut console = BufferConsole::default();
+        console.log(markup! {
+            {table}
+        });
+
+        let mut messages = console.buffer.into_iter();
+        let message = match messages.next() {
+            Some(msg) => msg,
+            other => panic!("unexpected message {other:?}"),
+        };
+
+        assert_eq!(
+            message.content,
+            markup! {
+                <Emphasis>"Name"</Emphasis>"          "<Emphasis>"Recommended"</Emphasis>"\n"
+                "noDeadCode    no\n"
+                "noDebugger    yes\n"
+                "useSingleCa…  no\n"
+            }
+            .to_owned()
+        );
+
+        assert!(messages.next().is_none());
+    }
+    #[test]
+    fn test_table_zero_column_width() {
+        let table = Table {
+            columns: &["Name", "Recommended"],
+            rows: &[&["noDeadCode", "no"]],
+            max_column_width: 0,
+        };
+
+        let mut console = BufferConsole::default();
+        console.log(markup! {
+            {table}
+        });
+
+        let mut messages = console.buffer.into_iter();
+        let message = match messages.next() {
+            Some(msg) => msg,
+            other => panic!("unexpected message {other:?}"),
+        };
+
+        assert_eq!(
+            message.content,
+            markup! {
+                <Emphasis>"…"</Emphasis>"  "<Emphasis>"…"</Emphasis>"\n"
+                "…  …\n"
+            }
+            .to_owned()
+        );
+    }
+}
@@ -69,6 +69,11 @@ impl rome_rowan::SyntaxKind for CssSyntaxKind {
     fn from_raw(raw: RawSyntaxKind) -> Self {
         Self::from(raw.0)
     }
+
+    #[inline]
+    fn is_valid_raw(raw: RawSyntaxKind) -> bool {
+        raw.0 <= CssSyntaxKind::__LAST as u16
+    }
 }
 
 impl TryFrom<CssSyntaxKind> for TriviaPieceKind {
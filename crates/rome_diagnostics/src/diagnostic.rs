@@ -1,6 +1,7 @@
 use crate::suggestion::SuggestionChange;
 use crate::{
     file::{FileId, FileSpan, Span},
+    line_index::{LineIndex, RangeUtf16},
     Applicability, CodeSuggestion, DiagnosticTag, Severity, SuggestionStyle,
 };
 use rome_console::fmt::Display;
@@ -432,6 +433,13 @@ impl Diagnostic {
     pub fn is_error(&self) -> bool {
         self.severity >= Severity::Error
     }
+
+    /// Returns the range of the primary label of this diagnostic, converted to UTF-16 line/column
+    /// positions as expected by the Language Server Protocol
+    pub fn primary_range_utf16(&self, line_index: &LineIndex) -> Option<RangeUtf16> {
+        let primary = self.primary.as_ref()?;
+        Some(line_index.range_utf16(primary.span.range))
+    }
 }
 
 /// Everything that can be added to a diagnostic, like
@@ -1,6 +1,7 @@
 #![deny(rust_2018_idioms)]
 
 pub mod file;
+pub mod line_index;
 
 mod diagnostic;
 mod emit;
@@ -30,6 +30,14 @@ pub struct LineCol {
     pub col: u32,
 }
 
+/// Start and end positions of a [TextRange], with columns in UTF-16 code units as expected by
+/// the Language Server Protocol
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RangeUtf16 {
+    pub start: LineColUtf16,
+    pub end: LineColUtf16,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub(crate) struct Utf16Char {
     /// Start offset of a character inside a line, zero-based
@@ -104,6 +112,11 @@ impl LineIndex {
         }
     }
 
+    /// Returns the number of lines of the text
+    pub fn line_count(&self) -> usize {
+        self.newlines.len()
+    }
+
     pub fn line_col(&self, offset: TextSize) -> LineCol {
         let line = self.newlines.partition_point(|&it| it <= offset) - 1;
         let line_start_offset = self.newlines[line];
@@ -134,6 +147,14 @@ impl LineIndex {
         }
     }
 
+    /// Converts a range of byte offsets into UTF-16 line/column positions
+    pub fn range_utf16(&self, range: TextRange) -> RangeUtf16 {
+        RangeUtf16 {
+            start: self.to_utf16(self.line_col(range.start())),
+            end: self.to_utf16(self.line_col(range.end())),
+        }
+    }
+
     pub fn lines(&self, range: TextRange) -> impl Iterator<Item = TextRange> + '_ {
         let lo = self.newlines.partition_point(|&it| it < range.start());
         let hi = self.newlines.partition_point(|&it| it <= range.end());
@@ -179,3 +200,95 @@ impl LineIndex {
         col.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rome_rowan::{TextRange, TextSize};
+
+    use super::{LineCol, LineColUtf16, LineIndex, RangeUtf16};
+    use crate::Diagnostic;
+
+    fn range_of(text: &str, needle: &str) -> TextRange {
+        let start = text.find(needle).unwrap();
+        TextRange::at(TextSize::from(start as u32), TextSize::of(needle))
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let text = "let a = '😀';\r\nlet b = 'é👍🏽' + c;\r\n";
+        let index = LineIndex::new(text);
+
+        assert_eq!(index.line_count(), 3);
+
+        let position = |line, col| LineColUtf16 { line, col };
+
+        // The emoji is 4 bytes long in UTF-8 but only 2 code units in UTF-16
+        assert_eq!(
+            index.range_utf16(range_of(text, "'😀'")),
+            RangeUtf16 {
+                start: position(0, 8),
+                end: position(0, 12),
+            }
+        );
+
+        // The line ends before the carriage return
+        assert_eq!(
+            index.range_utf16(range_of(text, ";\r\n")),
+            RangeUtf16 {
+                start: position(0, 12),
+                end: position(1, 0),
+            }
+        );
+
+        assert_eq!(
+            index.range_utf16(range_of(text, "c")),
+            RangeUtf16 {
+                start: position(1, 18),
+                end: position(1, 19),
+            }
+        );
+    }
+
+    #[test]
+    fn utf16_round_trip() {
+        let text = "a\r\n'👍🏽' + b";
+        let index = LineIndex::new(text);
+
+        let offset = TextSize::from(text.find('b').unwrap() as u32);
+        let line_col = index.line_col(offset);
+        assert_eq!(line_col, LineCol { line: 1, col: 13 });
+
+        let utf16 = index.to_utf16(line_col);
+        assert_eq!(utf16, LineColUtf16 { line: 1, col: 9 });
+        assert_eq!(index.offset(index.to_utf8(utf16)), offset);
+    }
+
+    #[test]
+    fn diagnostic_primary_range() {
+        let text = "const a = '🚀';\r\na = 1;\r\n";
+        let index = LineIndex::new(text);
+
+        let diagnostic = Diagnostic::error(0, "", "").primary(range_of(text, "a = 1"), "");
+
+        assert_eq!(
+            diagnostic.primary_range_utf16(&index),
+            Some(RangeUtf16 {
+                start: LineColUtf16 { line: 1, col: 0 },
+                end: LineColUtf16 { line: 1, col: 5 },
+            })
+        );
+
+        let diagnostic = Diagnostic::error(0, "", "").primary(range_of(text, "';"), "");
+
+        assert_eq!(
+            diagnostic.primary_range_utf16(&index),
+            Some(RangeUtf16 {
+                start: LineColUtf16 { line: 0, col: 13 },
+                end: LineColUtf16 { line: 0, col: 15 },
+            })
+        );
+
+        let diagnostic = Diagnostic::error(0, "", "");
+        assert_eq!(diagnostic.primary_range_utf16(&index), None);
+    }
+}
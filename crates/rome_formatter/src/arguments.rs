@@ -120,6 +120,30 @@ mod tests {
     use crate::prelude::*;
     use crate::{format_args, write, FormatState, VecBuffer};
 
+    #[test]
+    fn test_optional_entries() {
+        let mut context = FormatState::new(());
+        let mut buffer = VecBuffer::new(&mut context);
+
+        let present = Some(token("b"));
+        let missing: Option<StaticToken> = None;
+
+        write!(
+            &mut buffer,
+            [format_args!(token("a"), present, missing, token("c"))]
+        )
+        .unwrap();
+
+        assert_eq!(
+            buffer.into_element(),
+            FormatElement::List(List::new(vec![
+                FormatElement::Token(Token::Static { text: "a" }),
+                FormatElement::Token(Token::Static { text: "b" }),
+                FormatElement::Token(Token::Static { text: "c" }),
+            ]))
+        );
+    }
+
     #[test]
     fn test_nesting() {
         let mut context = FormatState::new(());
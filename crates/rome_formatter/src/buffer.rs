@@ -217,6 +217,8 @@ impl<Context> Buffer for VecBuffer<'_, Context> {
     type Context = Context;
 
     fn write_element(&mut self, element: FormatElement) -> FormatResult<()> {
+        self.state.track_element(&element);
+
         match element {
             FormatElement::List(list) => self.elements.extend(list.into_vec()),
             element => self.elements.push(element),
@@ -249,6 +251,91 @@ Make sure that you take and restore the snapshot in order and that this snapshot
     }
 }
 
+/// [Buffer] for short-lived content, for example, the content of an [crate::builders::Indent].
+///
+/// The buffer takes its vector from the pool of the [FormatState] and returns it to the pool when
+/// dropped, so that consecutive scratch buffers reuse the same allocations.
+/// See [crate::Formatter::scratch_buffer]
+pub(crate) struct ScratchBuffer<'a, Context> {
+    state: &'a mut FormatState<Context>,
+    elements: Vec<FormatElement>,
+}
+
+impl<'a, Context> ScratchBuffer<'a, Context> {
+    /// Maximum capacity of the vectors returned to the pool. Larger vectors are shrunk so that
+    /// the content of a single large node doesn't keep its allocation alive until the end of
+    /// the formatting.
+    const MAX_POOLED_CAPACITY: usize = 1024;
+
+    pub(crate) fn new(state: &'a mut FormatState<Context>) -> Self {
+        let elements = state.scratch_buffers.pop().unwrap_or_default();
+
+        Self { state, elements }
+    }
+
+    /// Copies the written elements into a slice that fits the content exactly.
+    /// The allocation of the buffer goes back to the pool.
+    pub(crate) fn into_boxed_slice(mut self) -> Box<[FormatElement]> {
+        self.elements.drain(..).collect()
+    }
+}
+
+impl<Context> Drop for ScratchBuffer<'_, Context> {
+    fn drop(&mut self) {
+        let mut elements = std::mem::take(&mut self.elements);
+        elements.clear();
+        elements.shrink_to(Self::MAX_POOLED_CAPACITY);
+
+        self.state.scratch_buffers.push(elements);
+    }
+}
+
+impl<Context> Deref for ScratchBuffer<'_, Context> {
+    type Target = [FormatElement];
+
+    fn deref(&self) -> &Self::Target {
+        &self.elements
+    }
+}
+
+impl<Context> Buffer for ScratchBuffer<'_, Context> {
+    type Context = Context;
+
+    fn write_element(&mut self, element: FormatElement) -> FormatResult<()> {
+        self.state.track_element(&element);
+
+        match element {
+            FormatElement::List(list) => self.elements.extend(list.into_vec()),
+            element => self.elements.push(element),
+        }
+
+        Ok(())
+    }
+
+    fn state(&self) -> &FormatState<Self::Context> {
+        self.state
+    }
+
+    fn state_mut(&mut self) -> &mut FormatState<Self::Context> {
+        self.state
+    }
+
+    fn snapshot(&self) -> BufferSnapshot {
+        BufferSnapshot::position(self.elements.len())
+    }
+
+    fn restore_snapshot(&mut self, snapshot: BufferSnapshot) {
+        let position = snapshot.unwrap_position();
+        assert!(
+            self.elements.len() >= position,
+            r#"Outdated snapshot. This buffer contains fewer elements than at the time the snapshot was taken.
+Make sure that you take and restore the snapshot in order and that this snapshot belongs to the current buffer."#
+        );
+
+        self.elements.truncate(position);
+    }
+}
+
 /// This struct wraps an existing buffer and emits a preamble text when the first text is written.
 ///
 /// This can be useful if you, for example, want to write some content if what gets written next isn't empty.
@@ -312,6 +399,12 @@ pub struct PreambleBuffer<'buf, Preamble, Context> {
 
     /// Whether some content (including the pre-amble) has been written at this point.
     empty: bool,
+
+    /// Whether writing the pre-amble produced any elements.
+    preamble_written: bool,
+
+    /// Whether some content, excluding the pre-amble, has been written at this point.
+    content_written: bool,
 }
 
 impl<'buf, Preamble, Context> PreambleBuffer<'buf, Preamble, Context> {
@@ -320,12 +413,33 @@ impl<'buf, Preamble, Context> PreambleBuffer<'buf, Preamble, Context> {
             inner,
             preamble,
             empty: true,
+            preamble_written: false,
+            content_written: false,
         }
     }
 
-    /// Returns `true` if the preamble has been written, `false` otherwise.
+    /// Returns `true` if the preamble has been written and it wrote some elements, `false` otherwise.
     pub fn did_write_preamble(&self) -> bool {
-        !self.empty
+        self.preamble_written
+    }
+
+    /// Returns `true` if some content that prints to the output, not counting the preamble, has
+    /// been written to this buffer, `false` otherwise.
+    ///
+    /// Elements that don't print anything on their own, like [FormatElement::ExpandParent], are
+    /// passed through to the inner buffer without writing the preamble.
+    pub fn did_write_content(&self) -> bool {
+        self.content_written
+    }
+}
+
+/// Returns `true` if `element` doesn't print anything by itself but only affects how
+/// the surrounding content gets printed.
+fn is_marker_element(element: &FormatElement) -> bool {
+    match element {
+        FormatElement::ExpandParent => true,
+        FormatElement::Group(group) => group.content.is_empty(),
+        _ => false,
     }
 }
 
@@ -338,13 +452,24 @@ where
     fn write_element(&mut self, element: FormatElement) -> FormatResult<()> {
         if element.is_empty() {
             Ok(())
+        } else if is_marker_element(&element) {
+            self.inner.write_element(element)
         } else {
             if self.empty {
-                write!(self.inner, [&self.preamble])?;
+                let mut preamble_written = false;
+                let mut inspect = Inspect::new(self.inner, |element: &FormatElement| {
+                    preamble_written |= !element.is_empty();
+                });
+                write!(inspect, [&self.preamble])?;
+
                 self.empty = false;
+                self.preamble_written = preamble_written;
             }
 
-            self.inner.write_element(element)
+            self.inner.write_element(element)?;
+            self.content_written = true;
+
+            Ok(())
         }
     }
 
@@ -360,6 +485,8 @@ where
         BufferSnapshot::Any(Box::new(PreambleBufferSnapshot {
             inner: self.inner.snapshot(),
             empty: self.empty,
+            preamble_written: self.preamble_written,
+            content_written: self.content_written,
         }))
     }
 
@@ -367,6 +494,8 @@ where
         let snapshot = snapshot.unwrap_any::<PreambleBufferSnapshot>();
 
         self.empty = snapshot.empty;
+        self.preamble_written = snapshot.preamble_written;
+        self.content_written = snapshot.content_written;
         self.inner.restore_snapshot(snapshot.inner);
     }
 }
@@ -374,6 +503,8 @@ where
 struct PreambleBufferSnapshot {
     inner: BufferSnapshot,
     empty: bool,
+    preamble_written: bool,
+    content_written: bool,
 }
 
 /// Buffer that allows you inspecting elements as they get written to the formatter.
@@ -769,3 +900,85 @@ impl<Context> Buffer for NullBuffer<'_, Context> {
         drop(snapshot);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::ScratchBuffer;
+    use crate::prelude::*;
+    use crate::{FormatState, Formatted, PreambleBuffer, VecBuffer};
+
+    #[test]
+    fn scratch_buffers_are_reused() {
+        let mut state = FormatState::new(SimpleFormatContext::default());
+        let mut buffer = VecBuffer::new(&mut state);
+
+        let elements = format_with(|f: &mut Formatter<SimpleFormatContext>| {
+            for _ in 0..1000 {
+                crate::write!(
+                    f,
+                    [
+                        indent(&crate::format_args![
+                            if_group_breaks(&token("a")),
+                            line_suffix(&token("// a"))
+                        ]),
+                        token(","),
+                        soft_line_break_or_space()
+                    ]
+                )?;
+            }
+
+            Ok(())
+        });
+
+        crate::write!(buffer, [token("["), block_indent(&elements), token("]")]).unwrap();
+
+        // Every element writes three scratch buffers, but only one vector gets allocated
+        // for each level of nesting: block indent, indent, and if group breaks or line suffix.
+        assert_eq!(state.scratch_buffers.len(), 3);
+    }
+
+    #[test]
+    fn scratch_buffers_returned_to_the_pool_are_shrunk() {
+        let mut state = FormatState::new(SimpleFormatContext::default());
+        let mut buffer = VecBuffer::new(&mut state);
+
+        let elements = format_with(|f: &mut Formatter<SimpleFormatContext>| {
+            for _ in 0..5000 {
+                crate::write!(f, [token("a"), soft_line_break_or_space()])?;
+            }
+
+            Ok(())
+        });
+
+        crate::write!(buffer, [block_indent(&elements)]).unwrap();
+
+        assert_eq!(state.scratch_buffers.len(), 1);
+        assert!(state.scratch_buffers[0].capacity() <= ScratchBuffer::<()>::MAX_POOLED_CAPACITY);
+    }
+
+    #[test]
+    fn preamble_buffer_tracks_preamble_and_content_separately() {
+        let mut state = FormatState::new(SimpleFormatContext::default());
+        let mut buffer = VecBuffer::new(&mut state);
+
+        let empty_preamble = format_with(|_: &mut Formatter<SimpleFormatContext>| Ok(()));
+        let mut with_preamble = PreambleBuffer::new(&mut buffer, empty_preamble);
+
+        crate::write!(with_preamble, [expand_parent()]).unwrap();
+        assert!(!with_preamble.did_write_preamble());
+        assert!(!with_preamble.did_write_content());
+
+        crate::write!(with_preamble, [token("a")]).unwrap();
+        assert!(!with_preamble.did_write_preamble());
+        assert!(with_preamble.did_write_content());
+
+        let mut with_preamble = PreambleBuffer::new(&mut buffer, token(","));
+
+        crate::write!(with_preamble, [token("b")]).unwrap();
+        assert!(with_preamble.did_write_preamble());
+        assert!(with_preamble.did_write_content());
+
+        let formatted = Formatted::new(buffer.into_element(), PrinterOptions::default());
+        assert_eq!(formatted.print().as_code(), "a,b");
+    }
+}
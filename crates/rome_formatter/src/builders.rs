@@ -4,7 +4,9 @@ use crate::{
     PreambleBuffer, TextRange, TextSize,
 };
 use crate::{Buffer, VecBuffer};
-use rome_rowan::{Language, SyntaxNode, SyntaxToken, SyntaxTokenText, TextLen};
+use rome_rowan::{
+    Direction, Language, SyntaxNode, SyntaxToken, SyntaxTokenText, SyntaxTriviaPiece, TextLen,
+};
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::marker::PhantomData;
@@ -366,12 +368,198 @@ impl std::fmt::Debug for SyntaxTokenTextSlice {
     }
 }
 
+/// Copies the trimmed text of `token`, without its leading and trailing trivia, 1:1 into the
+/// output text. This is a shorthand for calling [syntax_token_text_slice] with the
+/// [text_trimmed_range](SyntaxToken::text_trimmed_range) of the token.
+///
+/// Unlike [crate::token::format_trimmed_token], this doesn't track the kind of the last
+/// formatted token nor insert a space after a preceding comment.
+///
+/// ## Examples
+///
+/// ```
+/// use rome_formatter::prelude::*;
+/// use rome_formatter::{format, SourceMarker};
+/// use rome_rowan::raw_language::{RawLanguageKind, RawSyntaxTreeBuilder};
+/// use rome_rowan::{TextSize, TriviaPiece};
+///
+/// let mut builder = RawSyntaxTreeBuilder::new();
+/// builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+/// builder.token_with_trivia(
+///     RawLanguageKind::STRING_TOKEN,
+///     "  'abc'",
+///     &[TriviaPiece::whitespace(2)],
+///     &[],
+/// );
+/// builder.finish_node();
+/// let node = builder.finish();
+/// let token = node.first_token().unwrap();
+///
+/// let elements = format!(SimpleFormatContext::default(), [syntax_token(&token)]).unwrap();
+/// let printed = elements.print();
+///
+/// assert_eq!("'abc'", printed.as_code());
+/// assert_eq!(
+///     &SourceMarker {
+///         source: TextSize::from(2),
+///         dest: TextSize::from(0)
+///     },
+///     &printed.sourcemap()[0]
+/// );
+/// ```
+pub fn syntax_token<L: Language>(token: &SyntaxToken<L>) -> SyntaxTokenTextSlice {
+    syntax_token_text_slice(token, token.text_trimmed_range())
+}
+
 fn debug_assert_no_newlines(text: &str) {
     debug_assert!(!text.contains('\r'), "The content '{}' contains an unsupported '\\r' line terminator character but string tokens must only use line feeds '\\n' as line separator. Use '\\n' instead of '\\r' and '\\r\\n' to insert a line break in strings.", text);
 }
 
+/// "Formats" a node according to its original formatting in the source text. Being able to format
+/// a node "as is" is useful if a node contains syntax errors or if there's no formatting
+/// implementation for it yet.
+///
+/// The node's text is copied 1:1, including its comments, except for the whitespace
+/// before the first and after the last comment or token: the formatting of the surrounding
+/// nodes is responsible for it. Line terminators are normalized to `\n`.
+///
+/// You may be inclined to call `node.text` directly. However, using `text` doesn't track the nodes
+/// nor its children source mapping information, resulting in incorrect source maps for this subtree.
+///
+/// The node gets tracked as [FormatElement::Verbatim] and the printer records the range of the
+/// printed text in [crate::Printed::verbatim_ranges], useful to understand if a node still
+/// needs to have its own implementation.
+///
+/// ## Examples
+///
+/// ```
+/// use rome_formatter::prelude::*;
+/// use rome_formatter::{format};
+/// use rome_rowan::raw_language::{RawLanguageKind, RawSyntaxTreeBuilder};
+/// use rome_rowan::{TextRange, TextSize};
+///
+/// let mut builder = RawSyntaxTreeBuilder::new();
+/// builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+/// builder.token(RawLanguageKind::STRING_TOKEN, "'abc'");
+/// builder.finish_node();
+/// let node = builder.finish();
+///
+/// let elements = format!(
+///     SimpleFormatContext::default(),
+///     [token("a"), space_token(), format_verbatim(&node)]
+/// )
+/// .unwrap();
+///
+/// let printed = elements.print();
+///
+/// assert_eq!("a 'abc'", printed.as_code());
+/// assert_eq!(
+///     vec![(TextRange::new(TextSize::from(2), TextSize::from(7)), "'abc'")],
+///     printed.verbatim().collect::<Vec<_>>()
+/// );
+/// ```
+pub fn format_verbatim<L: Language>(node: &SyntaxNode<L>) -> FormatVerbatimNode<L> {
+    FormatVerbatimNode {
+        node,
+        kind: VerbatimKind::Verbatim {
+            length: crate::text_non_whitespace_range(node).len(),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct FormatVerbatimNode<'node, L: Language> {
+    node: &'node SyntaxNode<L>,
+    kind: VerbatimKind,
+}
+
+impl<L: Language> FormatVerbatimNode<'_, L> {
+    /// Changes the reason why the node is formatted verbatim.
+    ///
+    /// Only nodes of kind [VerbatimKind::Verbatim] are recorded in the verbatim ranges
+    /// of the printed result.
+    pub fn with_kind(mut self, kind: VerbatimKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+impl<L: Language, Context> Format<Context> for FormatVerbatimNode<'_, L> {
+    fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
+        for token in self.node.descendants_tokens(Direction::Next) {
+            f.state_mut().track_token(&token);
+        }
+
+        fn skip_whitespace<L: Language>(piece: &SyntaxTriviaPiece<L>) -> bool {
+            piece.is_newline() || piece.is_whitespace()
+        }
+
+        fn write_trivia_token<L: Language, Context>(
+            f: &mut Formatter<Context>,
+            piece: SyntaxTriviaPiece<L>,
+        ) -> FormatResult<()> {
+            syntax_token_cow_slice(
+                normalize_newlines(piece.text(), LINE_TERMINATORS),
+                &piece.token(),
+                piece.text_range().start(),
+            )
+            .fmt(f)
+        }
+
+        let mut buffer = f.scratch_buffer();
+
+        write!(
+            buffer,
+            [format_with(|f: &mut Formatter<Context>| {
+                for leading_trivia in self
+                    .node
+                    .first_leading_trivia()
+                    .into_iter()
+                    .flat_map(|trivia| trivia.pieces())
+                    .skip_while(skip_whitespace)
+                {
+                    write_trivia_token(f, leading_trivia)?;
+                }
+
+                dynamic_token(
+                    &normalize_newlines(&self.node.text_trimmed().to_string(), LINE_TERMINATORS),
+                    self.node.text_trimmed_range().start(),
+                )
+                .fmt(f)?;
+
+                // Clippy false positive: SkipWhile does not implement DoubleEndedIterator
+                #[allow(clippy::needless_collect)]
+                let trailing_trivia: Vec<_> = self
+                    .node
+                    .last_trailing_trivia()
+                    .into_iter()
+                    .flat_map(|trivia| trivia.pieces().rev())
+                    .skip_while(skip_whitespace)
+                    .collect();
+
+                for trailing_trivia in trailing_trivia.into_iter().rev() {
+                    write_trivia_token(f, trailing_trivia)?;
+                }
+
+                Ok(())
+            })]
+        )?;
+
+        let verbatim = Verbatim {
+            content: buffer.into_boxed_slice(),
+            kind: self.kind,
+        };
+
+        f.write_element(FormatElement::Verbatim(verbatim))
+    }
+}
+
 /// Pushes some content to the end of the current line
 ///
+/// The printer flushes all pending line suffixes, in the order they were pushed, right before it
+/// prints the next line break, the next [line_suffix_boundary], or at the end of the document.
+/// Soft line breaks of groups printed in flat mode don't flush the line suffixes.
+///
 /// ## Examples
 ///
 /// ```
@@ -389,6 +577,25 @@ fn debug_assert_no_newlines(text: &str) {
 ///     elements.print().as_code()
 /// );
 /// ```
+///
+/// The line suffix is printed before the hard line break
+///
+/// ```
+/// use rome_formatter::{format};
+/// use rome_formatter::prelude::*;
+///
+/// let elements = format!(SimpleFormatContext::default(), [
+///     token("a"),
+///     line_suffix(&token("c")),
+///     hard_line_break(),
+///     token("b")
+/// ]).unwrap();
+///
+/// assert_eq!(
+///     "ac\nb",
+///     elements.print().as_code()
+/// );
+/// ```
 #[inline]
 pub fn line_suffix<Content, Context>(inner: &Content) -> LineSuffix<Context>
 where
@@ -406,11 +613,11 @@ pub struct LineSuffix<'a, Context> {
 
 impl<Context> Format<Context> for LineSuffix<'_, Context> {
     fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
-        let mut buffer = VecBuffer::new(f.state_mut());
+        let mut buffer = f.scratch_buffer();
         buffer.write_fmt(Arguments::from(&self.content))?;
 
-        let content = buffer.into_vec();
-        f.write_element(FormatElement::LineSuffix(content.into_boxed_slice()))
+        let content = buffer.into_boxed_slice();
+        f.write_element(FormatElement::LineSuffix(content))
     }
 }
 
@@ -508,12 +715,12 @@ pub struct FormatComment<'a, Context> {
 
 impl<Context> Format<Context> for FormatComment<'_, Context> {
     fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
-        let mut buffer = VecBuffer::new(f.state_mut());
+        let mut buffer = f.scratch_buffer();
 
         buffer.write_fmt(Arguments::from(&self.content))?;
-        let content = buffer.into_vec();
+        let content = buffer.into_boxed_slice();
 
-        f.write_element(FormatElement::Comment(content.into_boxed_slice()))
+        f.write_element(FormatElement::Comment(content))
     }
 }
 
@@ -645,16 +852,16 @@ pub struct Indent<'a, Context> {
 
 impl<Context> Format<Context> for Indent<'_, Context> {
     fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
-        let mut buffer = VecBuffer::new(f.state_mut());
+        let mut buffer = f.scratch_buffer();
 
-        buffer.write_fmt(Arguments::from(&self.content))?;
+        write_indented(&mut buffer, &self.content)?;
 
         if buffer.is_empty() {
             return Ok(());
         }
 
-        let content = buffer.into_vec();
-        f.write_element(FormatElement::Indent(content.into_boxed_slice()))
+        let content = buffer.into_boxed_slice();
+        f.write_element(FormatElement::Indent(content))
     }
 }
 
@@ -664,6 +871,79 @@ impl<Context> std::fmt::Debug for Indent<'_, Context> {
     }
 }
 
+/// Writes `content` to `buffer` with the indent level of the [crate::FormatState] incremented by one
+fn write_indented<Context>(
+    buffer: &mut dyn Buffer<Context = Context>,
+    content: &Argument<Context>,
+) -> FormatResult<()> {
+    buffer.state_mut().indent_level += 1;
+    let result = buffer.write_fmt(Arguments::from(content));
+    buffer.state_mut().indent_level -= 1;
+
+    result
+}
+
+/// Aligns the lines of the content that follow a line break with the column at which the
+/// content starts, by printing spaces after the indention of these lines.
+///
+/// It doesn't add any line breaks at the edges of the content.
+///
+/// # Examples
+///
+/// ```
+/// use rome_formatter::{format, format_args};
+/// use rome_formatter::prelude::*;
+///
+/// let block = format!(SimpleFormatContext::default(), [
+///     token("let a = "),
+///     align(&format_args![
+///         token("/**"),
+///         hard_line_break(),
+///         token(" */"),
+///     ])
+/// ]).unwrap();
+///
+/// assert_eq!(
+///     "let a = /**\n         */",
+///     block.print().as_code()
+/// );
+/// ```
+#[inline]
+pub fn align<Content, Context>(content: &Content) -> Align<Context>
+where
+    Content: Format<Context>,
+{
+    Align {
+        content: Argument::new(content),
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Align<'a, Context> {
+    content: Argument<'a, Context>,
+}
+
+impl<Context> Format<Context> for Align<'_, Context> {
+    fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
+        let mut buffer = f.scratch_buffer();
+
+        buffer.write_fmt(Arguments::from(&self.content))?;
+
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        let content = buffer.into_boxed_slice();
+        f.write_element(FormatElement::Align(content))
+    }
+}
+
+impl<Context> std::fmt::Debug for Align<'_, Context> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Align").field(&"{{content}}").finish()
+    }
+}
+
 /// Inserts a hard line break before and after the content and increases the indention level for the content by one.
 ///
 /// Block indents indent a block of code, such as in a function body, and therefore insert a line
@@ -851,7 +1131,7 @@ enum IndentMode {
 
 impl<Context> Format<Context> for BlockIndent<'_, Context> {
     fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
-        let mut buffer = VecBuffer::new(f.state_mut());
+        let mut buffer = f.scratch_buffer();
 
         match self.mode {
             IndentMode::Soft => write!(buffer, [soft_line_break()])?,
@@ -859,16 +1139,16 @@ impl<Context> Format<Context> for BlockIndent<'_, Context> {
             IndentMode::SoftLineOrSpace => write!(buffer, [soft_line_break_or_space()])?,
         };
 
-        buffer.write_fmt(Arguments::from(&self.content))?;
+        write_indented(&mut buffer, &self.content)?;
 
         // Don't create an indent if the content is empty
         if buffer.len() == 1 {
             return Ok(());
         }
 
-        let content = buffer.into_vec();
+        let content = buffer.into_boxed_slice();
 
-        f.write_element(FormatElement::Indent(content.into_boxed_slice()))?;
+        f.write_element(FormatElement::Indent(content))?;
 
         match self.mode {
             IndentMode::Soft => write!(f, [soft_line_break()])?,
@@ -1038,6 +1318,11 @@ impl<'inner, Context> GroupElementsBuffer<'inner, Context> {
         self.content
     }
 
+    fn push_content(&mut self, element: FormatElement) {
+        self.inner.state_mut().track_element(&element);
+        self.content.push(element);
+    }
+
     fn write_interned(&mut self, interned: Interned) -> FormatResult<()> {
         debug_assert!(self.content.is_empty());
 
@@ -1075,7 +1360,7 @@ impl<'inner, Context> GroupElementsBuffer<'inner, Context> {
 
                 // No leading comments, this group has no comments
                 if content_start == 0 {
-                    self.content.push(FormatElement::Interned(interned));
+                    self.push_content(FormatElement::Interned(interned));
                     return Ok(());
                 }
 
@@ -1086,7 +1371,7 @@ impl<'inner, Context> GroupElementsBuffer<'inner, Context> {
             }
             FormatElement::Interned(interned) => self.write_interned(interned.clone()),
             _ => {
-                self.content.push(FormatElement::Interned(interned));
+                self.push_content(FormatElement::Interned(interned));
                 Ok(())
             }
         }
@@ -1109,9 +1394,11 @@ impl<Context> Buffer for GroupElementsBuffer<'_, Context> {
                 comment @ FormatElement::Comment { .. } => {
                     self.inner.write_element(comment)?;
                 }
-                element => self.content.push(element),
+                element => self.push_content(element),
             }
         } else {
+            self.inner.state_mut().track_element(&element);
+
             match element {
                 FormatElement::List(list) => {
                     self.content.extend(list.into_vec());
@@ -1419,7 +1706,7 @@ impl<Context> IfGroupBreaks<'_, Context> {
 
 impl<Context> Format<Context> for IfGroupBreaks<'_, Context> {
     fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
-        let mut buffer = VecBuffer::new(f.state_mut());
+        let mut buffer = f.scratch_buffer();
 
         buffer.write_fmt(Arguments::from(&self.content))?;
 
@@ -1427,10 +1714,9 @@ impl<Context> Format<Context> for IfGroupBreaks<'_, Context> {
             return Ok(());
         }
 
-        let content = buffer.into_vec();
+        let content = buffer.into_boxed_slice();
         f.write_element(FormatElement::ConditionalGroupContent(
-            ConditionalGroupContent::new(content.into_boxed_slice(), self.mode)
-                .with_group_id(self.group_id),
+            ConditionalGroupContent::new(content, self.mode).with_group_id(self.group_id),
         ))
     }
 }
@@ -1683,6 +1969,96 @@ where
     }
 }
 
+/// Builder to join together a sequence of content that gets formatted only once.
+/// See [Formatter::join_memoized_with]
+#[must_use = "must eventually call `finish()` on Format builders"]
+pub struct JoinMemoizedBuilder<'fmt, 'buf, Context> {
+    result: FormatResult<()>,
+    fmt: &'fmt mut Formatter<'buf, Context>,
+    separator: Interned,
+    entries: Vec<Interned>,
+}
+
+impl<'fmt, 'buf, Context> JoinMemoizedBuilder<'fmt, 'buf, Context> {
+    pub(crate) fn new<Separator>(
+        fmt: &'fmt mut Formatter<'buf, Context>,
+        separator: Separator,
+    ) -> Self
+    where
+        Separator: Format<Context>,
+    {
+        let mut buffer = VecBuffer::new(fmt.state_mut());
+        let result = write!(buffer, [separator]);
+        let separator = buffer.into_element().intern();
+
+        Self {
+            result,
+            fmt,
+            separator,
+            entries: vec![],
+        }
+    }
+
+    /// Formats the entry and adds it to the join output.
+    pub fn entry(&mut self, entry: &dyn Format<Context>) -> &mut Self {
+        self.result = self.result.and_then(|_| {
+            let entry = self.fmt.intern(entry)?;
+            self.entries.push(entry);
+
+            Ok(())
+        });
+
+        self
+    }
+
+    /// Adds the contents of an iterator of entries to the join output.
+    pub fn entries<F, I>(&mut self, entries: I) -> &mut Self
+    where
+        F: Format<Context>,
+        I: IntoIterator<Item = F>,
+    {
+        for entry in entries {
+            self.entry(&entry);
+        }
+
+        self
+    }
+
+    /// Finishes the output and returns the joined entries or any error encountered.
+    pub fn finish(&mut self) -> FormatResult<JoinedEntries> {
+        self.result?;
+
+        Ok(JoinedEntries {
+            separator: self.separator.clone(),
+            entries: std::mem::take(&mut self.entries),
+        })
+    }
+}
+
+/// The entries joined by a [JoinMemoizedBuilder].
+///
+/// Writing the entries only copies the formatted content of the entries and separators.
+/// It can be written multiple times, for example in the variants of a [BestFitting].
+#[derive(Debug, Clone)]
+pub struct JoinedEntries {
+    separator: Interned,
+    entries: Vec<Interned>,
+}
+
+impl<Context> Format<Context> for JoinedEntries {
+    fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
+        for (index, entry) in self.entries.iter().enumerate() {
+            if index > 0 {
+                f.write_element(FormatElement::Interned(self.separator.clone()))?;
+            }
+
+            f.write_element(FormatElement::Interned(entry.clone()))?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Builder to join together nodes that ensures that nodes separated by empty lines continue
 /// to be separated by empty lines in the formatted output.
 #[must_use = "must eventually call `finish()` on Format builders"]
@@ -1710,12 +2086,27 @@ where
     /// Adds a new node with the specified formatted content to the output, respecting any new lines
     /// that appear before the node in the input source.
     pub fn entry<L: Language>(&mut self, node: &SyntaxNode<L>, content: &dyn Format<Context>) {
+        self.entry_with_options(node, content, true)
+    }
+
+    /// Adds a new node with the specified formatted content to the output. Empty lines before the
+    /// node in the input source are only preserved if `allow_blank_lines` is `true`, otherwise
+    /// the node is always preceded by the separator.
+    ///
+    /// This is useful for lists where some nodes must never be separated by an empty line, for
+    /// example the signatures of an overloaded function.
+    pub fn entry_with_options<L: Language>(
+        &mut self,
+        node: &SyntaxNode<L>,
+        content: &dyn Format<Context>,
+        allow_blank_lines: bool,
+    ) {
         self.result = self.result.and_then(|_| {
             let mut buffer = PreambleBuffer::new(
                 self.fmt,
                 format_with(|f| {
                     if self.has_elements {
-                        if get_lines_before(node) > 1 {
+                        if allow_blank_lines && get_lines_before(node) > 1 {
                             write!(f, [empty_line()])?;
                         } else {
                             self.separator.fmt(f)?;
@@ -1728,7 +2119,7 @@ where
 
             write!(buffer, [content])?;
 
-            self.has_elements = self.has_elements || buffer.did_write_preamble();
+            self.has_elements = self.has_elements || buffer.did_write_content();
 
             Ok(())
         });
@@ -1771,6 +2162,24 @@ pub fn get_lines_before<L: Language>(next_node: &SyntaxNode<L>) -> usize {
     }
 }
 
+/// Get the number of line breaks in the leading trivia of a SyntaxNode, including the line breaks
+/// that come before, between, and after its leading comments.
+///
+/// Unlike [get_lines_before], which stops counting at the first comment, this stops only at the
+/// first skipped token trivia. This is useful to know if the source has a blank line before a
+/// block of leading comments.
+pub fn get_lines_before_comments<L: Language>(next_node: &SyntaxNode<L>) -> usize {
+    if let Some(leading_trivia) = next_node.first_leading_trivia() {
+        leading_trivia
+            .pieces()
+            .take_while(|piece| !piece.is_skipped())
+            .filter(|piece| piece.is_newline())
+            .count()
+    } else {
+        0
+    }
+}
+
 /// Builder to fill as many elements as possible on a single line.
 #[must_use = "must eventually call `finish()` on Format builders"]
 pub struct FillBuilder<'fmt, 'buf, Context> {
@@ -1780,6 +2189,8 @@ pub struct FillBuilder<'fmt, 'buf, Context> {
     /// The separator to use to join the elements
     separator: FormatElement,
     items: Vec<FormatElement>,
+    /// The mode of the separator preceding each item
+    separator_modes: Vec<FillSeparatorMode>,
 }
 
 impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
@@ -1799,6 +2210,7 @@ impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
             fmt,
             separator,
             items: vec![],
+            separator_modes: vec![],
         }
     }
 
@@ -1841,7 +2253,10 @@ impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
             let mut buffer = VecBuffer::new(self.fmt.state_mut());
             write!(buffer, [entry])?;
 
-            self.items.extend(buffer.into_vec());
+            let items = buffer.into_vec();
+            self.separator_modes
+                .extend(items.iter().map(|_| FillSeparatorMode::Auto));
+            self.items.extend(items);
 
             Ok(())
         });
@@ -1851,6 +2266,34 @@ impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
 
     /// Adds a new entry to the fill output.
     pub fn entry(&mut self, entry: &dyn Format<Context>) -> &mut Self {
+        self.entry_with_separator_mode(FillSeparatorMode::Auto, entry)
+    }
+
+    /// Adds a new entry to the fill output, printing the separator between the previous entry
+    /// and this one according to `separator_mode`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use rome_formatter::prelude::*;
+    /// use rome_formatter::format;
+    ///
+    /// let formatted = format!(SimpleFormatContext::default(), [format_with(|f| {
+    ///     f.fill(soft_line_break_or_space())
+    ///         .entry(&token("a"))
+    ///         .entry(&token("b"))
+    ///         .entry_with_separator_mode(FillSeparatorMode::Expanded, &token("c"))
+    ///         .entry(&token("d"))
+    ///         .finish()
+    /// })]).unwrap();
+    ///
+    /// assert_eq!("a b\nc d", formatted.print().as_code());
+    /// ```
+    pub fn entry_with_separator_mode(
+        &mut self,
+        separator_mode: FillSeparatorMode,
+        entry: &dyn Format<Context>,
+    ) -> &mut Self {
         self.result = self.result.and_then(|_| {
             let mut buffer = VecBuffer::new(self.fmt.state_mut());
             write!(buffer, [entry])?;
@@ -1859,6 +2302,7 @@ impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
 
             if !item.is_empty() {
                 self.items.push(item);
+                self.separator_modes.push(separator_mode);
             }
 
             Ok(())
@@ -1871,6 +2315,7 @@ impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
     pub fn finish(&mut self) -> FormatResult<()> {
         self.result.and_then(|_| {
             let mut items = std::mem::take(&mut self.items);
+            let separator_modes = std::mem::take(&mut self.separator_modes);
 
             match items.len() {
                 0 => Ok(()),
@@ -1878,6 +2323,7 @@ impl<'a, 'buf, Context> FillBuilder<'a, 'buf, Context> {
                 _ => self.fmt.write_element(FormatElement::Fill(Fill {
                     content: items.into_boxed_slice(),
                     separator: Box::new(self.separator.clone()),
+                    separator_modes: separator_modes.into_boxed_slice(),
                 })),
             }
         })
@@ -1936,3 +2382,237 @@ impl<Context> Format<Context> for BestFitting<'_, Context> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{TextRange, TextSize};
+    use rome_rowan::raw_language::{RawLanguageKind, RawSyntaxTreeBuilder};
+    use rome_rowan::TriviaPiece;
+    use std::cell::Cell;
+
+    #[test]
+    fn join_memoized_with_formats_entries_once() {
+        let count = Cell::new(0);
+        let entry = format_with(|f: &mut Formatter<SimpleFormatContext>| {
+            count.set(count.get() + 1);
+            crate::write!(f, [token("aaaaaaaaaa")])
+        });
+
+        let formatted = crate::format!(
+            SimpleFormatContext::default(),
+            [format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                let joined = f
+                    .join_memoized_with(soft_line_break_or_space())
+                    .entries(std::iter::repeat(&entry).take(10))
+                    .finish()?;
+
+                crate::write!(
+                    f,
+                    [best_fitting!(
+                        crate::format_args!(token("["), joined, token("]")),
+                        crate::format_args!(
+                            token("["),
+                            group_elements(&soft_block_indent(&joined)),
+                            token("]")
+                        ),
+                        crate::format_args!(token("["), block_indent(&joined), token("]"))
+                    )]
+                )
+            })]
+        )
+        .unwrap();
+
+        assert_eq!(count.get(), 10);
+        assert_eq!(
+            formatted.print().as_code(),
+            std::format!("[\n{}]", "\taaaaaaaaaa\n".repeat(10))
+        );
+    }
+
+    #[test]
+    fn lines_before_with_leading_comment() {
+        let mut builder = RawSyntaxTreeBuilder::new();
+
+        builder.start_node(RawLanguageKind::ROOT);
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.token_with_trivia(
+            RawLanguageKind::STRING_TOKEN,
+            "\n\n// c\na",
+            &[
+                TriviaPiece::newline(1),
+                TriviaPiece::newline(1),
+                TriviaPiece::single_line_comment(4),
+                TriviaPiece::newline(1),
+            ],
+            &[],
+        );
+        builder.finish_node();
+        builder.finish_node();
+
+        let root = builder.finish();
+        let node = root.first_child().unwrap();
+
+        assert_eq!(get_lines_before(&node), 2);
+        assert_eq!(get_lines_before_comments(&node), 3);
+    }
+
+    #[test]
+    fn format_verbatim_records_printed_range() {
+        let mut builder = RawSyntaxTreeBuilder::new();
+
+        builder.start_node(RawLanguageKind::ROOT);
+        builder.start_node(RawLanguageKind::UNKNOWN);
+        builder.token_with_trivia(
+            RawLanguageKind::STRING_TOKEN,
+            "\n/* a */ 'x'",
+            &[
+                TriviaPiece::newline(1),
+                TriviaPiece::multi_line_comment(7),
+                TriviaPiece::whitespace(1),
+            ],
+            &[],
+        );
+        builder.token_with_trivia(
+            RawLanguageKind::PLUS_TOKEN,
+            " + /* b */ ",
+            &[TriviaPiece::whitespace(1)],
+            &[
+                TriviaPiece::whitespace(1),
+                TriviaPiece::multi_line_comment(7),
+                TriviaPiece::whitespace(1),
+            ],
+        );
+        builder.finish_node();
+        builder.finish_node();
+
+        let root = builder.finish();
+        let node = root.first_child().unwrap();
+
+        let formatted = crate::format!(
+            SimpleFormatContext::default(),
+            [token("a"), space_token(), format_verbatim(&node)]
+        )
+        .unwrap();
+
+        let printed = formatted.print();
+
+        assert_eq!(printed.as_code(), "a /* a */ 'x' + /* b */");
+        assert_eq!(
+            printed.verbatim().collect::<Vec<_>>(),
+            vec![(
+                TextRange::new(TextSize::from(2), TextSize::from(23)),
+                "/* a */ 'x' + /* b */"
+            )]
+        );
+    }
+
+    #[test]
+    fn join_nodes_skips_separator_for_entries_without_content() {
+        let mut builder = RawSyntaxTreeBuilder::new();
+
+        builder.start_node(RawLanguageKind::ROOT);
+        for text in ["a", "b", "c", "d"] {
+            builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+            builder.token(RawLanguageKind::STRING_TOKEN, text);
+            builder.finish_node();
+        }
+        builder.finish_node();
+
+        let root = builder.finish();
+        let nodes: Vec<_> = root.children().collect();
+
+        let empty = format_with(|_: &mut Formatter<SimpleFormatContext>| Ok(()));
+
+        let formatted = crate::format!(
+            SimpleFormatContext::default(),
+            [format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                let mut join = f.join_nodes_with_soft_line();
+
+                join.entry(&nodes[0], &empty);
+                join.entry(&nodes[1], &token("b"));
+                join.entry(&nodes[2], &empty);
+                join.entry(&nodes[3], &token("d"));
+
+                join.finish()
+            })]
+        )
+        .unwrap();
+
+        assert_eq!(formatted.print().as_code(), "b\nd");
+    }
+
+    #[test]
+    fn join_nodes_skips_separator_for_entries_that_only_expand_the_parent() {
+        let mut builder = RawSyntaxTreeBuilder::new();
+
+        builder.start_node(RawLanguageKind::ROOT);
+        for text in ["a", "b", "c"] {
+            builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+            builder.token(RawLanguageKind::STRING_TOKEN, text);
+            builder.finish_node();
+        }
+        builder.finish_node();
+
+        let root = builder.finish();
+        let nodes: Vec<_> = root.children().collect();
+
+        let formatted = crate::format!(
+            SimpleFormatContext::default(),
+            [format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                let mut join = JoinNodesBuilder::new(token(","), f);
+
+                join.entry(&nodes[0], &token("a"));
+                join.entry(&nodes[1], &expand_parent());
+                join.entry(&nodes[2], &token("c"));
+
+                join.finish()
+            })]
+        )
+        .unwrap();
+
+        assert_eq!(formatted.print().as_code(), "a,c");
+    }
+
+    #[test]
+    fn join_nodes_entry_with_options_collapses_blank_lines() {
+        let mut builder = RawSyntaxTreeBuilder::new();
+
+        builder.start_node(RawLanguageKind::ROOT);
+        for text in ["a", "b", "c", "d"] {
+            builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+            if text == "a" {
+                builder.token(RawLanguageKind::STRING_TOKEN, text);
+            } else {
+                builder.token_with_trivia(
+                    RawLanguageKind::STRING_TOKEN,
+                    &std::format!("\n\n{text}"),
+                    &[TriviaPiece::newline(1), TriviaPiece::newline(1)],
+                    &[],
+                );
+            }
+            builder.finish_node();
+        }
+        builder.finish_node();
+
+        let root = builder.finish();
+        let nodes: Vec<_> = root.children().collect();
+
+        let formatted = crate::format!(
+            SimpleFormatContext::default(),
+            [format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                let mut join = f.join_nodes_with_hardline();
+
+                join.entry_with_options(&nodes[0], &token("a"), true);
+                join.entry_with_options(&nodes[1], &token("b"), false);
+                join.entry_with_options(&nodes[2], &token("c"), true);
+                join.entry_with_options(&nodes[3], &token("d"), false);
+
+                join.finish()
+            })]
+        )
+        .unwrap();
+
+        assert_eq!(formatted.print().as_code(), "a\nb\n\nc\nd");
+    }
+}
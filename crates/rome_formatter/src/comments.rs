@@ -1,5 +1,7 @@
-use crate::CommentStyle;
-use rome_rowan::{Language, SyntaxTriviaPieceComments};
+use crate::prelude::*;
+use crate::{write, CommentStyle};
+use rome_rowan::{Language, SyntaxTriviaPieceComments, TextSize};
+use std::borrow::Cow;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum CommentKind {
@@ -39,6 +41,31 @@ pub enum CommentKind {
     Line,
 }
 
+/// Placement of an inline comment relative to the tokens surrounding it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CommentPlacement {
+    /// The comment is separated by a space from the tokens before and after it.
+    ///
+    /// ```javascript
+    /// a /* comment */ + b
+    /// ```
+    Spaced,
+
+    /// The comment directly follows the token before it, without a space.
+    ///
+    /// ```javascript
+    /// call(/* comment */ a)
+    /// ```
+    AttachedToPrevious,
+
+    /// The comment is directly followed by the token after it, without a space.
+    ///
+    /// ```javascript
+    /// call(a /* comment */)
+    /// ```
+    AttachedToNext,
+}
+
 #[derive(Debug, Clone)]
 pub struct SourceComment<L: Language> {
     /// The number of lines appearing before this comment
@@ -114,3 +141,175 @@ pub trait CommentContext<L: Language> {
 
     fn comment_style(&self) -> Self::Style;
 }
+
+/// Formats a block comment, re-aligning the lines of JSDoc-style comments.
+///
+/// A block comment is JSDoc-style if it spans multiple lines and all its lines, except the first,
+/// start with a `*`. The lines of such a comment are re-indented so that their `*` align one
+/// column after the start of the comment. Other comments are printed verbatim.
+///
+/// ```javascript
+/// function a() {
+///         /**
+///    * Re-aligned
+///    */
+/// }
+/// ```
+///
+/// Becomes
+///
+/// ```javascript
+/// function a() {
+///   /**
+///    * Re-aligned
+///    */
+/// }
+/// ```
+pub fn format_block_comment<L: Language>(
+    comment: &SyntaxTriviaPieceComments<L>,
+) -> FormatBlockComment<L> {
+    FormatBlockComment { comment }
+}
+
+pub struct FormatBlockComment<'a, L: Language> {
+    comment: &'a SyntaxTriviaPieceComments<L>,
+}
+
+impl<L: Language, Context> Format<Context> for FormatBlockComment<'_, L> {
+    fn fmt(&self, f: &mut Formatter<Context>) -> FormatResult<()> {
+        let text = self.comment.text();
+
+        if !is_jsdoc_comment(text) {
+            return write!(f, [self.comment]);
+        }
+
+        let comment_token = self.comment.as_piece().token();
+        let start = self.comment.text_range().start();
+
+        let lines = format_with(|f: &mut Formatter<Context>| {
+            let mut line_start = 0;
+
+            for (index, line) in text.split('\n').enumerate() {
+                let trimmed_start = if index == 0 { line } else { line.trim_start() };
+                let trimmed = trimmed_start.trim_end();
+                let position =
+                    start + TextSize::from((line_start + line.len() - trimmed_start.len()) as u32);
+
+                if index > 0 {
+                    // A space token would be dropped at the start of the line, use a token to
+                    // align the `*` with the start of the comment instead
+                    write!(f, [hard_line_break(), token(" ")])?;
+                }
+
+                write!(
+                    f,
+                    [syntax_token_cow_slice(
+                        Cow::Borrowed(trimmed),
+                        &comment_token,
+                        position
+                    )]
+                )?;
+
+                line_start += line.len() + 1;
+            }
+
+            Ok(())
+        });
+
+        write!(f, [align(&lines)])
+    }
+}
+
+/// Returns `true` if `text` is a multiline block comment with all its lines, except the first,
+/// starting with a `*`
+fn is_jsdoc_comment(text: &str) -> bool {
+    let mut lines = text.lines();
+
+    match lines.next() {
+        Some(first) if first.starts_with("/*") => {}
+        _ => return false,
+    }
+
+    let mut has_lines = false;
+
+    for line in lines {
+        if !line.trim_start().starts_with('*') {
+            return false;
+        }
+
+        has_lines = true;
+    }
+
+    has_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_block_comment;
+    use crate::prelude::*;
+    use rome_rowan::raw_language::{RawLanguageKind, RawSyntaxTreeBuilder};
+    use rome_rowan::{TextSize, TriviaPiece};
+
+    fn format_comment(comment: &str) -> String {
+        format_comment_after("", comment)
+    }
+
+    fn format_comment_after(prefix: &'static str, comment: &str) -> String {
+        let mut builder = RawSyntaxTreeBuilder::new();
+        builder.start_node(RawLanguageKind::ROOT);
+        builder.token_with_trivia(
+            RawLanguageKind::STRING_TOKEN,
+            &std::format!("{comment}a"),
+            &[TriviaPiece::multi_line_comment(TextSize::of(comment))],
+            &[],
+        );
+        builder.finish_node();
+
+        let root = builder.finish();
+        let syntax_token = root.first_token().unwrap();
+        let piece = syntax_token.leading_trivia().pieces().next().unwrap();
+        let comment = piece.as_comments().unwrap();
+
+        let formatted = crate::format!(
+            SimpleFormatContext::default(),
+            [
+                token("{"),
+                block_indent(&crate::format_args![
+                    token(prefix),
+                    format_block_comment(&comment)
+                ]),
+                token("}")
+            ]
+        )
+        .unwrap();
+
+        formatted.print().as_code().to_string()
+    }
+
+    #[test]
+    fn realigns_jsdoc_comment() {
+        assert_eq!(
+            format_comment("/**\n        * Description\n      *\n   * @param a   \n*/"),
+            "{\n\t/**\n\t * Description\n\t *\n\t * @param a\n\t */\n}"
+        );
+    }
+
+    #[test]
+    fn aligns_jsdoc_comment_with_its_start() {
+        assert_eq!(
+            format_comment_after("a = ", "/**\n * Description\n */"),
+            "{\n\ta = /**\n\t     * Description\n\t     */\n}"
+        );
+    }
+
+    #[test]
+    fn preserves_block_comment() {
+        let comment = "/* first line\n        second line\n   */";
+
+        assert_eq!(format_comment(comment), std::format!("{{\n\t{comment}\n}}"));
+        assert_eq!(
+            format_comment("/* single line */"),
+            "{\n\t/* single line */\n}"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use similar::{capture_diff_slices, Algorithm, DiffTag};
+use std::ops::Range;
+
+/// A change between the original source text of a file and the output of the formatter
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DiffHunk {
+    /// The lines in the `original` range of the source text are replaced with the lines in the
+    /// `formatted` range of the output. Both ranges are zero-based and exclusive, and are empty
+    /// for pure insertions and deletions respectively.
+    Lines {
+        original: Range<usize>,
+        formatted: Range<usize>,
+    },
+    /// Only one of the source text and the output end with a newline. `formatted` is `true` if the
+    /// formatter adds a trailing newline, `false` if it removes it.
+    TrailingNewline { formatted: bool },
+}
+
+/// Computes the list of [DiffHunk] required to turn `original` into `formatted`, sorted by their
+/// position in the document.
+///
+/// Lines are compared without their trailing `\n` so that a missing newline at the end of the
+/// file doesn't cause the last line to be reported as changed: this case is reported
+/// with [DiffHunk::TrailingNewline] instead. A preceding `\r` is kept, so that changing the
+/// line endings of a file is reported as a change of its lines.
+pub(crate) fn diff_lines(original: &str, formatted: &str) -> Vec<DiffHunk> {
+    let original_lines = split_lines(original);
+    let formatted_lines = split_lines(formatted);
+
+    let mut hunks: Vec<DiffHunk> = Vec::new();
+
+    for op in capture_diff_slices(Algorithm::Myers, &original_lines, &formatted_lines) {
+        if op.tag() == DiffTag::Equal {
+            continue;
+        }
+
+        let (old_range, new_range) = (op.old_range(), op.new_range());
+
+        // Merge a deletion directly followed by an insertion in a single hunk
+        if let Some(DiffHunk::Lines {
+            original,
+            formatted,
+        }) = hunks.last_mut()
+        {
+            if original.end == old_range.start && formatted.end == new_range.start {
+                original.end = old_range.end;
+                formatted.end = new_range.end;
+                continue;
+            }
+        }
+
+        hunks.push(DiffHunk::Lines {
+            original: old_range,
+            formatted: new_range,
+        });
+    }
+
+    let formatted_newline = formatted.ends_with('\n');
+    if original.ends_with('\n') != formatted_newline {
+        hunks.push(DiffHunk::TrailingNewline {
+            formatted: formatted_newline,
+        });
+    }
+
+    hunks
+}
+
+/// Splits `text` into its lines, keeping the `\r` of CRLF line endings
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n')
+        .map(|line| line.strip_suffix('\n').unwrap_or(line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_lines, DiffHunk};
+
+    #[test]
+    fn diff_reformatted_file() {
+        let original = "let a = 1\nlet b = 2;\ncall(\n\tc,\n);\nlet d = 4;";
+        let formatted = "let a = 1;\nlet b = 2;\ncall(c);\nlet d = 4;\n";
+
+        assert_eq!(
+            diff_lines(original, formatted),
+            [
+                DiffHunk::Lines {
+                    original: 0..1,
+                    formatted: 0..1,
+                },
+                DiffHunk::Lines {
+                    original: 2..5,
+                    formatted: 2..3,
+                },
+                DiffHunk::TrailingNewline { formatted: true },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_line_endings() {
+        let original = "let a = 1;\r\nlet b = 2;\r\n";
+        let formatted = "let a = 1;\nlet b = 2;\n";
+
+        assert_eq!(
+            diff_lines(original, formatted),
+            [DiffHunk::Lines {
+                original: 0..2,
+                formatted: 0..2,
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_formatted_file() {
+        let content = "let a = 1;\n\nlet b = 2;\n";
+
+        assert!(diff_lines(content, content).is_empty());
+    }
+}
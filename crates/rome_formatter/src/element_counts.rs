@@ -0,0 +1,110 @@
+use crate::format_element::FormatElement;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Counts the [FormatElement]s written while formatting a document, grouped by kind.
+///
+/// This is only tracked in debug builds to help finding out why the IR of a document is large.
+/// The elements are counted when they get written to a buffer storing them: the elements of a
+/// [FormatElement::List] are counted one by one, and the content of an element is counted when it
+/// gets written to the buffer of its parent element. Elements moved from one buffer to another
+/// are counted again.
+#[derive(Debug, Clone, Default)]
+pub struct ElementCounts {
+    /// Key: The name of the [FormatElement] variant
+    counts: BTreeMap<&'static str, usize>,
+}
+
+impl ElementCounts {
+    /// Tracks a written element
+    pub fn track(&mut self, element: &FormatElement) {
+        match element {
+            FormatElement::List(list) => {
+                for element in list.iter() {
+                    self.track(element);
+                }
+            }
+            element => {
+                *self.counts.entry(element_kind(element)).or_default() += 1;
+            }
+        }
+    }
+
+    /// Returns the number of written elements of the given kind, for example `"Token"`
+    pub fn get(&self, kind: &str) -> usize {
+        self.counts.get(kind).copied().unwrap_or_default()
+    }
+
+    /// Returns the total number of written elements
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+impl fmt::Display for ElementCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (kind, count) in &self.counts {
+            writeln!(f, "{kind}: {count}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn element_kind(element: &FormatElement) -> &'static str {
+    match element {
+        FormatElement::Space => "Space",
+        FormatElement::Line(_) => "Line",
+        FormatElement::Indent(_) => "Indent",
+        FormatElement::Align(_) => "Align",
+        FormatElement::Group(_) => "Group",
+        FormatElement::ExpandParent => "ExpandParent",
+        FormatElement::ConditionalGroupContent(_) => "ConditionalGroupContent",
+        FormatElement::List(_) => "List",
+        FormatElement::Fill(_) => "Fill",
+        FormatElement::Token(_) => "Token",
+        FormatElement::LineSuffix(_) => "LineSuffix",
+        FormatElement::LineSuffixBoundary => "LineSuffixBoundary",
+        FormatElement::Comment(_) => "Comment",
+        FormatElement::Verbatim(_) => "Verbatim",
+        FormatElement::BestFitting(_) => "BestFitting",
+        FormatElement::Interned(_) => "Interned",
+        FormatElement::Label(_) => "Label",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{format_args, write, FormatState, SimpleFormatContext, VecBuffer};
+
+    #[test]
+    fn counts_written_elements() {
+        let mut state = FormatState::new(SimpleFormatContext::default());
+        let mut buffer = VecBuffer::new(&mut state);
+
+        write!(
+            &mut buffer,
+            [
+                group_elements(&format_args![
+                    token("a"),
+                    soft_line_break_or_space(),
+                    token("b")
+                ]),
+                hard_line_break(),
+                token("c")
+            ]
+        )
+        .unwrap();
+
+        drop(buffer);
+
+        let counts = state.element_counts();
+
+        assert_eq!(counts.get("Token"), 3);
+        assert_eq!(counts.get("Line"), 2);
+        assert_eq!(counts.get("Group"), 1);
+        assert_eq!(counts.total(), 6);
+        assert_eq!(counts.to_string(), "Group: 1\nLine: 2\nToken: 3\n");
+    }
+}
@@ -26,6 +26,10 @@ pub enum FormatElement {
     /// Indents the content one level deeper, see [crate::indent] for documentation and examples.
     Indent(Content),
 
+    /// Aligns the lines of the content following a line break with the column at which the
+    /// content starts, see [crate::align] for documentation and examples.
+    Align(Content),
+
     /// Creates a logical group where its content is either consistently printed:
     /// * on a single line: Omitting `LineMode::Soft` line breaks and printing spaces for `LineMode::SoftOrSpace`
     /// * on multiple lines: Printing all line breaks
@@ -133,6 +137,7 @@ impl Debug for FormatElement {
             FormatElement::Space => write!(fmt, "Space"),
             FormatElement::Line(content) => fmt.debug_tuple("Line").field(content).finish(),
             FormatElement::Indent(content) => fmt.debug_tuple("Indent").field(content).finish(),
+            FormatElement::Align(content) => fmt.debug_tuple("Align").field(content).finish(),
             FormatElement::Group(content) => {
                 write!(fmt, "Group")?;
                 content.fmt(fmt)
@@ -217,6 +222,8 @@ impl Deref for List {
 pub struct Fill {
     pub(super) content: Content,
     pub(super) separator: Box<FormatElement>,
+    /// The mode of the separator preceding each item of `content`. The mode of the first item is unused.
+    pub(super) separator_modes: Box<[FillSeparatorMode]>,
 }
 
 impl Fill {
@@ -227,6 +234,36 @@ impl Fill {
     pub fn separator(&self) -> &FormatElement {
         &self.separator
     }
+
+    /// Returns the mode of the separator between the item at `index` and the previous item
+    pub fn separator_mode(&self, index: usize) -> FillSeparatorMode {
+        self.separator_modes[index]
+    }
+
+    /// Returns `true` if any of the separators must be printed in expanded mode
+    pub fn has_expanded_separator(&self) -> bool {
+        self.separator_modes
+            .iter()
+            .skip(1)
+            .any(|mode| *mode == FillSeparatorMode::Expanded)
+    }
+}
+
+/// Controls how the printer prints the separator between two items of a [Fill]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillSeparatorMode {
+    /// Prints the separator in flat mode if the next item fits on the line, and in expanded
+    /// mode otherwise
+    Auto,
+    /// Always prints the separator in expanded mode, for example to force a line break
+    /// between two items regardless of whether they fit on the line
+    Expanded,
+}
+
+impl Default for FillSeparatorMode {
+    fn default() -> Self {
+        FillSeparatorMode::Auto
+    }
 }
 
 /// Group is a special token that controls how the child tokens are printed.
@@ -237,6 +274,10 @@ impl Fill {
 pub struct Group {
     pub(crate) content: Box<[FormatElement]>,
     pub(crate) id: Option<GroupId>,
+    /// `true` if the content contains a hard line break, meaning the group can never be printed
+    /// in flat mode. Computed once when the group is created so that the printer doesn't have to
+    /// measure the content of the group.
+    pub(crate) expand: bool,
 }
 
 impl Debug for Group {
@@ -254,19 +295,28 @@ impl Debug for Group {
 
 impl Group {
     pub fn new(content: Vec<FormatElement>) -> Self {
+        let expand = content.iter().any(FormatElement::has_hard_line_break);
+
         Self {
             content: content.into_boxed_slice(),
             id: None,
+            expand,
         }
     }
 
+    /// Returns `true` if this group always gets printed in expanded mode because it
+    /// contains a hard line break
+    pub fn will_expand(&self) -> bool {
+        self.expand
+    }
+
     pub fn with_id(mut self, id: Option<GroupId>) -> Self {
         self.id = id;
         self
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum PrintMode {
     /// Omits any soft line breaks
     Flat,
@@ -288,6 +338,7 @@ impl PrintMode {
 /// can pick the best fitting variant.
 ///
 /// Best fitting is defined as the variant that takes the most horizontal space but fits on the line.
+/// The printer picks the first variant that fits and falls back to the last variant if none does.
 #[derive(Clone, Eq, PartialEq)]
 pub struct BestFitting {
     /// The different variants for this element.
@@ -569,10 +620,13 @@ impl FormatElement {
             FormatElement::Group(Group { content, .. })
             | FormatElement::ConditionalGroupContent(ConditionalGroupContent { content, .. })
             | FormatElement::Comment(content)
-            | FormatElement::Fill(Fill { content, .. })
             | FormatElement::Verbatim(Verbatim { content, .. })
             | FormatElement::Label(Label { content, .. })
-            | FormatElement::Indent(content) => content.iter().any(FormatElement::will_break),
+            | FormatElement::Indent(content)
+            | FormatElement::Align(content) => content.iter().any(FormatElement::will_break),
+            FormatElement::Fill(fill) => {
+                fill.has_expanded_separator() || fill.content.iter().any(FormatElement::will_break)
+            }
             FormatElement::List(list) => list.content.iter().any(FormatElement::will_break),
             FormatElement::Token(token) => token.contains('\n'),
             FormatElement::LineSuffix(_) => false,
@@ -583,6 +637,39 @@ impl FormatElement {
         }
     }
 
+    /// Returns `true` if printing this element always prints a hard line break, forcing the
+    /// enclosing group to expand. Unlike [FormatElement::will_break], this ignores the content
+    /// that may not get printed, like the content of a [ConditionalGroupContent] or the variants
+    /// of a [BestFitting].
+    fn has_hard_line_break(&self) -> bool {
+        match self {
+            FormatElement::Line(line_mode) => matches!(line_mode, LineMode::Hard | LineMode::Empty),
+            FormatElement::ExpandParent => true,
+            FormatElement::Group(group) => group.expand,
+            FormatElement::Fill(fill) => {
+                fill.has_expanded_separator()
+                    || fill.content.iter().any(FormatElement::has_hard_line_break)
+            }
+            FormatElement::Comment(content)
+            | FormatElement::Verbatim(Verbatim { content, .. })
+            | FormatElement::Label(Label { content, .. })
+            | FormatElement::Indent(content)
+            | FormatElement::Align(content) => {
+                content.iter().any(FormatElement::has_hard_line_break)
+            }
+            FormatElement::List(list) => {
+                list.content.iter().any(FormatElement::has_hard_line_break)
+            }
+            FormatElement::Interned(inner) => inner.0.has_hard_line_break(),
+            FormatElement::Space
+            | FormatElement::Token(_)
+            | FormatElement::ConditionalGroupContent(_)
+            | FormatElement::LineSuffix(_)
+            | FormatElement::LineSuffixBoundary
+            | FormatElement::BestFitting(_) => false,
+        }
+    }
+
     /// Utility function to get the "last element" of a [FormatElement], recursing
     /// into lists and groups to find the last element that's not
     /// a line break, verbatim or a comment.
@@ -593,7 +680,9 @@ impl FormatElement {
             }
             FormatElement::Line(_) | FormatElement::Comment(_) => None,
 
-            FormatElement::Group(Group { content, .. }) | FormatElement::Indent(content) => {
+            FormatElement::Group(Group { content, .. })
+            | FormatElement::Indent(content)
+            | FormatElement::Align(content) => {
                 content.iter().rev().find_map(FormatElement::last_element)
             }
             FormatElement::Interned(Interned(inner)) => inner.last_element(),
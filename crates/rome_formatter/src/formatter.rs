@@ -1,7 +1,12 @@
-use crate::buffer::BufferSnapshot;
-use crate::builders::{FillBuilder, JoinBuilder, JoinNodesBuilder, Line};
+use crate::buffer::{BufferSnapshot, ScratchBuffer};
+use crate::builders::{FillBuilder, JoinBuilder, JoinMemoizedBuilder, JoinNodesBuilder, Line};
 use crate::prelude::*;
-use crate::{Arguments, Buffer, FormatState, FormatStateSnapshot, GroupId, VecBuffer};
+use crate::printer::{indent_width, Printer};
+use crate::{
+    Arguments, Buffer, FormatContext, FormatState, FormatStateSnapshot, GroupId, LineWidth,
+    VecBuffer,
+};
+use rome_rowan::TextSize;
 
 /// Handles the formatting of a CST and stores the context how the CST should be formatted (user preferences).
 /// The formatter is passed to the [Format] implementation of every node in the CST so that they
@@ -89,6 +94,47 @@ impl<'buf, Context> Formatter<'buf, Context> {
         JoinBuilder::with_separator(self, joiner)
     }
 
+    /// Joins the objects by placing the specified separator between every two items, like
+    /// [Formatter::join_with], but formats every entry and the separator only once.
+    ///
+    /// [JoinMemoizedBuilder::finish] returns the joined entries instead of writing them. The entries
+    /// can then be written multiple times without formatting them again, for example in the
+    /// variants of [crate::best_fitting].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use rome_formatter::{best_fitting, format, format_args, write};
+    /// use rome_formatter::prelude::*;
+    ///
+    /// let formatted = format!(SimpleFormatContext::default(), [format_with(|f| {
+    ///     let joined = f.join_memoized_with(format_args!(token(","), soft_line_break_or_space()))
+    ///         .entry(&token("1"))
+    ///         .entry(&token("2"))
+    ///         .entry(&token("3"))
+    ///         .finish()?;
+    ///
+    ///     write!(f, [best_fitting!(
+    ///         format_args!(token("["), joined, token("]")),
+    ///         format_args!(token("["), block_indent(&joined), token("]"))
+    ///     )])
+    /// })]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     "[1, 2, 3]",
+    ///     formatted.print().as_code()
+    /// );
+    /// ```
+    pub fn join_memoized_with<'a, Separator>(
+        &'a mut self,
+        separator: Separator,
+    ) -> JoinMemoizedBuilder<'a, 'buf, Context>
+    where
+        Separator: Format<Context>,
+    {
+        JoinMemoizedBuilder::new(self, separator)
+    }
+
     /// Specialized version of [crate::Formatter::join_with] for joining SyntaxNodes separated by a space, soft
     /// line break or empty line depending on the input file.
     ///
@@ -163,6 +209,12 @@ impl<'buf, Context> Formatter<'buf, Context> {
         FillBuilder::new(self, separator)
     }
 
+    /// Returns a buffer for short-lived content that reuses the allocations of the previously
+    /// dropped scratch buffers.
+    pub(crate) fn scratch_buffer(&mut self) -> ScratchBuffer<Context> {
+        ScratchBuffer::new(self.state_mut())
+    }
+
     /// Formats `content` into an interned element without writing it to the formatter's buffer.
     pub fn intern(&mut self, content: &dyn Format<Context>) -> FormatResult<Interned> {
         let mut buffer = VecBuffer::new(self.state_mut());
@@ -171,6 +223,105 @@ impl<'buf, Context> Formatter<'buf, Context> {
 
         Ok(buffer.into_element().intern())
     }
+
+    /// Formats `content` with the `context` of an embedded region, for example the CSS of a
+    /// template literal in a JavaScript file, instead of the context of this formatter.
+    ///
+    /// The content is formatted and printed on its own, using the indent style and line width of
+    /// `context`. Each line of the printed content is then written to this formatter at its
+    /// current indentation level, which acts as the indentation base of the embedded region.
+    /// The line width of `context` is reduced by the width of this indentation, so that the
+    /// embedded lines still fit once they're indented.
+    /// `position` is the offset of the embedded region in the source text: each printed line is
+    /// mapped to `position` plus the source position of its first token in `content`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use rome_formatter::prelude::*;
+    /// use rome_formatter::{format, write, IndentStyle};
+    /// use rome_rowan::TextSize;
+    ///
+    /// let formatted = format!(SimpleFormatContext::default(), [
+    ///     token("outer {"),
+    ///     block_indent(&format_with(|f| {
+    ///         f.with_context_override(
+    ///             SimpleFormatContext {
+    ///                 indent_style: IndentStyle::Space(2),
+    ///                 ..SimpleFormatContext::default()
+    ///             },
+    ///             &format_with(|f| {
+    ///                 write!(f, [token("inner {"), block_indent(&token("a;")), token("}")])
+    ///             }),
+    ///             TextSize::from(0),
+    ///         )
+    ///     })),
+    ///     token("}")
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     "outer {\n\tinner {\n\t  a;\n\t}\n}",
+    ///     formatted.print().as_code()
+    /// );
+    /// ```
+    pub fn with_context_override<C>(
+        &mut self,
+        context: C,
+        content: &dyn Format<C>,
+        position: TextSize,
+    ) -> FormatResult<()>
+    where
+        Context: FormatContext,
+        C: FormatContext,
+    {
+        // Columns taken by the indentation the embedded lines are written at
+        let indentation =
+            self.state().indent_level() as usize * indent_width(&self.context().as_print_options());
+        let options = context.as_print_options();
+        let print_width = options.print_width.saturating_sub(indentation);
+        let options = options.with_print_width(LineWidth(print_width.max(LineWidth::MIN)));
+
+        let root = crate::format!(context, [content])?.into_format_element();
+        let printed = Printer::new(options).print(&root);
+        let sourcemap = printed.sourcemap();
+
+        let mut separator = None;
+        let mut line_start = TextSize::from(0);
+        let mut line_position = position;
+
+        for line in printed.as_code().split_inclusive('\n') {
+            let line_end = line_start + TextSize::of(line);
+            let text = line.trim_end_matches(&['\r', '\n'][..]);
+
+            // Map the line to the source position of its first token, relative to `position`
+            let first_marker = sourcemap.partition_point(|marker| marker.dest < line_start);
+            if let Some(marker) = sourcemap
+                .get(first_marker)
+                .filter(|marker| marker.dest < line_end)
+            {
+                line_position = position + marker.source;
+            }
+
+            line_start = line_end;
+
+            if text.is_empty() {
+                // Leading empty lines are removed, consecutive empty lines are collapsed
+                if separator.is_some() {
+                    separator = Some(empty_line());
+                }
+                continue;
+            }
+
+            if let Some(separator) = separator {
+                crate::write!(self, [separator])?;
+            }
+
+            crate::write!(self, [dynamic_token(text, line_position)])?;
+            separator = Some(hard_line_break());
+        }
+
+        Ok(())
+    }
 }
 
 impl<Context> Formatter<'_, Context> {
@@ -232,3 +383,96 @@ pub struct FormatterSnapshot {
     buffer: BufferSnapshot,
     state: FormatStateSnapshot,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{format, format_args, write, IndentStyle, LineWidth, SourceMarker};
+    use rome_rowan::TextSize;
+
+    #[test]
+    fn context_override_reduces_the_line_width_by_the_indentation() {
+        let content = format_with(|f: &mut Formatter<SimpleFormatContext>| {
+            write!(
+                f,
+                [group_elements(&format_args![
+                    token("aaaa"),
+                    soft_line_break_or_space(),
+                    token("bbbb")
+                ])]
+            )
+        });
+
+        let formatted = format!(
+            SimpleFormatContext::default(),
+            [
+                token("{"),
+                block_indent(&format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                    f.with_context_override(
+                        SimpleFormatContext {
+                            line_width: LineWidth::try_from(10).unwrap(),
+                            ..SimpleFormatContext::default()
+                        },
+                        &content,
+                        TextSize::from(0),
+                    )
+                })),
+                token("}")
+            ]
+        )
+        .unwrap();
+
+        // `aaaa bbbb` fits in 10 columns, but not once it's indented by a tab
+        assert_eq!(formatted.print().as_code(), "{\n\taaaa\n\tbbbb\n}");
+    }
+
+    #[test]
+    fn context_override_maps_each_line_to_its_source_position() {
+        let formatted = format!(
+            SimpleFormatContext::default(),
+            [
+                token("x"),
+                hard_line_break(),
+                format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                    f.with_context_override(
+                        SimpleFormatContext {
+                            indent_style: IndentStyle::Space(2),
+                            ..SimpleFormatContext::default()
+                        },
+                        &format_with(|f: &mut Formatter<SimpleFormatContext>| {
+                            write!(
+                                f,
+                                [
+                                    dynamic_token("a;", TextSize::from(0)),
+                                    hard_line_break(),
+                                    dynamic_token("b;", TextSize::from(3))
+                                ]
+                            )
+                        }),
+                        TextSize::from(10),
+                    )
+                })
+            ]
+        )
+        .unwrap();
+
+        let printed = formatted.print();
+        let marker = |source: u32, dest: u32| SourceMarker {
+            source: TextSize::from(source),
+            dest: TextSize::from(dest),
+        };
+
+        assert_eq!(printed.as_code(), "x\na;\nb;");
+        assert_eq!(
+            printed.sourcemap(),
+            &[
+                marker(0, 0),
+                marker(0, 1),
+                marker(10, 2),
+                marker(12, 4),
+                marker(13, 5),
+                marker(15, 7)
+            ]
+        );
+    }
+}
@@ -25,6 +25,9 @@ mod arguments;
 mod buffer;
 mod builders;
 mod comments;
+mod diff;
+#[cfg(debug_assertions)]
+pub mod element_counts;
 pub mod format_element;
 mod format_extensions;
 pub mod formatter;
@@ -42,6 +45,8 @@ use crate::group_id::UniqueGroupIdBuilder;
 use crate::prelude::syntax_token_cow_slice;
 use std::any::TypeId;
 
+#[cfg(debug_assertions)]
+use crate::element_counts::ElementCounts;
 #[cfg(debug_assertions)]
 use crate::printed_tokens::PrintedTokens;
 use crate::printer::{Printer, PrinterOptions};
@@ -51,12 +56,16 @@ pub use buffer::{
     PreambleBuffer, VecBuffer, WillBreakBuffer,
 };
 pub use builders::{
-    block_indent, comment, empty_line, get_lines_before, group_elements, hard_line_break,
-    if_group_breaks, if_group_fits_on_line, indent, labelled, line_suffix, soft_block_indent,
-    soft_line_break, soft_line_break_or_space, soft_line_indent_or_space, space_token, token,
-    BestFitting,
+    align, block_indent, comment, empty_line, get_lines_before, get_lines_before_comments,
+    group_elements, hard_line_break, if_group_breaks, if_group_fits_on_line, indent, labelled,
+    line_suffix, soft_block_indent, soft_line_break, soft_line_break_or_space,
+    soft_line_indent_or_space, space_token, token, BestFitting,
 };
-pub use comments::{CommentContext, CommentKind, SourceComment};
+pub use comments::{
+    format_block_comment, CommentContext, CommentKind, CommentPlacement, FormatBlockComment,
+    SourceComment,
+};
+pub use diff::DiffHunk;
 pub use format_element::{normalize_newlines, FormatElement, Token, Verbatim, LINE_TERMINATORS};
 pub use group_id::GroupId;
 use indexmap::IndexSet;
@@ -68,6 +77,7 @@ use std::error::Error;
 use std::fmt;
 use std::fmt::Display;
 use std::num::ParseIntError;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -80,6 +90,25 @@ pub enum IndentStyle {
 
 impl IndentStyle {
     pub const DEFAULT_SPACES: u8 = 2;
+
+    /// Maximum allowed width for an indentation with spaces
+    pub const MAX_SPACES: u8 = 16;
+
+    /// Creates an indentation with `size` spaces, the allowed range of values is 1..=16
+    ///
+    /// ```
+    /// use rome_formatter::IndentStyle;
+    ///
+    /// assert_eq!(IndentStyle::try_space(4), Ok(IndentStyle::Space(4)));
+    /// assert!(IndentStyle::try_space(0).is_err());
+    /// ```
+    pub fn try_space(size: u8) -> Result<Self, IndentWidthFromIntError> {
+        if (1..=Self::MAX_SPACES).contains(&size) {
+            Ok(Self::Space(size))
+        } else {
+            Err(IndentWidthFromIntError(size))
+        }
+    }
 }
 
 impl Default for IndentStyle {
@@ -89,14 +118,21 @@ impl Default for IndentStyle {
 }
 
 impl FromStr for IndentStyle {
-    type Err = &'static str;
+    type Err = ParseIndentStyleError;
 
+    /// Parses the name of an indent style, or the output of the [Display] implementation
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "tab" | "Tabs" => Ok(Self::Tab),
+            "tab" | "Tab" | "Tabs" => Ok(Self::Tab),
             "space" | "Spaces" => Ok(Self::Space(IndentStyle::DEFAULT_SPACES)),
-            // TODO: replace this error with a diagnostic
-            _ => Err("Value not supported for IndentStyle"),
+            _ => {
+                let size = s
+                    .strip_prefix("Spaces, size: ")
+                    .and_then(|size| size.parse::<u8>().ok())
+                    .ok_or(ParseIndentStyleError::UnsupportedValue)?;
+
+                Self::try_space(size).map_err(ParseIndentStyleError::InvalidWidth)
+            }
         }
     }
 }
@@ -110,6 +146,59 @@ impl std::fmt::Display for IndentStyle {
     }
 }
 
+/// Error type returned when parsing an [IndentStyle] from a string fails
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseIndentStyleError {
+    /// The string isn't the name of an indent style
+    // TODO: replace this error with a diagnostic
+    UnsupportedValue,
+    /// The width of the spaces indentation is out of range
+    InvalidWidth(IndentWidthFromIntError),
+}
+
+impl std::fmt::Display for ParseIndentStyleError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseIndentStyleError::UnsupportedValue => {
+                std::write!(fmt, "Value not supported for IndentStyle")
+            }
+            ParseIndentStyleError::InvalidWidth(error) => std::write!(fmt, "{error}"),
+        }
+    }
+}
+
+impl Error for ParseIndentStyleError {}
+
+/// Error type returned when creating an [IndentStyle::Space] with an invalid width, holding the
+/// invalid value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IndentWidthFromIntError(pub u8);
+
+impl IndentWidthFromIntError {
+    /// Returns the value that isn't a valid indent width
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the range of the valid values for an indent width
+    pub fn allowed_range(&self) -> RangeInclusive<u8> {
+        1..=IndentStyle::MAX_SPACES
+    }
+}
+
+impl Display for IndentWidthFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        std::write!(
+            f,
+            "The indent width {} is out of range, it must be between 1 and {}",
+            self.0,
+            IndentStyle::MAX_SPACES
+        )
+    }
+}
+
+impl Error for IndentWidthFromIntError {}
+
 /// Validated value for the `line_width` formatter options
 ///
 /// The allowed range of values is 1..=320
@@ -117,6 +206,9 @@ impl std::fmt::Display for IndentStyle {
 pub struct LineWidth(u16);
 
 impl LineWidth {
+    /// Minimum allowed value for a valid [LineWidth]
+    pub const MIN: u16 = 1;
+
     /// Maximum allowed value for a valid [LineWidth]
     pub const MAX: u16 = 320;
 
@@ -124,6 +216,21 @@ impl LineWidth {
     pub fn value(&self) -> u16 {
         self.0
     }
+
+    /// Returns the number of columns left when `width` columns of this [LineWidth] are used,
+    /// or `0` if `width` exceeds the line width.
+    ///
+    /// ```
+    /// use rome_formatter::LineWidth;
+    ///
+    /// let line_width = LineWidth::try_from(80).unwrap();
+    ///
+    /// assert_eq!(line_width.saturating_sub(6), 74);
+    /// assert_eq!(line_width.saturating_sub(100), 0);
+    /// ```
+    pub fn saturating_sub(self, width: usize) -> u16 {
+        (self.0 as usize).saturating_sub(width) as u16
+    }
 }
 
 impl Default for LineWidth {
@@ -143,10 +250,15 @@ pub enum ParseLineWidthError {
 
 impl std::fmt::Display for ParseLineWidthError {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::write!(fmt, "{self:?}")
+        match self {
+            ParseLineWidthError::ParseError(error) => std::write!(fmt, "{error}"),
+            ParseLineWidthError::TryFromIntError(error) => std::write!(fmt, "{error}"),
+        }
     }
 }
 
+impl Error for ParseLineWidthError {}
+
 impl FromStr for LineWidth {
     type Err = ParseLineWidthError;
 
@@ -157,15 +269,27 @@ impl FromStr for LineWidth {
     }
 }
 
-/// Error type returned when converting a u16 to a [LineWidth] fails
-#[derive(Clone, Copy, Debug)]
+/// Error type returned when converting a u16 to a [LineWidth] fails, holding the invalid value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct LineWidthFromIntError(pub u16);
 
+impl LineWidthFromIntError {
+    /// Returns the value that isn't a valid [LineWidth]
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the range of the valid values for a [LineWidth]
+    pub fn allowed_range(&self) -> RangeInclusive<u16> {
+        LineWidth::MIN..=LineWidth::MAX
+    }
+}
+
 impl TryFrom<u16> for LineWidth {
     type Error = LineWidthFromIntError;
 
     fn try_from(value: u16) -> Result<Self, Self::Error> {
-        if value > 0 && value <= Self::MAX {
+        if (Self::MIN..=Self::MAX).contains(&value) {
             Ok(Self(value))
         } else {
             Err(LineWidthFromIntError(value))
@@ -175,14 +299,18 @@ impl TryFrom<u16> for LineWidth {
 
 impl Display for LineWidthFromIntError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(
+        std::write!(
             f,
-            "The line width exceeds the maximum value ({})",
+            "The line width {} is out of range, it must be between {} and {}",
+            self.0,
+            LineWidth::MIN,
             LineWidth::MAX
         )
     }
 }
 
+impl Error for LineWidthFromIntError {}
+
 impl From<LineWidth> for u16 {
     fn from(value: LineWidth) -> Self {
         value.0
@@ -265,6 +393,10 @@ pub struct Printed {
     range: Option<TextRange>,
     sourcemap: Vec<SourceMarker>,
     verbatim_ranges: Vec<TextRange>,
+    overflowing_lines: Vec<(usize, usize)>,
+    overflowing_tokens: Vec<TextRange>,
+    remaining_width: u16,
+    measured_elements: usize,
 }
 
 impl Printed {
@@ -279,9 +411,37 @@ impl Printed {
             range,
             sourcemap,
             verbatim_ranges: verbatim_source,
+            overflowing_lines: Vec::new(),
+            overflowing_tokens: Vec::new(),
+            remaining_width: 0,
+            measured_elements: 0,
         }
     }
 
+    /// Sets the lines of the printed code that exceed the configured line width
+    pub fn with_overflowing_lines(mut self, overflowing_lines: Vec<(usize, usize)>) -> Self {
+        self.overflowing_lines = overflowing_lines;
+        self
+    }
+
+    /// Sets the ranges of the printed code containing tokens wider than the configured line width
+    pub fn with_overflowing_tokens(mut self, overflowing_tokens: Vec<TextRange>) -> Self {
+        self.overflowing_tokens = overflowing_tokens;
+        self
+    }
+
+    /// Sets the number of columns remaining on the last line of the printed code
+    pub fn with_remaining_width(mut self, remaining_width: u16) -> Self {
+        self.remaining_width = remaining_width;
+        self
+    }
+
+    /// Sets how many elements the printer measured while testing if groups fit
+    pub(crate) fn with_measured_elements(mut self, measured_elements: usize) -> Self {
+        self.measured_elements = measured_elements;
+        self
+    }
+
     /// Construct an empty formatter result
     pub fn new_empty() -> Self {
         Self {
@@ -289,6 +449,10 @@ impl Printed {
             range: None,
             sourcemap: Vec::new(),
             verbatim_ranges: Vec::new(),
+            overflowing_lines: Vec::new(),
+            overflowing_tokens: Vec::new(),
+            remaining_width: 0,
+            measured_elements: 0,
         }
     }
 
@@ -331,6 +495,40 @@ impl Printed {
     pub fn verbatim_ranges(&self) -> &[TextRange] {
         &self.verbatim_ranges
     }
+
+    /// Returns the lines of the formatted code that exceed the line width as `(line, width)`
+    /// tuples, where `line` is the zero-based index of the line and `width` its width
+    /// with tabs counted according to the printer's tab width.
+    pub fn overflowing_lines(&self) -> &[(usize, usize)] {
+        &self.overflowing_lines
+    }
+
+    /// Returns the ranges in the formatted code of the tokens that are wider than the line
+    /// width on their own and therefore can't be made to fit by breaking the surrounding groups.
+    /// Only recorded if the printer is configured with [crate::printer::LineWidthViolationPolicy::Warn].
+    pub fn overflowing_tokens(&self) -> &[TextRange] {
+        &self.overflowing_tokens
+    }
+
+    /// Returns how many columns remained on the last printed line before reaching the print
+    /// width, accounting for its indention and any pending space.
+    pub fn remaining_width(&self) -> u16 {
+        self.remaining_width
+    }
+
+    /// Returns how many elements the printer measured while testing if groups fit on a line.
+    /// Memoized content is only measured once, no matter how often it gets printed.
+    pub(crate) fn measured_elements(&self) -> usize {
+        self.measured_elements
+    }
+
+    /// Computes the line-based differences between the `original` source text and the
+    /// formatted code, returning an empty list if the source text is already formatted.
+    ///
+    /// This is only meaningful for a result covering the entire file, see [Printed::range].
+    pub fn diff(&self, original: &str) -> Vec<DiffHunk> {
+        diff::diff_lines(original, &self.code)
+    }
 }
 
 /// Public return type of the formatter
@@ -419,6 +617,22 @@ where
     }
 }
 
+/// Formats the value if it's present and writes nothing otherwise. This allows to conditionally
+/// include an entry in a [crate::format_args!] or [crate::write!] sequence without any ceremony.
+///
+/// ```
+/// use rome_formatter::prelude::*;
+/// use rome_formatter::{format, format_args};
+///
+/// let label = Some(token("label:"));
+/// let empty: Option<StaticToken> = None;
+///
+/// let formatted = format!(SimpleFormatContext::default(), [
+///     format_args!(label, empty, token("statement"))
+/// ]).unwrap();
+///
+/// assert_eq!("label:statement", formatted.print().as_code());
+/// ```
 impl<T, Context> Format<Context> for Option<T>
 where
     T: Format<Context>,
@@ -715,7 +929,12 @@ pub fn format_node<
     tracing::trace_span!("format_node").in_scope(move || {
         let print_options = context.as_print_options();
         let mut state = FormatState::new(context);
-        let mut buffer = VecBuffer::new(&mut state);
+
+        // Reserve a rough estimate of the number of elements in the document from the length
+        // of its source text, so that the root buffer doesn't get reallocated over and over
+        // when formatting a large file
+        let capacity = usize::from(root.item().text_range().len()) / 8;
+        let mut buffer = VecBuffer::with_capacity(capacity, &mut state);
 
         write!(&mut buffer, [root])?;
 
@@ -723,13 +942,16 @@ pub fn format_node<
 
         state.assert_formatted_all_tokens(root.item());
 
+        #[cfg(debug_assertions)]
+        tracing::trace!("written format elements:\n{}", state.element_counts());
+
         Ok(Formatted::new(document, print_options))
     })
 }
 
 /// Returns the [TextRange] for this [SyntaxElement] with the leading and
 /// trailing whitespace trimmed (but keeping comments or skipped trivias)
-fn text_non_whitespace_range<E, L>(elem: &E) -> TextRange
+pub(crate) fn text_non_whitespace_range<E, L>(elem: &E) -> TextRange
 where
     E: Into<SyntaxElement<L>> + Clone,
     L: Language,
@@ -1076,12 +1298,20 @@ pub fn format_sub_tree<
     let printed = formatted.print_with_indent(initial_indent);
     let sourcemap = Vec::from(printed.sourcemap());
     let verbatim_ranges = Vec::from(printed.verbatim_ranges());
+    let overflowing_lines = Vec::from(printed.overflowing_lines());
+    let overflowing_tokens = Vec::from(printed.overflowing_tokens());
+    let remaining_width = printed.remaining_width();
+    let measured_elements = printed.measured_elements();
     Ok(Printed::new(
         printed.into_code(),
         Some(syntax.text_range()),
         sourcemap,
         verbatim_ranges,
-    ))
+    )
+    .with_overflowing_lines(overflowing_lines)
+    .with_overflowing_tokens(overflowing_tokens)
+    .with_remaining_width(remaining_width)
+    .with_measured_elements(measured_elements))
 }
 
 impl<L: Language, Context> Format<Context> for SyntaxTriviaPieceComments<L> {
@@ -1124,10 +1354,20 @@ pub struct FormatState<Context> {
     /// absolute position.
     manually_formatted_comments: IndexSet<TextSize>,
 
+    /// Pool of the vectors of dropped [crate::buffer::ScratchBuffer]s, reused by the next scratch buffers.
+    scratch_buffers: Vec<Vec<FormatElement>>,
+
+    /// Number of indents enclosing the content that is currently being formatted
+    indent_level: u16,
+
     // This is using a RefCell as it only exists in debug mode,
     // the Formatter is still completely immutable in release builds
     #[cfg(debug_assertions)]
     pub printed_tokens: PrintedTokens,
+
+    /// Number of elements of each kind written to the buffers, see [FormatState::element_counts]
+    #[cfg(debug_assertions)]
+    element_counts: ElementCounts,
 }
 
 impl<Context> fmt::Debug for FormatState<Context>
@@ -1155,8 +1395,12 @@ impl<Context> FormatState<Context> {
             last_content_inline_comment: false,
             last_token_kind: None,
             manually_formatted_comments: IndexSet::default(),
+            scratch_buffers: Vec::new(),
+            indent_level: 0,
             #[cfg(debug_assertions)]
             printed_tokens: Default::default(),
+            #[cfg(debug_assertions)]
+            element_counts: Default::default(),
         }
     }
 
@@ -1173,6 +1417,12 @@ impl<Context> FormatState<Context> {
         self.last_content_inline_comment = has_comment;
     }
 
+    /// Returns the number of indents, for example created by [crate::indent] or [crate::block_indent],
+    /// that enclose the content that is currently being formatted.
+    pub fn indent_level(&self) -> u16 {
+        self.indent_level
+    }
+
     /// Returns the kind of the last formatted token.
     pub fn last_token_kind(&self) -> Option<LastTokenKind> {
         self.last_token_kind
@@ -1277,6 +1527,23 @@ impl<Context> FormatState<Context> {
         }
     }
 
+    /// Tracks an element written to a buffer in debug builds, see [FormatState::element_counts]
+    #[inline]
+    pub fn track_element(&mut self, #[allow(unused_variables)] element: &FormatElement) {
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                self.element_counts.track(element);
+            }
+        }
+    }
+
+    /// Returns how many elements of each kind have been written while formatting with this state.
+    /// Only available in debug builds.
+    #[cfg(debug_assertions)]
+    pub fn element_counts(&self) -> &ElementCounts {
+        &self.element_counts
+    }
+
     /// Asserts in debug builds that all tokens have been printed.
     #[inline]
     pub fn assert_formatted_all_tokens<L: Language>(
@@ -1330,4 +1597,103 @@ pub trait CommentStyle<L: Language>: Copy {
     /// * element separator: `,` or `.`.
     /// * end of file token: `EOF`
     fn is_group_end_token(&self, kind: L::Kind) -> bool;
+
+    /// Returns how an inline comment is placed between a token of kind `prev_kind` and a token of
+    /// kind `next_kind`. A kind is `None` if there's no token on that side of the comment or if
+    /// the token isn't known at the time the comment gets formatted.
+    ///
+    /// The default implementation attaches comments to group start and group end tokens and separates
+    /// them by a space from any other token. Languages can override it if they have other tokens
+    /// that comments should be attached to.
+    fn comment_boundary(
+        &self,
+        prev_kind: Option<L::Kind>,
+        next_kind: Option<L::Kind>,
+    ) -> CommentPlacement {
+        match (prev_kind, next_kind) {
+            (_, Some(next)) if self.is_group_end_token(next) => CommentPlacement::AttachedToNext,
+            (Some(prev), _) if self.is_group_start_token(prev) => {
+                CommentPlacement::AttachedToPrevious
+            }
+            _ => CommentPlacement::Spaced,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        IndentStyle, IndentWidthFromIntError, LineWidth, LineWidthFromIntError,
+        ParseIndentStyleError,
+    };
+
+    #[test]
+    fn line_width_boundaries() {
+        assert_eq!(LineWidth::try_from(LineWidth::MIN).unwrap().value(), 1);
+        assert_eq!(LineWidth::try_from(LineWidth::MAX).unwrap().value(), 320);
+
+        assert_eq!(LineWidth::try_from(0), Err(LineWidthFromIntError(0)));
+        assert_eq!(LineWidth::try_from(321), Err(LineWidthFromIntError(321)));
+    }
+
+    #[test]
+    fn line_width_error_message() {
+        let error = LineWidth::try_from(500).unwrap_err();
+
+        assert_eq!(error.value(), 500);
+        assert_eq!(error.allowed_range(), 1..=320);
+        assert_eq!(
+            error.to_string(),
+            "The line width 500 is out of range, it must be between 1 and 320"
+        );
+
+        assert_eq!(
+            "0".parse::<LineWidth>().unwrap_err().to_string(),
+            "The line width 0 is out of range, it must be between 1 and 320"
+        );
+    }
+
+    #[test]
+    fn indent_style_parsing() {
+        assert_eq!("tab".parse(), Ok(IndentStyle::Tab));
+        assert_eq!("space".parse(), Ok(IndentStyle::Space(2)));
+        assert_eq!("Spaces, size: 4".parse(), Ok(IndentStyle::Space(4)));
+
+        assert_eq!(
+            "spaces".parse::<IndentStyle>(),
+            Err(ParseIndentStyleError::UnsupportedValue)
+        );
+        assert_eq!(
+            "Spaces, size: 0".parse::<IndentStyle>(),
+            Err(ParseIndentStyleError::InvalidWidth(
+                IndentWidthFromIntError(0)
+            ))
+        );
+    }
+
+    #[test]
+    fn indent_style_space_width() {
+        assert_eq!(IndentStyle::try_space(1), Ok(IndentStyle::Space(1)));
+        assert_eq!(IndentStyle::try_space(16), Ok(IndentStyle::Space(16)));
+
+        assert_eq!(IndentStyle::try_space(0), Err(IndentWidthFromIntError(0)));
+
+        let error = IndentStyle::try_space(17).unwrap_err();
+        assert_eq!(error.allowed_range(), 1..=16);
+        assert_eq!(
+            error.to_string(),
+            "The indent width 17 is out of range, it must be between 1 and 16"
+        );
+    }
+
+    #[test]
+    fn indent_style_display_round_trip() {
+        for style in [
+            IndentStyle::Tab,
+            IndentStyle::Space(2),
+            IndentStyle::Space(8),
+        ] {
+            assert_eq!(style.to_string().parse(), Ok(style));
+        }
+    }
 }
@@ -247,6 +247,11 @@ macro_rules! format {
 /// up being printed in flat mode if they fit on the line while printing. But there is currently no way
 /// to enforce that a specific group inside a variant must be flat when measuring if that variant fits.
 ///
+/// The macro accepts any number of variants, as long as there are at least two. The printer tests the
+/// variants in the order they're passed and prints the first one that fits. The last variant is never
+/// measured: it's the fallback that the printer uses in [`Expanded`] mode if none of the other
+/// variants fit, even if it overflows the line itself.
+///
 /// [`Flat`]: crate::format_element::PrintMode::Flat
 /// [`Expanded`]: crate::format_element::PrintMode::Expanded
 #[macro_export]
@@ -2,12 +2,16 @@ mod printer_options;
 
 pub use printer_options::*;
 
-use crate::format_element::{ConditionalGroupContent, Group, LineMode, PrintMode, VerbatimKind};
+use crate::format_element::{
+    ConditionalGroupContent, Fill, FillSeparatorMode, Group, LineMode, PrintMode, VerbatimKind,
+};
 use crate::intersperse::Intersperse;
 use crate::{FormatElement, GroupId, Printed, SourceMarker, TextRange};
 
 use rome_rowan::TextSize;
+use std::collections::HashMap;
 use std::iter::{once, Rev};
+use unicode_width::UnicodeWidthChar;
 
 /// Prints the format elements into a string
 #[derive(Debug, Default)]
@@ -33,32 +37,83 @@ impl<'a> Printer<'a> {
     /// starting at the specified indentation level
     pub fn print_with_indent(mut self, element: &'a FormatElement, indent: u16) -> Printed {
         tracing::debug_span!("Printer::print").in_scope(move || {
-            let mut queue = ElementCallQueue::default();
-
-            queue.enqueue(PrintElementCall::new(
-                element,
-                PrintElementArgs::new(indent),
-            ));
-
-            while let Some(print_element_call) = queue.dequeue() {
-                self.print_element(
-                    &mut queue,
-                    print_element_call.element,
-                    print_element_call.args,
-                );
+            self.print_root(element, indent);
+            self.finish()
+        })
+    }
 
-                if queue.is_empty() && !self.state.line_suffixes.is_empty() {
-                    queue.extend(self.state.line_suffixes.drain(..));
-                }
+    /// Prints the passed in element and its content without finishing the document.
+    ///
+    /// Use this together with [Printer::remaining_width] to base the decision of what to print
+    /// next on the space left on the current line, and call [Printer::finish] once all
+    /// elements have been printed.
+    pub fn print_element_incrementally(&mut self, element: &'a FormatElement) {
+        self.print_root(element, 0);
+    }
+
+    /// Finishes the document and returns the printed result.
+    pub fn finish(mut self) -> Printed {
+        // The last line isn't terminated by a line break
+        self.track_overflowing_line();
+
+        tracing::trace!(
+            measured_elements = self.state.measured_elements,
+            "Printed document"
+        );
+
+        let remaining_width = self.remaining_width();
+
+        Printed::new(
+            self.state.buffer,
+            None,
+            self.state.source_markers,
+            self.state.verbatim_markers,
+        )
+        .with_overflowing_lines(self.state.overflowing_lines)
+        .with_overflowing_tokens(self.state.overflowing_tokens)
+        .with_remaining_width(remaining_width)
+        .with_measured_elements(self.state.measured_elements)
+    }
+
+    /// Prints the passed in element and its content without finishing the document, leaving
+    /// the printer positioned after the last printed element.
+    fn print_root(&mut self, element: &'a FormatElement, indent: u16) {
+        let mut queue = ElementCallQueue::default();
+
+        queue.enqueue(PrintElementCall::new(
+            element,
+            PrintElementArgs::new(indent),
+        ));
+
+        while let Some(print_element_call) = queue.dequeue() {
+            self.print_element(
+                &mut queue,
+                print_element_call.element,
+                print_element_call.args,
+            );
+
+            if queue.is_empty() && !self.state.line_suffixes.is_empty() {
+                queue.extend(self.state.line_suffixes.drain(..));
             }
+        }
+    }
 
-            Printed::new(
-                self.state.buffer,
-                None,
-                self.state.source_markers,
-                self.state.verbatim_markers,
-            )
-        })
+    /// Returns how many columns remain on the current line before reaching the print width.
+    ///
+    /// Accounts for the content already printed on the line as well as the pending indention,
+    /// alignment and space that get printed before the next token.
+    pub fn remaining_width(&self) -> u16 {
+        let column = self.pending_column();
+
+        self.options.print_width.saturating_sub(column)
+    }
+
+    /// Returns the column at which the next token gets printed
+    fn pending_column(&self) -> usize {
+        self.state.line_width
+            + self.state.pending_indent as usize * indent_width(&self.options)
+            + self.state.pending_align as usize
+            + usize::from(self.state.pending_space)
     }
 
     /// Prints a single element and push the following elements to queue
@@ -75,22 +130,7 @@ impl<'a> Printer<'a> {
                 }
             }
             FormatElement::Token(token) => {
-                // Print pending indention
-                if self.state.pending_indent > 0 {
-                    self.print_str(
-                        self.options
-                            .indent_string
-                            .repeat(self.state.pending_indent as usize)
-                            .as_str(),
-                    );
-                    self.state.pending_indent = 0;
-                }
-
-                // Print pending spaces
-                if self.state.pending_space {
-                    self.print_str(" ");
-                    self.state.pending_space = false;
-                }
+                self.print_pending_indent_and_space();
 
                 // Insert source map markers before and after the token
                 //
@@ -110,8 +150,17 @@ impl<'a> Printer<'a> {
                     dest: TextSize::of(&self.state.buffer),
                 });
 
+                let start = TextSize::of(&self.state.buffer);
                 self.print_str(token);
 
+                if self.options.max_line_width_violation_policy == LineWidthViolationPolicy::Warn
+                    && self.is_overflowing_token(token)
+                {
+                    self.state
+                        .overflowing_tokens
+                        .push(TextRange::new(start, TextSize::of(&self.state.buffer)));
+                }
+
                 if token.source_position().is_some() {
                     self.state.source_position += TextSize::of(&**token);
                 }
@@ -122,7 +171,11 @@ impl<'a> Printer<'a> {
                 });
             }
 
-            FormatElement::Group(Group { content, id }) => {
+            FormatElement::Group(Group {
+                content,
+                id,
+                expand,
+            }) => {
                 let group_mode = match args.mode {
                     PrintMode::Flat if self.state.measured_group_fits => {
                         // A parent group has already verified that this group fits on a single line
@@ -130,6 +183,14 @@ impl<'a> Printer<'a> {
                         queue.extend_with_args(content.iter(), args);
                         PrintMode::Flat
                     }
+                    // The group contains a hard line break, it can never fit on a single line
+                    _ if *expand => {
+                        queue.extend_with_args(
+                            content.iter(),
+                            args.with_print_mode(PrintMode::Expanded),
+                        );
+                        PrintMode::Expanded
+                    }
                     // The printer is either in expanded mode or it's necessary to re-measure if the group fits
                     // because the printer printed a line break
                     _ => {
@@ -157,7 +218,7 @@ impl<'a> Printer<'a> {
             }
 
             FormatElement::Fill(fill) => {
-                self.print_fill(queue, fill.content(), fill.separator(), args);
+                self.print_fill(queue, fill, args);
             }
 
             FormatElement::List(list) => {
@@ -168,6 +229,13 @@ impl<'a> Printer<'a> {
                 queue.extend_with_args(content.iter(), args.with_incremented_indent());
             }
 
+            FormatElement::Align(content) => {
+                let indentation_width = args.indent as usize * indent_width(&self.options);
+                let align = self.pending_column().saturating_sub(indentation_width);
+
+                queue.extend_with_args(content.iter(), args.with_align(align as u16));
+            }
+
             FormatElement::ConditionalGroupContent(ConditionalGroupContent {
                 mode,
                 content,
@@ -206,6 +274,7 @@ impl<'a> Printer<'a> {
 
                     self.state.pending_space = false;
                     self.state.pending_indent = args.indent;
+                    self.state.pending_align = args.align;
 
                     // Fit's only tests if groups up to the first line break fit.
                     // The next group must re-measure if it still fits.
@@ -229,6 +298,11 @@ impl<'a> Printer<'a> {
 
             FormatElement::Verbatim(verbatim) => {
                 if let VerbatimKind::Verbatim { length } = &verbatim.kind {
+                    // The range starts after the indention and space preceding the verbatim text
+                    if !verbatim.content.is_empty() {
+                        self.print_pending_indent_and_space();
+                    }
+
                     self.state.verbatim_markers.push(TextRange::at(
                         TextSize::from(self.state.buffer.len() as u32),
                         *length,
@@ -339,16 +413,18 @@ impl<'a> Printer<'a> {
     /// * The first and second content fit on a single line. It prints the content and separator in flat mode.
     /// * The first content fits on a single line, but the second doesn't. It prints the content in flat and the separator in expanded mode.
     /// * Neither the first nor the second content fit on the line. It brings the first content and the separator in expanded mode.
+    ///
+    /// A separator with the [FillSeparatorMode::Expanded] mode is always printed in expanded mode.
     fn print_fill(
         &mut self,
         queue: &mut ElementCallQueue<'a>,
-        content: &'a [FormatElement],
-        separator: &'a FormatElement,
+        fill: &'a Fill,
         args: PrintElementArgs,
     ) {
         let empty_rest = ElementCallQueue::default();
 
-        let mut items = content.iter();
+        let separator = fill.separator();
+        let mut items = fill.content().iter();
 
         let current_content = match items.next() {
             None => {
@@ -376,10 +452,14 @@ impl<'a> Printer<'a> {
         );
 
         // Process remaining items
-        for next_item in items {
-            // A line break in expanded mode is always necessary if the current item didn't fit.
+        for (index, next_item) in items.enumerate() {
+            let separator_mode = fill.separator_mode(index + 1);
+
+            // A line break in expanded mode is always necessary if the current item didn't fit
+            // or if the separator is forced in expanded mode.
             // otherwise see if both contents fit on the line.
             let current_and_next_fit = current_fits
+                && separator_mode == FillSeparatorMode::Auto
                 && fits_on_line(
                     [separator, next_item],
                     args.with_print_mode(PrintMode::Flat),
@@ -449,9 +529,34 @@ impl<'a> Printer<'a> {
         }
     }
 
+    /// Prints the indention and the space that are pending before the next token
+    fn print_pending_indent_and_space(&mut self) {
+        if self.state.pending_indent > 0 {
+            self.print_str(
+                self.options
+                    .indent_string
+                    .repeat(self.state.pending_indent as usize)
+                    .as_str(),
+            );
+            self.state.pending_indent = 0;
+        }
+
+        if self.state.pending_align > 0 {
+            self.print_str(" ".repeat(self.state.pending_align as usize).as_str());
+            self.state.pending_align = 0;
+        }
+
+        if self.state.pending_space {
+            self.print_str(" ");
+            self.state.pending_space = false;
+        }
+    }
+
     fn print_str(&mut self, content: &str) {
         for char in content.chars() {
             if char == '\n' {
+                self.track_overflowing_line();
+
                 self.state
                     .buffer
                     .push_str(self.options.line_ending.as_str());
@@ -462,18 +567,54 @@ impl<'a> Printer<'a> {
                 self.state.buffer.push(char);
                 self.state.generated_column += 1;
 
-                let char_width = if char == '\t' {
-                    self.options.tab_width as usize
-                } else {
-                    1
-                };
-
-                self.state.line_width += char_width;
+                self.state.line_width += char_width(char, self.options.tab_width);
             }
 
             self.state.has_empty_line = false;
         }
     }
+
+    /// Records the current line if its width, measured with the configured tab width,
+    /// exceeds the print width
+    fn track_overflowing_line(&mut self) {
+        if self.state.line_width > self.options.print_width.value() as usize {
+            self.state
+                .overflowing_lines
+                .push((self.state.generated_line, self.state.line_width));
+        }
+    }
+
+    /// Returns `true` if any line of `token` is wider than the print width on its own, meaning
+    /// that the line overflows no matter how the surrounding content gets broken
+    fn is_overflowing_token(&self, token: &str) -> bool {
+        token.split('\n').any(|line| {
+            let width: usize = line
+                .chars()
+                .map(|char| char_width(char, self.options.tab_width))
+                .sum();
+
+            width > self.options.print_width.value() as usize
+        })
+    }
+}
+
+/// Returns the number of columns taken by `char` when printed: tabs take `tab_width` columns, wide
+/// characters (for example CJK ideographs) two columns, and zero-width characters (for example
+/// combining marks) none.
+pub fn char_width(char: char, tab_width: u8) -> usize {
+    match char {
+        '\t' => tab_width as usize,
+        char => char.width().unwrap_or(0),
+    }
+}
+
+/// Returns the number of columns taken by a single level of indentation
+pub(crate) fn indent_width(options: &PrinterOptions) -> usize {
+    options
+        .indent_string
+        .chars()
+        .map(|c| char_width(c, options.tab_width))
+        .sum()
 }
 
 /// Printer state that is global to all elements.
@@ -485,6 +626,7 @@ struct PrinterState<'a> {
     source_markers: Vec<SourceMarker>,
     source_position: TextSize,
     pending_indent: u16,
+    pending_align: u16,
     pending_space: bool,
     measured_group_fits: bool,
     generated_line: usize,
@@ -493,10 +635,17 @@ struct PrinterState<'a> {
     has_empty_line: bool,
     line_suffixes: Vec<PrintElementCall<'a>>,
     verbatim_markers: Vec<TextRange>,
+    overflowing_lines: Vec<(usize, usize)>,
+    overflowing_tokens: Vec<TextRange>,
     group_modes: GroupModes,
     // Re-used queue to measure if a group fits. Optimisation to avoid re-allocating a new
     // vec everytime a group gets measured
     measure_queue: Vec<PrintElementCall<'a>>,
+    // Measurements of interned elements. Avoids measuring the same memoized content again
+    // and again, for example when it's part of multiple best fitting variants.
+    measured_interned: HashMap<MeasuredInternedKey, MeasuredInterned>,
+    // Number of elements inspected when measuring if content fits
+    measured_elements: usize,
 }
 
 /// Tracks the mode in which groups with ids are printed. Stores the groups at `group.id()` index.
@@ -535,6 +684,8 @@ impl GroupModes {
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct PrintElementArgs {
     indent: u16,
+    /// Number of spaces printed after the indention of a new line
+    align: u16,
     mode: PrintMode,
 }
 
@@ -551,6 +702,11 @@ impl PrintElementArgs {
         self
     }
 
+    pub fn with_align(mut self, align: u16) -> Self {
+        self.align = align;
+        self
+    }
+
     pub fn with_print_mode(mut self, mode: PrintMode) -> Self {
         self.mode = mode;
         self
@@ -561,6 +717,7 @@ impl Default for PrintElementArgs {
     fn default() -> Self {
         Self {
             indent: 0,
+            align: 0,
             mode: PrintMode::Expanded,
         }
     }
@@ -661,38 +818,36 @@ where
 
     let mut measure_state = MeasureState {
         pending_indent: printer.state.pending_indent,
+        pending_align: printer.state.pending_align,
         pending_space: printer.state.pending_space,
         line_width: printer.state.line_width,
         has_line_suffix: !printer.state.line_suffixes.is_empty(),
+        measured_elements: 0,
         group_modes: &mut printer.state.group_modes,
     };
 
     let result = loop {
-        match measure_queue.dequeue() {
-            None => {
-                break true;
-            }
+        let (element, args) = match measure_queue.dequeue() {
+            None => break true,
+            Some(next) => next,
+        };
 
-            Some((element, args)) => match fits_element_on_line(
-                element,
-                args,
-                &mut measure_state,
-                &mut measure_queue,
-                &printer.options,
-            ) {
-                Fits::Yes => {
-                    break true;
-                }
-                Fits::No => {
-                    break false;
-                }
-                Fits::Maybe => {
-                    continue;
-                }
-            },
+        match fits_next_element_on_line(
+            element,
+            args,
+            &mut measure_state,
+            &mut measure_queue,
+            &mut printer.state.measured_interned,
+            &printer.options,
+        ) {
+            Fits::Yes => break true,
+            Fits::No => break false,
+            Fits::Maybe => continue,
         }
     };
 
+    printer.state.measured_elements += measure_state.measured_elements;
+
     let mut shared_buffer = measure_queue.into_vec();
     // Clear out remaining items
     shared_buffer.clear();
@@ -701,6 +856,165 @@ where
     result
 }
 
+/// Tests if the passed element fits on the current line or not.
+/// Uses the cached measurement of the content for interned elements.
+fn fits_next_element_on_line<'a, 'rest>(
+    element: &'a FormatElement,
+    args: PrintElementArgs,
+    state: &mut MeasureState,
+    queue: &mut MeasureQueue<'a, 'rest>,
+    measured: &mut HashMap<MeasuredInternedKey, MeasuredInterned>,
+    options: &PrinterOptions,
+) -> Fits {
+    let fits = match element {
+        FormatElement::Interned(interned) => {
+            fits_interned_on_line(interned, args, state, measured, options)
+        }
+        _ => None,
+    };
+
+    fits.unwrap_or_else(|| fits_element_on_line(element, args, state, queue, options))
+}
+
+/// Tests if an interned element fits on the current line by using the measurement of
+/// its content cached in `measured`, measuring the content first if it isn't cached yet.
+///
+/// Returns `None` if the content must be measured element by element because its measurement
+/// depends on or changes state that isn't part of the cache key.
+fn fits_interned_on_line(
+    interned: &FormatElement,
+    args: PrintElementArgs,
+    state: &mut MeasureState,
+    measured: &mut HashMap<MeasuredInternedKey, MeasuredInterned>,
+    options: &PrinterOptions,
+) -> Option<Fits> {
+    let key = MeasuredInternedKey {
+        element: interned,
+        mode: args.mode,
+        pending_indent: state.pending_indent,
+        pending_align: state.pending_align,
+        pending_space: state.pending_space,
+        line_start: state.line_width == 0,
+    };
+
+    let measurement = match measured.get(&key) {
+        Some(measurement) => *measurement,
+        None => {
+            let measurement = measure_interned(interned, args, &key, state, measured, options);
+            measured.insert(key, measurement);
+            measurement
+        }
+    };
+
+    state.measured_elements += 1;
+
+    let (width, fits) = match measurement {
+        MeasuredInterned::Dynamic => return None,
+        MeasuredInterned::Open {
+            width,
+            pending_indent,
+            pending_align,
+            pending_space,
+        } => {
+            state.pending_indent = pending_indent;
+            state.pending_align = pending_align;
+            state.pending_space = pending_space;
+            (width, Fits::Maybe)
+        }
+        MeasuredInterned::Closed { width, fits } => (width, fits.into()),
+    };
+
+    state.line_width += width;
+
+    let fits = match fits {
+        // The content reached a line break: test the line up to it the same way as
+        // the measurement of a token containing a line break
+        Fits::Yes => fits_until_line_break(state, options),
+        Fits::Maybe if width > 0 && state.line_width > options.print_width.value().into() => {
+            Fits::No
+        }
+        fits => fits,
+    };
+
+    Some(fits)
+}
+
+/// Measures the content of an interned element on its own, starting from the state described by `key`.
+///
+/// The measured width is relative to the start of the content. The measurement still stops early
+/// if the content alone exceeds the print width: it then can't fit on the current line either.
+fn measure_interned(
+    interned: &FormatElement,
+    args: PrintElementArgs,
+    key: &MeasuredInternedKey,
+    outer_state: &mut MeasureState,
+    measured: &mut HashMap<MeasuredInternedKey, MeasuredInterned>,
+    options: &PrinterOptions,
+) -> MeasuredInterned {
+    // Never read because the content of groups with an id is measured element by element
+    let mut group_modes = GroupModes::default();
+
+    // Only the width added by the content matters, but `Space` tests if the line is empty
+    let start_width = usize::from(!key.line_start);
+    let mut state = MeasureState {
+        pending_indent: key.pending_indent,
+        pending_align: key.pending_align,
+        pending_space: key.pending_space,
+        has_line_suffix: false,
+        line_width: start_width,
+        measured_elements: 0,
+        group_modes: &mut group_modes,
+    };
+
+    let mut queue = MeasureQueue::new();
+    queue.enqueue(PrintElementCall::new(interned, args));
+
+    let measurement = loop {
+        let (element, args) = match queue.dequeue() {
+            None => {
+                break MeasuredInterned::Open {
+                    width: state.line_width - start_width,
+                    pending_indent: state.pending_indent,
+                    pending_align: state.pending_align,
+                    pending_space: state.pending_space,
+                }
+            }
+            Some(next) => next,
+        };
+
+        let is_dynamic = match element {
+            FormatElement::Group(group) => group.id.is_some(),
+            FormatElement::ConditionalGroupContent(conditional) => conditional.group_id.is_some(),
+            FormatElement::LineSuffix(_) | FormatElement::LineSuffixBoundary => true,
+            _ => false,
+        };
+
+        if is_dynamic {
+            break MeasuredInterned::Dynamic;
+        }
+
+        match fits_next_element_on_line(element, args, &mut state, &mut queue, measured, options) {
+            Fits::Yes => {
+                break MeasuredInterned::Closed {
+                    width: state.line_width - start_width,
+                    fits: true,
+                }
+            }
+            Fits::No => {
+                break MeasuredInterned::Closed {
+                    width: state.line_width - start_width,
+                    fits: false,
+                }
+            }
+            Fits::Maybe => {}
+        }
+    };
+
+    outer_state.measured_elements += state.measured_elements;
+
+    measurement
+}
+
 /// Tests if the passed element fits on the current line or not.
 fn fits_element_on_line<'a, 'rest>(
     element: &'a FormatElement,
@@ -709,6 +1023,8 @@ fn fits_element_on_line<'a, 'rest>(
     queue: &mut MeasureQueue<'a, 'rest>,
     options: &PrinterOptions,
 ) -> Fits {
+    state.measured_elements += 1;
+
     match element {
         FormatElement::Space => {
             if state.line_width > 0 {
@@ -740,7 +1056,15 @@ fn fits_element_on_line<'a, 'rest>(
             queue.extend(content.iter(), args.with_incremented_indent())
         }
 
+        // The alignment only applies to lines following a line break, which don't
+        // get measured by `fits`
+        FormatElement::Align(content) => queue.extend(content.iter(), args),
+
         FormatElement::Group(group) => {
+            if group.expand && args.mode.is_flat() {
+                return Fits::No;
+            }
+
             queue.extend(group.content.iter(), args);
 
             if let Some(id) = group.id {
@@ -764,31 +1088,36 @@ fn fits_element_on_line<'a, 'rest>(
 
         FormatElement::List(list) => queue.extend(list.iter(), args),
 
-        FormatElement::Fill(fill) => queue.queue.0.extend(
-            Intersperse::new(fill.content().iter().rev(), fill.separator())
-                .map(|t| PrintElementCall::new(t, args)),
-        ),
+        FormatElement::Fill(fill) => {
+            if fill.has_expanded_separator() && args.mode.is_flat() {
+                return Fits::No;
+            }
+
+            queue.queue.0.extend(
+                Intersperse::new(fill.content().iter().rev(), fill.separator())
+                    .map(|t| PrintElementCall::new(t, args)),
+            )
+        }
 
         FormatElement::Token(token) => {
-            state.line_width += state.pending_indent as usize * options.indent_string.len();
+            state.line_width += state.pending_indent as usize * indent_width(options);
             state.pending_indent = 0;
+            state.line_width += state.pending_align as usize;
+            state.pending_align = 0;
 
             if state.pending_space {
                 state.line_width += 1;
             }
 
             for c in token.chars() {
-                let char_width = match c {
-                    '\t' => options.tab_width,
-                    '\n' => {
-                        return match args.mode {
-                            PrintMode::Flat => Fits::No,
-                            PrintMode::Expanded => Fits::Yes,
-                        }
-                    }
-                    _ => 1,
-                };
-                state.line_width += char_width as usize;
+                if c == '\n' {
+                    return match args.mode {
+                        PrintMode::Flat => Fits::No,
+                        PrintMode::Expanded => fits_until_line_break(state, options),
+                    };
+                }
+
+                state.line_width += char_width(c, options.tab_width);
             }
 
             if state.line_width > options.print_width.value().into() {
@@ -831,6 +1160,15 @@ fn fits_element_on_line<'a, 'rest>(
     Fits::Maybe
 }
 
+/// Tests if the line measured so far fits when the measurement reaches a line break in expanded mode
+fn fits_until_line_break(state: &MeasureState, options: &PrinterOptions) -> Fits {
+    if state.line_width > options.print_width.value().into() {
+        Fits::No
+    } else {
+        Fits::Yes
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum Fits {
     // Element fits
@@ -854,12 +1192,45 @@ impl From<bool> for Fits {
 #[derive(Debug)]
 struct MeasureState<'group> {
     pending_indent: u16,
+    pending_align: u16,
     pending_space: bool,
     has_line_suffix: bool,
     line_width: usize,
+    measured_elements: usize,
     group_modes: &'group mut GroupModes,
 }
 
+/// Identifies the measurement of an interned element. Measuring the same content
+/// in a different mode or from a different state can give a different result.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct MeasuredInternedKey {
+    element: *const FormatElement,
+    mode: PrintMode,
+    pending_indent: u16,
+    pending_align: u16,
+    pending_space: bool,
+    /// `true` if nothing has been written to the line yet
+    line_start: bool,
+}
+
+/// Measurement of the content of an interned element, relative to the state in which the measurement started.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MeasuredInterned {
+    /// The whole content was measured without reaching a line break.
+    Open {
+        width: usize,
+        pending_indent: u16,
+        pending_align: u16,
+        pending_space: bool,
+    },
+    /// The measurement stopped inside of the content after adding `width` to the line, for example
+    /// because the content contains a hard line break.
+    Closed { width: usize, fits: bool },
+    /// The content contains elements that depend on the state of the measurement outside of it,
+    /// like the mode of a group with an id, or that change that state.
+    Dynamic,
+}
+
 #[derive(Debug)]
 struct MeasureQueue<'a, 'rest> {
     /// Queue that holds the elements that the `fits` operation inspects.
@@ -928,9 +1299,11 @@ impl<'a, 'rest> MeasureQueue<'a, 'rest> {
 
 #[cfg(test)]
 mod tests {
+    use crate::format_element::{LineMode, Token};
     use crate::prelude::*;
-    use crate::printer::{LineEnding, Printer, PrinterOptions};
+    use crate::printer::{LineEnding, LineWidthViolationPolicy, Printer, PrinterOptions};
     use crate::{format_args, write, FormatState, LineWidth, Printed, VecBuffer};
+    use rome_rowan::{TextRange, TextSize};
 
     fn format(root: &dyn Format<()>) -> Printed {
         format_with_options(
@@ -965,6 +1338,65 @@ mod tests {
         assert_eq!(r#"["a", "b", "c", "d"]"#, result.as_code())
     }
 
+    #[test]
+    fn it_reports_the_remaining_width_of_the_current_line() {
+        let result = format(&format_args![
+            token("a"),
+            indent(&format_args![
+                hard_line_break(),
+                token("abc"),
+                space_token()
+            ])
+        ]);
+
+        assert_eq!("a\n  abc", result.as_code());
+        // Two columns of indention, `abc`, and the pending space
+        assert_eq!(result.remaining_width(), 74);
+    }
+
+    #[test]
+    fn it_reports_the_remaining_width_while_printing() {
+        const COMMENT: &str = "// a comment that exceeds the remaining width";
+
+        let mut state = FormatState::new(());
+        let mut buffer = VecBuffer::new(&mut state);
+        write!(
+            &mut buffer,
+            [
+                token("{"),
+                indent(&format_args![hard_line_break(), token("let value = 1;")])
+            ]
+        )
+        .unwrap();
+        let statement = buffer.into_element();
+        let comment = FormatElement::Token(Token::Static { text: COMMENT });
+
+        let mut printer = Printer::new(PrinterOptions {
+            indent_string: String::from("  "),
+            print_width: LineWidth::try_from(60).unwrap(),
+            ..PrinterOptions::default()
+        });
+
+        printer.print_element_incrementally(&statement);
+        // Two columns of indention and `let value = 1;`
+        assert_eq!(printer.remaining_width(), 44);
+
+        // Only inline the comment if it fits on the current line
+        let separator = if usize::from(printer.remaining_width()) > COMMENT.len() {
+            FormatElement::Space
+        } else {
+            FormatElement::Line(LineMode::Hard)
+        };
+        printer.print_element_incrementally(&separator);
+        printer.print_element_incrementally(&comment);
+
+        let result = printer.finish();
+        assert_eq!(
+            "{\n  let value = 1;\n// a comment that exceeds the remaining width",
+            result.as_code()
+        );
+    }
+
     #[test]
     fn it_tracks_the_indent_for_each_token() {
         let formatted = format(&format_args!(
@@ -1045,6 +1477,38 @@ two lines`,
         assert_eq!("a\n  b\n", result.as_code())
     }
 
+    #[test]
+    fn it_expands_a_group_containing_a_nested_hard_line_break() {
+        let mut state = FormatState::new(());
+        let mut buffer = VecBuffer::new(&mut state);
+
+        write!(
+            &mut buffer,
+            [group_elements(&format_args![
+                token("a"),
+                soft_line_break_or_space(),
+                indent(&format_args![token("b"), hard_line_break()]),
+                token("c")
+            ])]
+        )
+        .unwrap();
+
+        let element = buffer.into_element();
+
+        match &element {
+            FormatElement::Group(group) => assert!(group.will_expand()),
+            element => panic!("expected a group but got {element:?}"),
+        }
+
+        let result = Printer::new(PrinterOptions {
+            indent_string: String::from("  "),
+            ..PrinterOptions::default()
+        })
+        .print(&element);
+
+        assert_eq!("a\nb\n  c", result.as_code())
+    }
+
     #[test]
     fn it_breaks_parent_groups_if_they_dont_fit_on_a_single_line() {
         let result = format(&FormatArrayElements {
@@ -1096,6 +1560,127 @@ two lines`,
         assert_eq!("[\n\t'a',\n\t\'b',\n\t\'c',\n\t'd',\n]", result.as_code());
     }
 
+    #[test]
+    fn it_reports_overflowing_lines_using_the_tab_width() {
+        let options = PrinterOptions {
+            indent_string: String::from("\t"),
+            tab_width: 4,
+            print_width: LineWidth::try_from(10).unwrap(),
+            ..PrinterOptions::default()
+        };
+
+        // The second line has 9 characters but is 15 columns wide: the indention and the tab at
+        // the start of the token are 4 columns each
+        let result = format_with_options(
+            &format_args![
+                token("short"),
+                block_indent(&format_args![token("\tlong"), token("est")]),
+                token("end"),
+            ],
+            options,
+        );
+
+        assert_eq!("short\n\t\tlongest\nend", result.as_code());
+        assert_eq!(result.overflowing_lines(), &[(1, 15)]);
+    }
+
+    #[test]
+    fn it_measures_the_pending_indent_using_the_tab_width() {
+        let options = PrinterOptions {
+            indent_string: String::from("\t"),
+            tab_width: 4,
+            print_width: LineWidth::try_from(10).unwrap(),
+            ..PrinterOptions::default()
+        };
+
+        // The group has 6 characters but starts after two levels of indention of 4 columns each
+        let result = format_with_options(
+            &block_indent(&block_indent(&group_elements(&format_args![
+                token("aaa"),
+                soft_line_break_or_space(),
+                token("bb")
+            ]))),
+            options,
+        );
+
+        assert_eq!("\t\taaa\n\t\tbb\n", result.as_code());
+    }
+
+    #[test]
+    fn it_reports_overflowing_tokens_with_the_warn_policy() {
+        let options = PrinterOptions {
+            print_width: LineWidth::try_from(20).unwrap(),
+            ..PrinterOptions::default()
+        };
+
+        let content = format_with(|f| {
+            write!(
+                f,
+                [group_elements(&format_args![
+                    token("fetch("),
+                    soft_block_indent(&token("\"https://rome.tools/docs/formatter\"")),
+                    token(")")
+                ])]
+            )
+        });
+
+        let result = format_with_options(&content, options.clone());
+
+        assert_eq!(
+            "fetch(\n\t\"https://rome.tools/docs/formatter\"\n)",
+            result.as_code()
+        );
+        assert_eq!(result.overflowing_lines(), &[(1, 37)]);
+        assert!(result.overflowing_tokens().is_empty());
+
+        let result = format_with_options(
+            &content,
+            options.with_max_line_width_violation_policy(LineWidthViolationPolicy::Warn),
+        );
+
+        assert_eq!(result.overflowing_lines(), &[(1, 37)]);
+        assert_eq!(
+            result.overflowing_tokens(),
+            &[TextRange::new(TextSize::from(8), TextSize::from(43))]
+        );
+    }
+
+    #[test]
+    fn it_measures_characters_with_their_display_width() {
+        let options = PrinterOptions {
+            print_width: LineWidth::try_from(20).unwrap(),
+            ..PrinterOptions::default()
+        };
+
+        // 15 characters but 24 columns wide
+        let result = format_with_options(
+            &group_elements(&format_args![
+                token("\"學而時習之\","),
+                soft_line_break_or_space(),
+                token("\"不亦說乎\"")
+            ]),
+            options.clone(),
+        );
+
+        assert_eq!("\"學而時習之\",\n\"不亦說乎\"", result.as_code());
+        assert!(result.overflowing_lines().is_empty());
+
+        // 25 characters but 17 columns wide because combining accents don't take any column
+        let result = format_with_options(
+            &group_elements(&format_args![
+                token("\"e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}\","),
+                soft_line_break_or_space(),
+                token("\"abc\"")
+            ]),
+            options,
+        );
+
+        assert_eq!(
+            "\"e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}\", \"abc\"",
+            result.as_code()
+        );
+    }
+
     #[test]
     fn it_prints_consecutive_hard_lines_as_one() {
         let result = format(&format_args![
@@ -1176,6 +1761,29 @@ two lines`,
         )
     }
 
+    #[test]
+    fn test_fill_expanded_separator() {
+        let mut state = FormatState::new(());
+        let mut buffer = VecBuffer::new(&mut state);
+        let mut formatter = Formatter::new(&mut buffer);
+
+        formatter
+            .fill(&soft_line_break_or_space())
+            .entry(&token("1"))
+            .entry(&token("2"))
+            // Breaks even though `3` fits on the line
+            .entry_with_separator_mode(FillSeparatorMode::Expanded, &token("3"))
+            .entry(&token("4"))
+            .finish()
+            .unwrap();
+
+        let document = buffer.into_element();
+
+        let printed = Printer::new(PrinterOptions::default()).print(&document);
+
+        assert_eq!(printed.as_code(), "1 2\n3 4")
+    }
+
     #[test]
     fn line_suffix_printed_at_end() {
         let printed = format(&format_args![
@@ -1201,6 +1809,42 @@ two lines`,
         assert_eq!(printed.as_code(), "[1, 2, 3]; // trailing")
     }
 
+    #[test]
+    fn line_suffixes_flushed_before_hard_line_break() {
+        let printed = format(&format_args![
+            token("a"),
+            line_suffix(&token(" // first")),
+            line_suffix(&token(" // second")),
+            hard_line_break(),
+            token("b"),
+        ]);
+
+        assert_eq!(printed.as_code(), "a // first // second\nb")
+    }
+
+    #[test]
+    fn line_suffixes_flushed_in_insertion_order() {
+        // Flushed at the end of the document
+        let printed = format(&format_args![
+            token("x"),
+            line_suffix(&token("a")),
+            line_suffix(&format_args![token("b"), token("c")]),
+        ]);
+
+        assert_eq!(printed.as_code(), "xabc");
+
+        // Flushed by a line suffix boundary
+        let printed = format(&format_args![
+            token("x"),
+            line_suffix(&token("a")),
+            line_suffix(&token("b")),
+            line_suffix_boundary(),
+            token("y"),
+        ]);
+
+        assert_eq!(printed.as_code(), "xab\ny");
+    }
+
     #[test]
     fn conditional_with_group_id_in_fits() {
         let content = format_with(|f| {
@@ -1228,6 +1872,120 @@ two lines`,
         assert_eq!(printed.as_code(), "The referenced group breaks.\nThis group breaks because:\nIt measures with the 'if_group_breaks' variant because the referenced group breaks and that's just way too much text.");
     }
 
+    #[test]
+    fn best_fitting_picks_first_variant_that_fits() {
+        let content = format_with(|f| {
+            write!(
+                f,
+                [best_fitting!(
+                    token("aaaa(bbbb, cccc)"),
+                    format_args!(token("aaaa(bbbb,"), hard_line_break(), token("cccc)")),
+                    format_args!(
+                        token("aaaa("),
+                        hard_line_break(),
+                        token("bbbb,"),
+                        hard_line_break(),
+                        token("cccc)")
+                    ),
+                    format_args!(
+                        token("aaaa"),
+                        hard_line_break(),
+                        token("("),
+                        hard_line_break(),
+                        token("bbbb,"),
+                        hard_line_break(),
+                        token("cccc"),
+                        hard_line_break(),
+                        token(")")
+                    )
+                )]
+            )
+        });
+
+        let print_with_width = |width: u16| {
+            format_with_options(
+                &content,
+                PrinterOptions::default().with_print_width(LineWidth(width)),
+            )
+            .as_code()
+            .to_string()
+        };
+
+        // The first variant is measured in flat mode
+        assert_eq!(print_with_width(80), "aaaa(bbbb, cccc)");
+        assert_eq!(print_with_width(16), "aaaa(bbbb, cccc)");
+
+        // The other variants only need their first line to fit
+        assert_eq!(print_with_width(15), "aaaa(bbbb,\ncccc)");
+        assert_eq!(print_with_width(10), "aaaa(bbbb,\ncccc)");
+        assert_eq!(print_with_width(9), "aaaa(\nbbbb,\ncccc)");
+        assert_eq!(print_with_width(5), "aaaa(\nbbbb,\ncccc)");
+
+        // Falls back to the last variant, even if it doesn't fit either
+        assert_eq!(print_with_width(4), "aaaa\n(\nbbbb,\ncccc\n)");
+        assert_eq!(print_with_width(2), "aaaa\n(\nbbbb,\ncccc\n)");
+    }
+
+    #[test]
+    fn measures_memoized_content_once() {
+        let print = |memoize: bool| {
+            let mut state = FormatState::new(());
+            let mut buffer = VecBuffer::new(&mut state);
+
+            write!(&mut buffer, [FormatNestedCall { depth: 6, memoize }]).unwrap();
+
+            let document = buffer.into_element();
+            let printed = Printer::new(PrinterOptions::default().with_print_width(LineWidth(30)))
+                .print(&document);
+
+            let measured = printed.measured_elements();
+            (printed.into_code(), measured)
+        };
+
+        let (memoized_code, memoized_measured) = print(true);
+        let (code, measured) = print(false);
+
+        assert_eq!(memoized_code, code);
+        assert!(
+            memoized_measured < measured,
+            "Expected memoized content to be measured less often ({memoized_measured} >= {measured})"
+        );
+    }
+
+    #[test]
+    fn measures_multiline_tokens_in_memoized_content_like_other_tokens() {
+        let print = |memoize: bool| {
+            let multiline = format_with(|f| write!(f, [token("cccccccc\nd")])).memoized();
+
+            let content = format_with(|f| {
+                write!(
+                    f,
+                    [group_elements(&format_args![
+                        token("a"),
+                        soft_line_break_or_space(),
+                        token("b")
+                    ])]
+                )?;
+
+                if memoize {
+                    write!(f, [multiline])
+                } else {
+                    write!(f, [token("cccccccc\nd")])
+                }
+            });
+
+            format_with_options(
+                &content,
+                PrinterOptions::default().with_print_width(LineWidth(10)),
+            )
+            .into_code()
+        };
+
+        // The first line of the token doesn't fit after the flat group
+        assert_eq!(print(false), "a\nbcccccccc\nd");
+        assert_eq!(print(true), print(false));
+    }
+
     struct FormatArrayElements<'a> {
         items: Vec<&'a dyn Format<()>>,
     }
@@ -1250,4 +2008,46 @@ two lines`,
             )
         }
     }
+
+    /// Formats nested calls that pick the best fitting layout for their argument,
+    /// which is either memoized or formatted once for every variant.
+    struct FormatNestedCall {
+        depth: usize,
+        memoize: bool,
+    }
+
+    impl Format<()> for FormatNestedCall {
+        fn fmt(&self, f: &mut Formatter<()>) -> FormatResult<()> {
+            if self.depth == 0 {
+                return write!(f, [token("argument")]);
+            }
+
+            let inner = FormatNestedCall {
+                depth: self.depth - 1,
+                memoize: self.memoize,
+            };
+            let memoized = format_with(|f| write!(f, [inner])).memoized();
+
+            let argument = format_with(|f| {
+                if self.memoize {
+                    write!(f, [memoized])
+                } else {
+                    write!(f, [inner])
+                }
+            });
+
+            write!(
+                f,
+                [best_fitting!(
+                    format_args!(token("call("), argument, token(")")),
+                    format_args!(
+                        token("call("),
+                        group_elements(&soft_block_indent(&argument)),
+                        token(")")
+                    ),
+                    format_args!(token("call("), block_indent(&argument), token(")"))
+                )]
+            )
+        }
+    }
 }
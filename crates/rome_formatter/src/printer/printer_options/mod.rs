@@ -18,6 +18,9 @@ pub struct PrinterOptions {
     /// * Tab: Value is '\t'
     /// * Spaces: String containing the number of spaces per indention level, e.g. "  " for using two spaces
     pub indent_string: String,
+
+    /// Whether the printer should record tokens that on their own are wider than the print width
+    pub max_line_width_violation_policy: LineWidthViolationPolicy,
 }
 
 impl PrinterOptions {
@@ -26,6 +29,11 @@ impl PrinterOptions {
         self
     }
 
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
     pub fn with_indent(mut self, style: IndentStyle) -> Self {
         match style {
             IndentStyle::Tab => {
@@ -40,10 +48,17 @@ impl PrinterOptions {
 
         self
     }
+
+    pub fn with_max_line_width_violation_policy(
+        mut self,
+        policy: LineWidthViolationPolicy,
+    ) -> Self {
+        self.max_line_width_violation_policy = policy;
+        self
+    }
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum LineEnding {
     ///  Line Feed only (\n), common on Linux and macOS as well as inside git repos
     LineFeed,
@@ -66,13 +81,37 @@ impl LineEnding {
     }
 }
 
+impl Default for LineEnding {
+    fn default() -> Self {
+        LineEnding::LineFeed
+    }
+}
+
+/// Policy applied by the printer to tokens that can't be broken and are wider than the print width
+/// on their own, for example a long URL in a string literal
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineWidthViolationPolicy {
+    /// Print the token without recording anything
+    Allow,
+
+    /// Print the token and record its range in [crate::Printed::overflowing_tokens]
+    Warn,
+}
+
+impl Default for LineWidthViolationPolicy {
+    fn default() -> Self {
+        LineWidthViolationPolicy::Allow
+    }
+}
+
 impl Default for PrinterOptions {
     fn default() -> Self {
         PrinterOptions {
             tab_width: 2,
             print_width: LineWidth::default(),
             indent_string: String::from("\t"),
-            line_ending: LineEnding::LineFeed,
+            line_ending: LineEnding::default(),
+            max_line_width_violation_policy: LineWidthViolationPolicy::default(),
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::{
-    format_args, write, Argument, Arguments, CommentContext, CommentKind, CommentStyle, GroupId,
-    LastTokenKind, SourceComment,
+    format_args, write, Argument, Arguments, CommentContext, CommentKind, CommentPlacement,
+    CommentStyle, GroupId, LastTokenKind, SourceComment,
 };
 use rome_rowan::{Language, SyntaxToken, SyntaxTriviaPiece};
 
@@ -29,8 +29,7 @@ where
 
         f.state_mut().set_last_token_kind(self.token.kind());
 
-        let trimmed_range = self.token.text_trimmed_range();
-        syntax_token_text_slice(self.token, trimmed_range).fmt(f)
+        syntax_token(self.token).fmt(f)
     }
 }
 
@@ -66,7 +65,7 @@ where
     }
 }
 
-fn write_space_between_comment_and_token<L: Language, Context>(
+fn write_space_between_comment_and_token<L: Language + 'static, Context>(
     token_kind: <L as Language>::Kind,
     f: &mut Formatter<Context>,
 ) -> FormatResult<()>
@@ -75,11 +74,22 @@ where
 {
     let is_last_content_inline_content = f.state().is_last_content_inline_comment();
 
-    // Insert a space if the previous token has any trailing comments and this is not a group
-    // end token
-    if is_last_content_inline_content && !f.context().comment_style().is_group_end_token(token_kind)
-    {
-        space_token().fmt(f)?;
+    // Insert a space if the previous token has any trailing comments, unless the comments
+    // are attached to this token
+    if is_last_content_inline_content {
+        let last_token = f
+            .state()
+            .last_token_kind()
+            .and_then(|kind| kind.as_language::<L>());
+
+        let placement = f
+            .context()
+            .comment_style()
+            .comment_boundary(last_token, Some(token_kind));
+
+        if placement != CommentPlacement::AttachedToNext {
+            space_token().fmt(f)?;
+        }
     }
 
     f.state_mut().set_last_content_inline_comment(false);
@@ -798,11 +808,14 @@ where
 
             let content = format_with(|f: &mut Formatter<C>| {
                 if !is_single_line {
-                    match self.token_kind {
-                        // Don't write a space if this is a group start token and it isn't the first trailing comment
-                        Some(token)
-                            if f.context().comment_style().is_group_start_token(token)
-                                && index == 0 => {}
+                    let placement = f
+                        .context()
+                        .comment_style()
+                        .comment_boundary(self.token_kind, None);
+
+                    match placement {
+                        // Don't write a space if the first trailing comment is attached to the token
+                        CommentPlacement::AttachedToPrevious if index == 0 => {}
                         //  Write a space for all other cases
                         _ => space_token().fmt(f)?,
                     }
@@ -4,13 +4,34 @@
 //! - the [FileHandlers] for the specific file
 //! - shortcuts to open/write to the file
 use std::fs::read_to_string;
+use std::hash::{Hash, Hasher};
 use std::io::Read;
 use std::{fs::File, io, io::Write, ops::Deref, path::PathBuf};
 
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+/// The UTF-8 byte order mark
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, Clone)]
 pub struct RomePath {
     path: PathBuf,
     id: usize,
+    /// Whether the content read by [RomePath::get_buffer_from_file] started with a byte order mark
+    has_bom: bool,
+}
+
+impl PartialEq for RomePath {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.id == other.id
+    }
+}
+
+impl Eq for RomePath {}
+
+impl Hash for RomePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.id.hash(state);
+    }
 }
 
 impl Deref for RomePath {
@@ -26,6 +47,7 @@ impl RomePath {
         Self {
             path: path_to_file.into(),
             id,
+            has_bom: false,
         }
     }
 
@@ -36,13 +58,23 @@ impl RomePath {
     }
 
     /// Accepts a file opened in read mode and saves into it
+    ///
+    /// The byte order mark is written back if the file had one when it was read
+    /// with [RomePath::get_buffer_from_file].
     pub fn save(&mut self, content: &str) -> Result<(), std::io::Error> {
         let mut file_to_write = File::create(&self.path).unwrap();
         // TODO: handle error with diagnostic
+        if self.has_bom {
+            let mut bom = [0; 3];
+            file_to_write.write_all(BOM.encode_utf8(&mut bom).as_bytes())?;
+        }
         file_to_write.write_all(content.as_bytes())
     }
 
     /// Returns the contents of a file, if it exists
+    ///
+    /// A leading byte order mark is removed from the returned content
+    /// and recorded in [RomePath::has_bom].
     pub fn get_buffer_from_file(&mut self) -> String {
         let mut file = self.open();
         let mut buffer = String::new();
@@ -50,9 +82,20 @@ impl RomePath {
         file.read_to_string(&mut buffer)
             .expect("cannot read the file to format");
 
+        self.has_bom = buffer.starts_with(BOM);
+        if self.has_bom {
+            buffer.drain(..BOM.len_utf8());
+        }
+
         buffer
     }
 
+    /// Returns `true` if the content read by [RomePath::get_buffer_from_file]
+    /// started with a byte order mark
+    pub fn has_bom(&self) -> bool {
+        self.has_bom
+    }
+
     /// Small wrapper for [read_to_string]
     pub fn read_to_string(&self) -> io::Result<String> {
         let path = self.path.as_path();
@@ -71,3 +114,51 @@ impl RomePath {
             .expect("Can't read the file")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RomePath;
+    use std::fs;
+
+    fn read(name: &str, content: &[u8]) -> (RomePath, String) {
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, content).unwrap();
+
+        let mut rome_path = RomePath::new(&path, 0);
+        let buffer = rome_path.get_buffer_from_file();
+        fs::remove_file(&path).unwrap();
+
+        (rome_path, buffer)
+    }
+
+    #[test]
+    fn strips_byte_order_mark() {
+        let (rome_path, buffer) = read("rome_fs_bom.js", b"\xef\xbb\xbflet a = 1;");
+
+        assert_eq!(buffer, "let a = 1;");
+        assert!(rome_path.has_bom());
+    }
+
+    #[test]
+    fn keeps_content_without_byte_order_mark() {
+        let (rome_path, buffer) = read("rome_fs_no_bom.js", b"let a = 1;");
+
+        assert_eq!(buffer, "let a = 1;");
+        assert!(!rome_path.has_bom());
+    }
+
+    #[test]
+    fn save_restores_byte_order_mark() {
+        let path = std::env::temp_dir().join("rome_fs_save_bom.js");
+        fs::write(&path, b"\xef\xbb\xbflet a = 1;").unwrap();
+
+        let mut rome_path = RomePath::new(&path, 0);
+        rome_path.get_buffer_from_file();
+        rome_path.save("let b = 2;").unwrap();
+
+        let content = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(content, b"\xef\xbb\xbflet b = 2;");
+    }
+}
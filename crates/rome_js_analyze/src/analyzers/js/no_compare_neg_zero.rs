@@ -33,7 +33,11 @@ declare_rule! {
     /// ```js
     /// (1 >= 0)
     ///```
-    pub(crate) NoCompareNegZero = "noCompareNegZero"
+    pub(crate) NoCompareNegZero {
+        version: "0.7.0",
+        name: "noCompareNegZero",
+        recommended: true,
+    }
 }
 
 impl Rule for NoCompareNegZero {
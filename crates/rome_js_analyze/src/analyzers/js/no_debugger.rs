@@ -28,7 +28,11 @@ declare_rule! {
     /// const test = { debugger: 1 };
     /// test.debugger;
     ///```
-    pub(crate) NoDebugger = "noDebugger"
+    pub(crate) NoDebugger {
+        version: "0.7.0",
+        name: "noDebugger",
+        recommended: true,
+    }
 }
 
 impl Rule for NoDebugger {
@@ -36,7 +36,11 @@ declare_rule! {
     /// const foo = new Set([1,2,3]);
     /// foo.delete(1);
     ///```
-    pub(crate) NoDelete = "noDelete"
+    pub(crate) NoDelete {
+        version: "0.7.0",
+        name: "noDelete",
+        recommended: true,
+    }
 }
 
 impl Rule for NoDelete {
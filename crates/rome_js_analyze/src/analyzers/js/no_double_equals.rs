@@ -3,7 +3,6 @@ use rome_analyze::{
 };
 use rome_console::markup;
 use rome_diagnostics::Applicability;
-use rome_js_factory::make;
 use rome_js_syntax::{JsAnyExpression, JsAnyLiteralExpression, JsBinaryExpression, T};
 use rome_js_syntax::{JsSyntaxKind::*, JsSyntaxToken};
 use rome_rowan::{AstNodeExt, SyntaxResult};
@@ -46,7 +45,11 @@ declare_rule! {
     /// ```js
     /// null != foo
     ///```
-    pub(crate) NoDoubleEquals = "noDoubleEquals"
+    pub(crate) NoDoubleEquals {
+        version: "0.7.0",
+        name: "noDoubleEquals",
+        recommended: true,
+    }
 }
 
 impl Rule for NoDoubleEquals {
@@ -93,17 +96,18 @@ impl Rule for NoDoubleEquals {
 
     fn action(ctx: &RuleContext<Self>, op: &Self::State) -> Option<JsRuleAction> {
         let suggestion = if op.kind() == EQ2 { T![===] } else { T![!==] };
+        // SAFETY: `suggestion` can only be JsSyntaxKind::EQ3 or JsSyntaxKind::NEQ2,
+        // the implementation of `to_string` for these two variants always returns Some
+        let text = suggestion.to_string().unwrap();
+
         let root = ctx
             .root()
-            .replace_token(op.clone(), make::token(suggestion))?;
+            .replace_token_discard_trivia(op.clone(), op.clone().with_kind(suggestion, text))?;
 
         Some(JsRuleAction {
             category: ActionCategory::QuickFix,
             applicability: Applicability::MaybeIncorrect,
-            // SAFETY: `suggestion` can only be JsSyntaxKind::EQ3 or JsSyntaxKind::NEQ2,
-            // the implementation of `to_string` for these two variants always returns Some
-            message: markup! { "Use "<Emphasis>{suggestion.to_string().unwrap()}</Emphasis> }
-                .to_owned(),
+            message: markup! { "Use "<Emphasis>{text}</Emphasis> }.to_owned(),
             root,
         })
     }
@@ -0,0 +1,95 @@
+use rome_analyze::{context::RuleContext, declare_rule, Ast, Rule, RuleCategory, RuleDiagnostic};
+use rome_console::markup;
+use rome_js_syntax::{JsBlockStatement, JsCatchClause, JsFunctionBody};
+use rome_rowan::{declare_node_union, AstNode, AstNodeList};
+
+declare_rule! {
+    /// Disallow empty block statements and function bodies
+    ///
+    /// Empty blocks are often the result of an incomplete refactoring. Blocks that only contain
+    /// comments are allowed since the comments document that the block is intentionally empty.
+    /// Empty `catch` clauses are allowed too, as they're a common way to ignore an error.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```js,expect_diagnostic
+    /// if (foo) {}
+    /// ```
+    ///
+    /// ```js,expect_diagnostic
+    /// function foo() {}
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```js
+    /// if (foo) {
+    ///     // empty
+    /// }
+    /// try {
+    ///     foo();
+    /// } catch (e) {}
+    /// ```
+    pub(crate) NoEmptyBlock {
+        version: "next",
+        name: "noEmptyBlock",
+        recommended: false,
+    }
+}
+
+impl Rule for NoEmptyBlock {
+    const CATEGORY: RuleCategory = RuleCategory::Lint;
+
+    type Query = Ast<JsAnyBlockLike>;
+    type State = ();
+    type Signals = Option<Self::State>;
+
+    fn run(ctx: &RuleContext<Self>) -> Option<Self::State> {
+        let node = ctx.query();
+
+        let is_empty = match node {
+            JsAnyBlockLike::JsBlockStatement(block) => {
+                let is_catch_body = block
+                    .syntax()
+                    .parent()
+                    .map_or(false, |parent| JsCatchClause::can_cast(parent.kind()));
+
+                !is_catch_body && block.statements().is_empty()
+            }
+            JsAnyBlockLike::JsFunctionBody(body) => {
+                body.directives().is_empty() && body.statements().is_empty()
+            }
+        };
+
+        if is_empty && !node.syntax().has_inner_comments() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn diagnostic(ctx: &RuleContext<Self>, _: &Self::State) -> Option<RuleDiagnostic> {
+        let node = ctx.query();
+        let block_type = match node {
+            JsAnyBlockLike::JsBlockStatement(_) => "block statement",
+            JsAnyBlockLike::JsFunctionBody(_) => "function body",
+        };
+
+        Some(
+            RuleDiagnostic::warning(
+                node.range(),
+                markup! {
+                    "Unexpected empty "{block_type}"."
+                },
+            )
+            .footer_note("Add a comment inside the block if it's intentionally empty."),
+        )
+    }
+}
+
+declare_node_union! {
+    /// enum of `JsBlockStatement` and `JsFunctionBody`
+    pub(crate) JsAnyBlockLike = JsBlockStatement | JsFunctionBody
+}
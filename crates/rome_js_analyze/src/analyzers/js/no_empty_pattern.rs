@@ -32,7 +32,11 @@ declare_rule! {
     /// function foo({a = []}) {}
     /// var [a] = foo;
     /// ```
-    pub(crate) NoEmptyPattern = "noEmptyPattern"
+    pub(crate) NoEmptyPattern {
+        version: "0.7.0",
+        name: "noEmptyPattern",
+        recommended: true,
+    }
 }
 
 impl Rule for NoEmptyPattern {
@@ -0,0 +1,79 @@
+use rome_analyze::{context::RuleContext, declare_rule, Ast, Rule, RuleCategory, RuleDiagnostic};
+use rome_console::markup;
+use rome_js_syntax::{matches_test_call, JsCallExpression, TextRange};
+use rome_rowan::AstNode;
+
+declare_rule! {
+    /// Disallow focused tests
+    ///
+    /// Focusing a test with `.only` or with the `fit`, `fdescribe` and `ftest` functions
+    /// skips every other test of the suite. It's useful while writing a test but it shouldn't
+    /// be committed.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```js,expect_diagnostic
+    /// describe.only("foo", () => {});
+    /// ```
+    ///
+    /// ```js,expect_diagnostic
+    /// test.describe.parallel.only("foo", () => {});
+    /// ```
+    ///
+    /// ```js,expect_diagnostic
+    /// fit("foo", () => {});
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```js
+    /// describe("foo", () => {});
+    /// it.skip("foo", () => {});
+    /// foo.only();
+    /// ```
+    pub(crate) NoFocusedTests {
+        version: "next",
+        name: "noFocusedTests",
+        recommended: true,
+    }
+}
+
+impl Rule for NoFocusedTests {
+    const CATEGORY: RuleCategory = RuleCategory::Lint;
+
+    type Query = Ast<JsCallExpression>;
+    type State = TextRange;
+    type Signals = Option<Self::State>;
+
+    fn run(ctx: &RuleContext<Self>) -> Option<Self::State> {
+        let node = ctx.query();
+        let callee = node.callee().ok()?;
+
+        let members = matches_test_call(&callee, &[]).ok()??;
+
+        let is_focused = members
+            .iter()
+            .enumerate()
+            .any(|(index, member)| match member.text() {
+                "fit" | "fdescribe" | "ftest" => index == 0,
+                "only" => index > 0,
+                _ => false,
+            });
+
+        is_focused.then(|| callee.syntax().text_trimmed_range())
+    }
+
+    fn diagnostic(_: &RuleContext<Self>, range: &Self::State) -> Option<RuleDiagnostic> {
+        Some(
+            RuleDiagnostic::warning(
+                *range,
+                markup! {
+                    "Don't focus the test."
+                },
+            )
+            .footer_note("Focused tests prevent the other tests of the suite from running."),
+        )
+    }
+}
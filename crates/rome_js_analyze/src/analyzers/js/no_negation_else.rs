@@ -35,7 +35,11 @@ declare_rule! {
     /// ```js
     /// true ? consequent : alternate
     ///```
-    pub(crate) NoNegationElse = "noNegationElse"
+    pub(crate) NoNegationElse {
+        version: "0.7.0",
+        name: "noNegationElse",
+        recommended: false,
+    }
 }
 
 impl Rule for NoNegationElse {
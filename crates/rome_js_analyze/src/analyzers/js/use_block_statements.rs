@@ -62,7 +62,21 @@ declare_rule! {
     /// ```js,expect_diagnostic
     ///   with (x);
     /// ```
-    pub(crate) UseBlockStatements = "useBlockStatements"
+    ///
+    /// ### Valid
+    ///
+    /// ```js
+    /// if (x) {
+    ///   x;
+    /// } else {
+    ///   y;
+    /// }
+    /// ```
+    pub(crate) UseBlockStatements {
+        version: "0.7.0",
+        name: "useBlockStatements",
+        recommended: true,
+    }
 }
 
 impl Rule for UseBlockStatements {
@@ -145,16 +159,46 @@ impl Rule for UseBlockStatements {
         let node = ctx.query();
         let root = ctx.root();
         let root = match nodes_need_to_replaced {
-            UseBlockStatementsOperationType::Wrap(stmt) => root.replace_node(
-                stmt.clone(),
-                JsAnyStatement::JsBlockStatement(make::js_block_statement(
-                    make::token(T!['{'])
-                        .with_trailing_trivia(iter::once((TriviaPieceKind::Whitespace, " "))),
-                    make::js_statement_list(iter::once(stmt.clone())),
-                    make::token(T!['}'])
-                        .with_leading_trivia(iter::once((TriviaPieceKind::Whitespace, " "))),
-                )),
-            )?,
+            UseBlockStatementsOperationType::Wrap(stmt) => {
+                let first_token = stmt.syntax().first_token()?;
+                let last_token = stmt.syntax().last_token()?;
+
+                // Keep the leading trivia of the statement inside of the block, and add the
+                // space after the opening curly before it instead if it already starts with
+                // trivia, for example a comment on its own line
+                let space = [(TriviaPieceKind::Whitespace, " ")];
+                let (l_curly_leading, l_curly_trailing) =
+                    if first_token.leading_trivia().text().is_empty() {
+                        (&space[..0], &space[..])
+                    } else {
+                        (&space[..], &space[..0])
+                    };
+
+                // Move the trailing trivia of the statement after the closing curly so that
+                // a trailing line comment doesn't comment it out
+                let trailing_trivia: Vec<_> = last_token.trailing_trivia().pieces().collect();
+                let body = stmt.clone().replace_token_discard_trivia(
+                    last_token.clone(),
+                    last_token.with_trailing_trivia(iter::empty()),
+                )?;
+
+                root.replace_node_discard_trivia(
+                    stmt.clone(),
+                    JsAnyStatement::JsBlockStatement(make::js_block_statement(
+                        make::token(T!['{'])
+                            .with_leading_trivia(l_curly_leading.iter().copied())
+                            .with_trailing_trivia(l_curly_trailing.iter().copied()),
+                        make::js_statement_list(iter::once(body)),
+                        make::token(T!['}'])
+                            .with_leading_trivia(space.iter().copied())
+                            .with_trailing_trivia(
+                                trailing_trivia
+                                    .iter()
+                                    .map(|piece| (piece.kind(), piece.text())),
+                            ),
+                    )),
+                )?
+            }
             UseBlockStatementsOperationType::ReplaceBody => match node {
                 JsAnyStatement::JsDoWhileStatement(stmt) => {
                     use_block_statements_replace_body!(JsDoWhileStatement, root, node, stmt)
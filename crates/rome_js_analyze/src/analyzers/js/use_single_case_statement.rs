@@ -41,7 +41,11 @@ declare_rule! {
     ///     }
     /// }
     /// ```
-    pub(crate) UseSingleCaseStatement = "useSingleCaseStatement"
+    pub(crate) UseSingleCaseStatement {
+        version: "0.7.0",
+        name: "useSingleCaseStatement",
+        recommended: true,
+    }
 }
 
 impl Rule for UseSingleCaseStatement {
@@ -30,7 +30,11 @@ declare_rule! {
     /// ```js
     /// for (let i = 0, x = 1; i < arr.length; i++) {}
     /// ```
-    pub(crate) UseSingleVarDeclarator = "useSingleVarDeclarator"
+    pub(crate) UseSingleVarDeclarator {
+        version: "0.7.0",
+        name: "useSingleVarDeclarator",
+        recommended: true,
+    }
 }
 
 impl Rule for UseSingleVarDeclarator {
@@ -70,7 +70,11 @@ declare_rule! {
     /// ```js
     /// typeof bar === typeof qux
     /// ```
-    pub(crate) UseValidTypeof = "useValidTypeof"
+    pub(crate) UseValidTypeof {
+        version: "0.7.0",
+        name: "useValidTypeof",
+        recommended: true,
+    }
 }
 
 impl Rule for UseValidTypeof {
@@ -34,7 +34,11 @@ declare_rule! {
     /// const a1 = <div>{/** comment */}</div>;
     /// const a2 = <div className={"cls" /* comment */}></div>;
     /// ```
-    pub(crate) NoCommentText = "noCommentText"
+    pub(crate) NoCommentText {
+        version: "0.7.0",
+        name: "noCommentText",
+        recommended: true,
+    }
 }
 
 impl Rule for NoCommentText {
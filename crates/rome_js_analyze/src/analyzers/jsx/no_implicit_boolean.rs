@@ -43,7 +43,11 @@ declare_rule! {
     /// ```jsx
     /// <input disabled='false' />
     ///```
-    pub(crate) NoImplicitBoolean = "noImplicitBoolean"
+    pub(crate) NoImplicitBoolean {
+        version: "0.7.0",
+        name: "noImplicitBoolean",
+        recommended: false,
+    }
 }
 
 impl Rule for NoImplicitBoolean {
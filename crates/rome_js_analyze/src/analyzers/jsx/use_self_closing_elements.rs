@@ -53,7 +53,11 @@ declare_rule! {
     /// ```js
     /// <Foo.bar>child</Foo.bar>
     ///```
-    pub(crate) UseSelfClosingElements = "useSelfClosingElements"
+    pub(crate) UseSelfClosingElements {
+        version: "0.7.0",
+        name: "useSelfClosingElements",
+        recommended: true,
+    }
 }
 
 impl Rule for UseSelfClosingElements {
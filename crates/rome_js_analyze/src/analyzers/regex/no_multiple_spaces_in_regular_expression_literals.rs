@@ -57,7 +57,11 @@ declare_rule! {
     /// ```js
     /// /foo /
     ///```
-    pub(crate) NoMultipleSpacesInRegularExpressionLiterals = "noMultipleSpacesInRegularExpressionLiterals"
+    pub(crate) NoMultipleSpacesInRegularExpressionLiterals {
+        version: "0.7.0",
+        name: "noMultipleSpacesInRegularExpressionLiterals",
+        recommended: true,
+    }
 }
 
 impl Rule for NoMultipleSpacesInRegularExpressionLiterals {
@@ -43,7 +43,11 @@ declare_rule! {
     /// let valid: Array<keyof Bar>;
     /// let valid: Array<foo | bar>;
     /// ```
-    pub(crate) UseShorthandArrayType = "useShorthandArrayType"
+    pub(crate) UseShorthandArrayType {
+        version: "0.7.0",
+        name: "useShorthandArrayType",
+        recommended: false,
+    }
 }
 
 impl Rule for UseShorthandArrayType {
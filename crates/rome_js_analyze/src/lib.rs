@@ -59,8 +59,6 @@ where
         &mut emit_signal,
     );
 
-    analyzer.add_visitor(make_visitor());
-
     analyzer.add_visitor(SyntaxVisitor::default());
 
     let breaking_reason = analyzer.run(AnalyzerContext {
@@ -86,6 +84,9 @@ where
         &mut emit_signal,
     );
 
+    // The control flow rules resolve names using the semantic model
+    analyzer.add_visitor(make_visitor());
+
     analyzer.add_visitor(SyntaxVisitor::default());
 
     analyzer.run(AnalyzerContext {
@@ -100,11 +101,12 @@ where
 #[cfg(test)]
 mod tests {
 
-    use rome_analyze::Never;
+    use rome_analyze::{apply_fixes, AnalyzerAction, Never, RuleCategories, RuleFilter};
     use rome_js_parser::parse;
-    use rome_js_syntax::{SourceType, TextRange, TextSize};
+    use rome_js_syntax::{JsLanguage, SourceType, TextRange, TextSize};
+    use rome_rowan::AstNode;
 
-    use crate::{analyze, AnalysisFilter, ControlFlow};
+    use crate::{analyze, metadata, AnalysisFilter, ControlFlow};
 
     #[test]
     fn suppression() {
@@ -154,4 +156,84 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn apply_fixes_skips_overlapping_actions() {
+        const SOURCE: &str = "for (; x;) {\n\tlet a = 1, b = 2;\n}\na == b;\n";
+
+        let parsed = parse(SOURCE, 0, SourceType::js_module());
+
+        let filter = AnalysisFilter {
+            categories: RuleCategories::LINT,
+            ..AnalysisFilter::default()
+        };
+
+        let mut actions = Vec::new();
+        analyze(0, &parsed.tree(), filter, |signal| {
+            if let Some(action) = signal.action() {
+                actions.push(action);
+            }
+
+            ControlFlow::<Never>::Continue(())
+        });
+
+        // The `useSingleVarDeclarator` fix is inside of the loop replaced by `useWhile`
+        let (root, applied, skipped) = apply_fixes(parsed.tree(), actions);
+
+        let rule_names = |actions: Vec<AnalyzerAction<JsLanguage>>| {
+            actions
+                .into_iter()
+                .map(|action| action.rule_name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rule_names(applied), ["useWhile", "noDoubleEquals"]);
+        assert_eq!(rule_names(skipped), ["useSingleVarDeclarator"]);
+        assert_eq!(
+            root.syntax().to_string(),
+            "while (x) {\n\tlet a = 1, b = 2;\n}\na === b;\n"
+        );
+    }
+
+    #[test]
+    fn rules_metadata() {
+        let rules: Vec<_> = metadata(AnalysisFilter::default()).collect();
+
+        let no_debugger = rules.iter().find(|rule| rule.name == "noDebugger").unwrap();
+
+        assert_eq!(no_debugger.group, "js");
+        assert_eq!(no_debugger.version, "0.7.0");
+        assert!(no_debugger.recommended);
+        assert_eq!(no_debugger.summary(), "Disallow the use of `debugger`");
+
+        let no_dead_code = rules.iter().find(|rule| rule.name == "noDeadCode").unwrap();
+
+        assert_eq!(no_dead_code.group, "js");
+        assert!(!no_dead_code.recommended);
+        assert_eq!(no_dead_code.summary(), "Disallow unreachable code");
+    }
+
+    #[test]
+    fn recommended_rules_filter() {
+        const SOURCE: &str = "debugger;\nif (!a) {\n\tb();\n} else {\n\tc();\n}\n";
+
+        let parsed = parse(SOURCE, 0, SourceType::js_module());
+
+        let filter = AnalysisFilter {
+            enabled_rules: Some(&[RuleFilter::Recommended]),
+            ..AnalysisFilter::default()
+        };
+
+        let mut codes = Vec::new();
+        analyze(0, &parsed.tree(), filter, |signal| {
+            if let Some(diag) = signal.diagnostic() {
+                codes.push(diag.code.unwrap());
+            }
+
+            ControlFlow::<Never>::Continue(())
+        });
+
+        // `noNegationElse` isn't a recommended rule
+        assert_eq!(codes, ["js/noDebugger"]);
+    }
 }
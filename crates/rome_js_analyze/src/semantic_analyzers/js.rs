@@ -3,5 +3,7 @@
 use rome_analyze::declare_group;
 mod no_arguments;
 mod no_catch_assign;
+mod no_dead_code;
 mod no_label_var;
-declare_group! { pub (crate) Js { name : "js" , rules : [no_arguments :: NoArguments , no_catch_assign :: NoCatchAssign , no_label_var :: NoLabelVar ,] } }
+mod no_unused_variables;
+declare_group! { pub (crate) Js { name : "js" , rules : [no_arguments :: NoArguments , no_catch_assign :: NoCatchAssign , no_dead_code :: NoDeadCode , no_label_var :: NoLabelVar , no_unused_variables :: NoUnusedVariables ,] } }
@@ -25,7 +25,11 @@ declare_rule! {
     ///     console.log(arguments);
     /// }
     /// ```
-    pub(crate) NoArguments = "noArguments"
+    pub(crate) NoArguments {
+        version: "0.7.0",
+        name: "noArguments",
+        recommended: true,
+    }
 }
 
 impl Rule for NoArguments {
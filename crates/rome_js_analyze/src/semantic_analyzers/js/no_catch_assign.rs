@@ -30,7 +30,11 @@ declare_rule! {
     ///   e = 100;
     /// }
     /// ```
-    pub(crate) NoCatchAssign = "noCatchAssign"
+    pub(crate) NoCatchAssign {
+        version: "0.7.0",
+        name: "noCatchAssign",
+        recommended: true,
+    }
 }
 
 impl Rule for NoCatchAssign {
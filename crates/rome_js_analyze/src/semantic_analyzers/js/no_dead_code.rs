@@ -4,15 +4,24 @@ use roaring::bitmap::RoaringBitmap;
 use rome_analyze::{context::RuleContext, declare_rule, Rule, RuleCategory, RuleDiagnostic};
 use rome_console::markup;
 use rome_control_flow::{builder::BlockId, ExceptionHandler, Instruction, InstructionKind};
-use rome_js_syntax::{JsLanguage, JsReturnStatement, JsSyntaxElement, JsSyntaxKind, TextRange};
+use rome_js_semantic::SemanticModel;
+use rome_js_syntax::{
+    JsAnyCallArgument, JsAnyExpression, JsCallExpression, JsExpressionStatement, JsLanguage,
+    JsReturnStatement, JsSyntaxElement, JsSyntaxKind, TextRange,
+};
 use rome_rowan::AstNode;
 use rustc_hash::FxHashMap;
 
-use crate::control_flow::ControlFlowGraph;
+use crate::{control_flow::ControlFlowGraph, semantic_services::SemanticControlFlowGraph};
 
 declare_rule! {
     /// Disallow unreachable code
     ///
+    /// Calls to a few global functions known to never throw, like `Math.max` or
+    /// `Array.isArray`, are not considered as a possible source of exceptions
+    /// when all their arguments are literals. This list of pure functions is
+    /// built into the rule and can't be changed from the rule options.
+    ///
     /// ## Examples
     ///
     /// ### Invalid
@@ -40,13 +49,17 @@ declare_rule! {
     ///     }
     /// }
     /// ```
-    pub(crate) NoDeadCode = "noDeadCode"
+    pub(crate) NoDeadCode {
+        version: "0.7.0",
+        name: "noDeadCode",
+        recommended: false,
+    }
 }
 
 impl Rule for NoDeadCode {
     const CATEGORY: RuleCategory = RuleCategory::Lint;
 
-    type Query = ControlFlowGraph;
+    type Query = SemanticControlFlowGraph;
     type State = UnreachableRange;
     type Signals = UnreachableRanges;
 
@@ -54,11 +67,12 @@ impl Rule for NoDeadCode {
         let mut signals = UnreachableRanges::new();
 
         let cfg = ctx.query();
+        let model = ctx.model();
 
         if exceeds_complexity_threshold(cfg) {
-            analyze_simple(cfg, &mut signals)
+            analyze_simple(cfg, model, &mut signals)
         } else {
-            analyze_fine(cfg, &mut signals)
+            analyze_fine(cfg, model, &mut signals)
         }
 
         signals
@@ -225,7 +239,7 @@ fn exceeds_complexity_threshold(cfg: &ControlFlowGraph) -> bool {
 /// Perform a simple reachability analysis, does not attempt to determine a
 /// terminator instruction for unreachable ranges allowing blocks to be visited
 /// at most once and ensuring the algorithm finishes in a bounded time
-fn analyze_simple(cfg: &ControlFlowGraph, signals: &mut UnreachableRanges) {
+fn analyze_simple(cfg: &ControlFlowGraph, model: &SemanticModel, signals: &mut UnreachableRanges) {
     // Perform a simple reachability analysis on the control flow graph by
     // traversing the function starting at the entry point
     let mut reachable_blocks = RoaringBitmap::new();
@@ -259,7 +273,7 @@ fn analyze_simple(cfg: &ControlFlowGraph, signals: &mut UnreachableRanges) {
             }
 
             // Do not create exception edges for instructions with no side effects
-            if has_side_effects(inst) {
+            if has_side_effects(inst, model) {
                 // If this block has a pending exception edge, create an
                 // additional path diverging towards the corresponding
                 // catch or finally block
@@ -330,9 +344,20 @@ fn analyze_simple(cfg: &ControlFlowGraph, signals: &mut UnreachableRanges) {
 /// the reachability of each block and instruction but also find one or more
 /// "terminator instructions" for each unreachable range of code that cause it
 /// to be impossible to reach
-fn analyze_fine(cfg: &ControlFlowGraph, signals: &mut UnreachableRanges) {
+///
+/// If the number of paths created by the traversal exceeds [MAX_PATH_STATES],
+/// the partial results of the fine analysis are discarded and the function
+/// falls back to [analyze_simple] instead
+fn analyze_fine(cfg: &ControlFlowGraph, model: &SemanticModel, signals: &mut UnreachableRanges) {
     // Traverse the CFG and calculate block / instruction reachability
-    let block_paths = traverse_cfg(cfg, signals);
+    let block_paths = match traverse_cfg(cfg, model, signals) {
+        Some(block_paths) => block_paths,
+        None => {
+            *signals = UnreachableRanges::new();
+            analyze_simple(cfg, model, signals);
+            return;
+        }
+    };
 
     // Detect unreachable blocks using the result of the above traversal
     'blocks: for (index, block) in cfg.blocks.iter().enumerate() {
@@ -379,6 +404,13 @@ fn analyze_fine(cfg: &ControlFlowGraph, signals: &mut UnreachableRanges) {
     }
 }
 
+/// Maximum number of path states the fine analysis is allowed to visit or
+/// hold in its queue before bailing out. The complexity score only
+/// approximates the number of paths through a function, and some control
+/// flow graphs (long chains of conditionals for instance) can still cause the
+/// number of paths to grow exponentially
+const MAX_PATH_STATES: usize = 100_000;
+
 /// Individual entry in the traversal queue, holding the state for a
 /// single "linearly independent path" through the function as it gets
 /// created during the control flow traversal
@@ -395,10 +427,13 @@ struct PathState<'cfg> {
 
 /// Perform a simple reachability analysis on the control flow graph by
 /// traversing the function starting at the entry points
+///
+/// Returns `None` if the traversal was aborted after exceeding [MAX_PATH_STATES]
 fn traverse_cfg(
     cfg: &ControlFlowGraph,
+    model: &SemanticModel,
     signals: &mut UnreachableRanges,
-) -> FxHashMap<u32, Vec<Option<Option<PathTerminator>>>> {
+) -> Option<FxHashMap<u32, Vec<Option<Option<PathTerminator>>>>> {
     let mut queue = VecDeque::new();
 
     queue.push_back(PathState {
@@ -412,7 +447,14 @@ fn traverse_cfg(
     // intruction for each path that can reach the block
     let mut block_paths = FxHashMap::default();
 
+    let mut visited_states = 0;
+
     while let Some(mut path) = queue.pop_front() {
+        visited_states += 1;
+        if visited_states > MAX_PATH_STATES || queue.len() > MAX_PATH_STATES {
+            return None;
+        }
+
         // Add the block to the visited set for the path, and the current
         // state of the path to the global reachable blocks map
         path.visited.insert(path.next_block);
@@ -434,7 +476,7 @@ fn traverse_cfg(
 
         for inst in &block.instructions {
             // Do not create exception edges for instructions with no side effects
-            if has_side_effects(inst) {
+            if has_side_effects(inst, model) {
                 // If this block has a pending exception edge, create an
                 // additional path diverging towards the corresponding
                 // catch or finally block
@@ -490,13 +532,13 @@ fn traverse_cfg(
         }
     }
 
-    block_paths
+    Some(block_paths)
 }
 
 /// Returns `true` if `inst` can potentially have side effects. Due to the
 /// dynamic nature of JavaScript this is a conservative check, biased towards
 /// returning false positives
-fn has_side_effects(inst: &Instruction<JsLanguage>) -> bool {
+fn has_side_effects(inst: &Instruction<JsLanguage>, model: &SemanticModel) -> bool {
     let element = match inst.node.as_ref() {
         Some(element) => element,
         None => return false,
@@ -508,11 +550,77 @@ fn has_side_effects(inst: &Instruction<JsLanguage>) -> bool {
             node.argument().is_some()
         }
 
+        JsSyntaxKind::JS_EXPRESSION_STATEMENT => {
+            let node = JsExpressionStatement::unwrap_cast(element.as_node().unwrap().clone());
+            let is_pure = match node.expression() {
+                Ok(JsAnyExpression::JsCallExpression(call)) => {
+                    is_pure_call(&call, model).unwrap_or(false)
+                }
+                _ => false,
+            };
+
+            !is_pure
+        }
+
         JsSyntaxKind::JS_BREAK_STATEMENT | JsSyntaxKind::JS_CONTINUE_STATEMENT => false,
         kind => element.as_node().is_some() && !kind.is_literal(),
     }
 }
 
+/// List of global functions known to never throw or have side effects. An
+/// entry in the form `Object.*` matches any member function of `Object`
+const PURE_FUNCTIONS: &[&str] = &[
+    "Math.*",
+    "Array.isArray",
+    "Number.isFinite",
+    "Number.isInteger",
+    "Number.isNaN",
+    "Number.isSafeInteger",
+    "Object.is",
+];
+
+/// Returns `Some(true)` if `call` is a call to one of the [PURE_FUNCTIONS]
+/// with only literal arguments, and the object of the callee resolves to the
+/// global rather than to a local binding shadowing it
+fn is_pure_call(call: &JsCallExpression, model: &SemanticModel) -> Option<bool> {
+    let callee = call.callee().ok()?;
+    let callee = callee.as_js_static_member_expression()?;
+
+    let object = callee.object().ok()?;
+    let reference = object.as_js_identifier_expression()?.name().ok()?;
+    if model.declaration(&reference).is_some() {
+        return Some(false);
+    }
+
+    let object = reference.value_token().ok()?;
+
+    let member = callee.member().ok()?;
+    let member = member.as_js_name()?.value_token().ok()?;
+
+    let is_allowed = PURE_FUNCTIONS
+        .iter()
+        .any(|function| match function.split_once('.') {
+            Some((function_object, function_member)) => {
+                function_object == object.text_trimmed()
+                    && (function_member == "*" || function_member == member.text_trimmed())
+            }
+            None => false,
+        });
+
+    if !is_allowed {
+        return Some(false);
+    }
+
+    for argument in call.arguments().ok()?.args() {
+        match argument.ok()? {
+            JsAnyCallArgument::JsAnyExpression(JsAnyExpression::JsAnyLiteralExpression(_)) => {}
+            _ => return Some(false),
+        }
+    }
+
+    Some(true)
+}
+
 /// Create an additional visitor path from a jump instruction and push it to the queue
 fn handle_jump<'cfg>(
     queue: &mut VecDeque<PathState<'cfg>>,
@@ -579,6 +687,10 @@ impl UnreachableRanges {
         UnreachableRanges { ranges: Vec::new() }
     }
 
+    /// Insert the range of `node` in the list. Since the range of a node
+    /// includes its leading and trailing trivia, unreachable nodes only
+    /// separated by whitespace or comments have touching ranges and get
+    /// merged into a single entry
     fn push(&mut self, node: &JsSyntaxElement, terminator: Option<PathTerminator>) {
         let text_range = node.text_range();
         let text_trimmed_range = node.text_trimmed_range();
@@ -604,17 +716,13 @@ impl UnreachableRanges {
                 entry.text_trimmed_range = entry.text_trimmed_range.cover(text_trimmed_range);
 
                 if let Some(terminator) = terminator {
-                    // Terminator labels are also stored in ascending order to
-                    // faciliate the generation of labels when the diagnostic
-                    // gets emitted
-                    let terminator_insertion = entry
-                        .terminators
-                        .binary_search_by_key(&terminator.range.start(), |node| node.range.start());
-
-                    if let Err(index) = terminator_insertion {
-                        entry.terminators.insert(index, terminator);
-                    }
+                    entry.add_terminator(terminator);
                 }
+
+                // The extended range may now be touching the previous or
+                // next entries in the list, if the incoming node was filling
+                // the gap between them
+                self.merge_neighbors(index);
             }
             // No overlapping range was found, insert at the appropriate
             // position to preserve the ordering instead
@@ -630,6 +738,24 @@ impl UnreachableRanges {
             }
         }
     }
+
+    /// Merge the entry at `index` with all the surrounding entries it overlaps or touches
+    fn merge_neighbors(&mut self, mut index: usize) {
+        while index > 0
+            && self.ranges[index - 1].text_range.end() >= self.ranges[index].text_range.start()
+        {
+            let entry = self.ranges.remove(index);
+            index -= 1;
+            self.ranges[index].merge(entry);
+        }
+
+        while index + 1 < self.ranges.len()
+            && self.ranges[index].text_range.end() >= self.ranges[index + 1].text_range.start()
+        {
+            let entry = self.ranges.remove(index + 1);
+            self.ranges[index].merge(entry);
+        }
+    }
 }
 
 impl IntoIterator for UnreachableRanges {
@@ -651,6 +777,31 @@ pub(crate) struct UnreachableRange {
     terminators: Vec<PathTerminator>,
 }
 
+impl UnreachableRange {
+    /// Extend this range to also cover `other`, including its terminators
+    fn merge(&mut self, other: UnreachableRange) {
+        self.text_range = self.text_range.cover(other.text_range);
+        self.text_trimmed_range = self.text_trimmed_range.cover(other.text_trimmed_range);
+
+        for terminator in other.terminators {
+            self.add_terminator(terminator);
+        }
+    }
+
+    fn add_terminator(&mut self, terminator: PathTerminator) {
+        // Terminator labels are also stored in ascending order to
+        // faciliate the generation of labels when the diagnostic
+        // gets emitted
+        let terminator_insertion = self
+            .terminators
+            .binary_search_by_key(&terminator.range.start(), |node| node.range.start());
+
+        if let Err(index) = terminator_insertion {
+            self.terminators.insert(index, terminator);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct PathTerminator {
     kind: JsSyntaxKind,
@@ -669,3 +820,198 @@ impl PathTerminator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rome_control_flow::{builder::FunctionBuilder, ExceptionHandlerKind};
+    use rome_js_parser::parse;
+    use rome_js_semantic::semantic_model;
+    use rome_js_syntax::{JsExpressionStatement, JsReturnStatement, SourceType};
+    use rome_rowan::AstNode;
+
+    use super::{analyze_fine, UnreachableRanges};
+
+    #[test]
+    fn merge_adjacent_ranges() {
+        let parsed = parse(
+            "function f() {\n    return;\n    first();\n    // comment\n    second();\n    third();\n}",
+            0,
+            SourceType::js_module(),
+        );
+
+        let statements: Vec<_> = parsed
+            .syntax()
+            .descendants()
+            .filter_map(JsExpressionStatement::cast)
+            .map(|stmt| stmt.into_syntax())
+            .collect();
+
+        assert_eq!(statements.len(), 3);
+
+        // Push the outer statements first, the middle one fills the gap
+        // between the two existing ranges and should merge all three
+        let mut signals = UnreachableRanges::new();
+        signals.push(&statements[0].clone().into(), None);
+        signals.push(&statements[2].clone().into(), None);
+        signals.push(&statements[1].clone().into(), None);
+
+        let ranges: Vec<_> = signals.into_iter().collect();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(
+            ranges[0].text_trimmed_range,
+            statements[0]
+                .text_trimmed_range()
+                .cover(statements[2].text_trimmed_range())
+        );
+    }
+
+    #[test]
+    fn fine_analysis_bails_out_on_path_explosion() {
+        let parsed = parse(
+            "function f() { return; afterReturn(); }",
+            0,
+            SourceType::js_module(),
+        );
+        let root = parsed.syntax();
+
+        let return_stmt = root
+            .descendants()
+            .find_map(JsReturnStatement::cast)
+            .unwrap();
+        let after_return = root
+            .descendants()
+            .find_map(JsExpressionStatement::cast)
+            .unwrap();
+
+        // Build a long chain of diamond-shaped conditionals, the number of
+        // paths through this graph doubles with each diamond
+        let mut builder = FunctionBuilder::default();
+
+        for _ in 0..64 {
+            let entry = builder.cursor();
+            let consequent = builder.append_block();
+            let alternate = builder.append_block();
+            let next = builder.append_block();
+
+            builder.set_cursor(entry);
+            builder.append_jump(true, consequent);
+            builder.append_jump(false, alternate);
+
+            builder.set_cursor(consequent);
+            builder.append_jump(false, next);
+
+            builder.set_cursor(alternate);
+            builder.append_jump(false, next);
+
+            builder.set_cursor(next);
+        }
+
+        builder.append_return().with_node(return_stmt.into_syntax());
+        builder
+            .append_statement()
+            .with_node(after_return.syntax().clone());
+
+        let cfg = builder.finish();
+
+        let model = semantic_model(&parsed.tree());
+
+        let mut signals = UnreachableRanges::new();
+        analyze_fine(&cfg, &model, &mut signals);
+
+        // The simple analysis still detects the unreachable statement, but
+        // does not report the terminator instruction causing it
+        let ranges: Vec<_> = signals.into_iter().collect();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(
+            ranges[0].text_trimmed_range,
+            after_return.syntax().text_trimmed_range()
+        );
+        assert!(ranges[0].terminators.is_empty());
+    }
+
+    /// Run the fine analysis on a function calling `try_call` inside a
+    /// `try` block preceded by `prelude`, and returns the unreachable ranges
+    fn analyze_try_catch(
+        prelude: &str,
+        try_call: &str,
+    ) -> (UnreachableRanges, JsExpressionStatement) {
+        let parsed = parse(
+            &format!("{prelude} try {{ {try_call}; }} catch {{ caught(); }}"),
+            0,
+            SourceType::js_module(),
+        );
+
+        let statements: Vec<_> = parsed
+            .syntax()
+            .descendants()
+            .filter_map(JsExpressionStatement::cast)
+            .collect();
+
+        let mut builder = FunctionBuilder::default();
+
+        let catch_block = builder.append_block();
+        builder.push_exception_target(ExceptionHandlerKind::Catch, catch_block);
+        let try_block = builder.append_block();
+        builder.pop_exception_target();
+        let next_block = builder.append_block();
+
+        builder.append_jump(false, try_block);
+
+        builder.set_cursor(try_block);
+        builder
+            .append_statement()
+            .with_node(statements[0].syntax().clone());
+        builder.append_jump(false, next_block);
+
+        builder.set_cursor(catch_block);
+        builder
+            .append_statement()
+            .with_node(statements[1].syntax().clone());
+        builder.append_jump(false, next_block);
+
+        builder.set_cursor(next_block);
+        builder.append_return();
+
+        let cfg = builder.finish();
+
+        let model = semantic_model(&parsed.tree());
+
+        let mut signals = UnreachableRanges::new();
+        analyze_fine(&cfg, &model, &mut signals);
+
+        (signals, statements[1].clone())
+    }
+
+    #[test]
+    fn pure_call_has_no_exception_edge() {
+        let (signals, caught) = analyze_try_catch("", "Math.max(0, 1)");
+
+        let ranges: Vec<_> = signals.into_iter().collect();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(
+            ranges[0].text_trimmed_range,
+            caught.syntax().text_trimmed_range()
+        );
+
+        let (signals, _) = analyze_try_catch("", "Array.isArray(null)");
+        assert_eq!(signals.into_iter().count(), 1);
+    }
+
+    #[test]
+    fn impure_call_has_exception_edge() {
+        let (signals, _) = analyze_try_catch("", "sideEffect(a)");
+        assert_eq!(signals.into_iter().count(), 0);
+
+        // Arguments may have side effects even if the function is pure
+        let (signals, _) = analyze_try_catch("", "Math.max(sideEffect(), 1)");
+        assert_eq!(signals.into_iter().count(), 0);
+
+        // Identifiers may be getters on the global object
+        let (signals, _) = analyze_try_catch("", "Math.max(a, 1)");
+        assert_eq!(signals.into_iter().count(), 0);
+
+        // A local binding may shadow the global
+        let (signals, _) = analyze_try_catch("let Math = value;", "Math.max(0, 1)");
+        assert_eq!(signals.into_iter().count(), 0);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::semantic_services::Semantic;
+use rome_analyze::{context::RuleContext, declare_rule, Rule, RuleCategory, RuleDiagnostic};
+use rome_console::markup;
+use rome_js_semantic::AllReferencesExtensions;
+use rome_js_syntax::{
+    JsExport, JsIdentifierBinding, JsVariableDeclaration, JsVariableDeclarationClause,
+    JsVariableDeclarator,
+};
+use rome_rowan::AstNode;
+
+declare_rule! {
+    /// Disallow unused variables.
+    ///
+    /// A variable that is declared but never read is most likely the leftover of a refactoring
+    /// or a typo. Variables declared with an exported declaration are ignored because they can
+    /// be used by other modules.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```js,expect_diagnostic
+    /// let a = 4;
+    /// ```
+    ///
+    /// ```js,expect_diagnostic
+    /// let a = 4;
+    /// a = 5;
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```js
+    /// let a = 4;
+    /// console.log(a);
+    /// ```
+    ///
+    /// ```js
+    /// export let a = 4;
+    /// ```
+    pub(crate) NoUnusedVariables {
+        version: "next",
+        name: "noUnusedVariables",
+        recommended: false,
+    }
+}
+
+impl Rule for NoUnusedVariables {
+    const CATEGORY: RuleCategory = RuleCategory::Lint;
+
+    type Query = Semantic<JsIdentifierBinding>;
+    type State = ();
+    type Signals = Option<Self::State>;
+
+    fn run(ctx: &RuleContext<Self>) -> Option<Self::State> {
+        let binding = ctx.query();
+        let model = ctx.model();
+
+        let declarator = binding.parent::<JsVariableDeclarator>()?;
+
+        if is_exported(&declarator) {
+            return None;
+        }
+
+        if binding.all_reads(model).next().is_some() {
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    fn diagnostic(ctx: &RuleContext<Self>, _: &Self::State) -> Option<RuleDiagnostic> {
+        let binding = ctx.query();
+
+        Some(
+            RuleDiagnostic::warning(
+                binding.syntax().text_trimmed_range(),
+                markup! {
+                    "This variable is unused."
+                },
+            )
+            .footer_note(
+                "Unused variables usually are the result of an incomplete refactoring or a typo.",
+            ),
+        )
+    }
+}
+
+/// Returns `true` if the declarator is part of an exported declaration: `export let a = 1;`
+fn is_exported(declarator: &JsVariableDeclarator) -> bool {
+    let declaration = declarator
+        .syntax()
+        .ancestors()
+        .find_map(JsVariableDeclaration::cast);
+
+    declaration
+        .and_then(|declaration| declaration.parent::<JsVariableDeclarationClause>())
+        .and_then(|clause| clause.parent::<JsExport>())
+        .is_some()
+}
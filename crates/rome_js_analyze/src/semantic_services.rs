@@ -5,6 +5,8 @@ use rome_js_semantic::SemanticModel;
 use rome_js_syntax::JsLanguage;
 use rome_rowan::AstNode;
 
+use crate::control_flow::ControlFlowGraph;
+
 pub struct SemanticServices {
     model: SemanticModel,
 }
@@ -57,3 +59,23 @@ where
         }
     }
 }
+
+/// Query type usable by lint rules **that uses the semantic model** to match on the
+/// [ControlFlowGraph] of functions
+#[derive(Clone)]
+pub struct SemanticControlFlowGraph(pub ControlFlowGraph);
+
+impl Queryable for SemanticControlFlowGraph {
+    type Output = ControlFlowGraph;
+    type Language = JsLanguage;
+    type Services = SemanticServices;
+
+    const KEY: QueryKey<Self::Language> = QueryKey::ControlFlowGraph;
+
+    fn unwrap_match(query: &QueryMatch<Self::Language>) -> Self::Output {
+        match query {
+            QueryMatch::ControlFlowGraph(cfg, _) => cfg.clone(),
+            _ => panic!("tried to unwrap unsupported QueryMatch kind, expected ControlFlowGraph"),
+        }
+    }
+}
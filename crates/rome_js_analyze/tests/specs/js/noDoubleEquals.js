@@ -3,3 +3,5 @@ const isNonZero = a != 0;
 
 const isNull = a == null;
 const isNonNull = a != null;
+
+const isEqual = a == /* b */ b;
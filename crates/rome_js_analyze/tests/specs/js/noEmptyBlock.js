@@ -0,0 +1,15 @@
+// valid
+if (a) { b(); }
+if (a) {
+  // intentionally empty
+}
+while (a) { /* wait */ }
+function foo() { "use strict"; }
+try { a(); } catch (e) {}
+// invalid
+if (a) {}
+for (;;) {}
+while (a) {}
+function bar() {}
+const baz = () => {};
+try { a(); } finally {}
\ No newline at end of file
@@ -0,0 +1,11 @@
+// valid
+describe("foo", () => {});
+it.skip("foo", () => {});
+foo.only();
+test.foo.only("foo", () => {});
+test.describe.parallel.only.foo("foo", () => {});
+// invalid
+describe.only("foo", () => {});
+test.only("foo", () => {});
+test.describe.parallel.only("foo", () => {});
+fit("foo", () => {});
\ No newline at end of file
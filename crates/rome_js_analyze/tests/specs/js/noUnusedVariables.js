@@ -0,0 +1,10 @@
+// invalid
+let a = 1;
+let b = 2;
+b = 3;
+// valid
+let c = 1;
+console.log(c);
+export let d = 1;
+let e = 1;
+export { e };
\ No newline at end of file
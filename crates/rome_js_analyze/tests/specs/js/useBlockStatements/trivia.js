@@ -0,0 +1,4 @@
+if (x)
+  // comment
+  y();
+while (x) y(); // trailing
\ No newline at end of file
@@ -0,0 +1,7 @@
+if (x) {
+  x;
+} else {
+  y;
+}
+for (;;) {}
+while (x) {}
\ No newline at end of file
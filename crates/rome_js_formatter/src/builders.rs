@@ -3,7 +3,8 @@ use crate::AsFormat;
 use rome_formatter::token::{FormatInserted, FormatInsertedCloseParen, FormatInsertedOpenParen};
 use rome_formatter::{format_args, write, Argument, Arguments, GroupId, PreambleBuffer, VecBuffer};
 use rome_js_syntax::{JsLanguage, JsSyntaxKind, JsSyntaxNode, JsSyntaxToken};
-use rome_rowan::{AstNode, Direction, Language, SyntaxTriviaPiece};
+use rome_rowan::syntax::SyntaxTrivia;
+use rome_rowan::{AstNode, SyntaxElement};
 
 /// Formats a node using its [`AsFormat`] implementation but falls back to printing the node as
 /// it is in the source document if the formatting returns an [`FormatError`].
@@ -154,121 +155,85 @@ impl Format<JsFormatContext> for FormatParenthesize<'_> {
     }
 }
 
-/// "Formats" a node according to its original formatting in the source text. Being able to format
-/// a node "as is" is useful if a node contains syntax errors. Formatting a node with syntax errors
-/// has the risk that Rome misinterprets the structure of the code and formatting it could
-/// "mess up" the developers, yet incomplete, work or accidentally introduce new syntax errors.
-///
-/// You may be inclined to call `node.text` directly. However, using `text` doesn't track the nodes
-/// nor its children source mapping information, resulting in incorrect source maps for this subtree.
+/// Formats a node as it is in the source text, see [rome_formatter::format_verbatim].
+pub fn format_verbatim_node(node: &JsSyntaxNode) -> FormatVerbatimNode<JsLanguage> {
+    format_verbatim(node)
+}
+
+/// Formats unknown nodes. The difference between this method  and `format_verbatim` is that this method
+/// doesn't track nodes/tokens as [FormatElement::Verbatim]. They are just printed as they are.
+pub fn format_unknown_node(node: &JsSyntaxNode) -> FormatVerbatimNode<JsLanguage> {
+    format_verbatim(node).with_kind(VerbatimKind::Unknown)
+}
+
+/// Formats a node by formatting each of its children in source order: tokens are formatted with
+/// their comments and child nodes with their own formatting rule. Two children separated by a
+/// line break in the source text remain separated by a line break, which matters for automatic
+/// semicolon insertion (`return\nx`). Children separated by other whitespace are separated by a
+/// single space, except after an opening or before a closing bracket.
 ///
-/// These nodes and tokens get tracked as [FormatElement::Verbatim], useful to understand
-/// if these nodes still need to have their own implementation.
-pub fn format_verbatim_node(node: &JsSyntaxNode) -> FormatVerbatimNode {
-    FormatVerbatimNode {
-        node,
-        kind: VerbatimKind::Verbatim {
-            length: node.text_range().len(),
-        },
-    }
+/// This is the default layout of nodes that don't have a specific formatting implementation,
+/// see [crate::FormatNodeRule::fmt_fields]. Unlike [format_verbatim_node], the descendants of
+/// the node still get formatted.
+pub fn format_children(node: &JsSyntaxNode) -> FormatChildren {
+    FormatChildren { node }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct FormatVerbatimNode<'node> {
+#[derive(Debug, Clone)]
+pub struct FormatChildren<'node> {
     node: &'node JsSyntaxNode,
-    kind: VerbatimKind,
 }
-impl Format<JsFormatContext> for FormatVerbatimNode<'_> {
-    fn fmt(&self, f: &mut JsFormatter) -> FormatResult<()> {
-        for token in self.node.descendants_tokens(Direction::Next) {
-            f.state_mut().track_token(&token);
-        }
-
-        fn skip_whitespace<L: Language>(piece: &SyntaxTriviaPiece<L>) -> bool {
-            piece.is_newline() || piece.is_whitespace()
-        }
 
-        fn write_trivia_token<L: Language>(
-            f: &mut JsFormatter,
-            piece: SyntaxTriviaPiece<L>,
-        ) -> FormatResult<()> {
-            syntax_token_cow_slice(
-                normalize_newlines(piece.text(), LINE_TERMINATORS),
-                &piece.token(),
-                piece.text_range().start(),
-            )
-            .fmt(f)
-        }
+impl Format<JsFormatContext> for FormatChildren<'_> {
+    fn fmt(&self, f: &mut JsFormatter) -> FormatResult<()> {
+        let mut previous_token: Option<JsSyntaxToken> = None;
 
-        let mut buffer = VecBuffer::new(f.state_mut());
+        for child in self.node.children_with_tokens() {
+            let (first_token, last_token) = match &child {
+                SyntaxElement::Node(node) => (node.first_token(), node.last_token()),
+                SyntaxElement::Token(token) => (Some(token.clone()), Some(token.clone())),
+            };
 
-        write!(
-            buffer,
-            [format_with(|f| {
-                for leading_trivia in self
-                    .node
-                    .first_leading_trivia()
-                    .into_iter()
-                    .flat_map(|trivia| trivia.pieces())
-                    .skip_while(skip_whitespace)
+            if let (Some(previous_token), Some(first_token)) = (&previous_token, &first_token) {
+                let is_delimited = matches!(
+                    previous_token.kind(),
+                    JsSyntaxKind::L_PAREN | JsSyntaxKind::L_BRACK | JsSyntaxKind::L_CURLY
+                ) || matches!(
+                    first_token.kind(),
+                    JsSyntaxKind::R_PAREN | JsSyntaxKind::R_BRACK | JsSyntaxKind::R_CURLY
+                );
+
+                if has_newline(first_token.leading_trivia()) {
+                    write!(f, [hard_line_break()])?;
+                } else if !is_delimited
+                    && (has_whitespace(previous_token.trailing_trivia())
+                        || has_whitespace(first_token.leading_trivia()))
                 {
-                    write_trivia_token(f, leading_trivia)?;
-                }
-
-                dynamic_token(
-                    &normalize_newlines(&self.node.text_trimmed().to_string(), LINE_TERMINATORS),
-                    self.node.text_trimmed_range().start(),
-                )
-                .fmt(f)?;
-
-                // Clippy false positive: SkipWhile does not implement DoubleEndedIterator
-                #[allow(clippy::needless_collect)]
-                let trailing_trivia: Vec<_> = self
-                    .node
-                    .last_trailing_trivia()
-                    .into_iter()
-                    .flat_map(|trivia| trivia.pieces().rev())
-                    .skip_while(skip_whitespace)
-                    .collect();
-
-                for trailing_trivia in trailing_trivia.into_iter().rev() {
-                    write_trivia_token(f, trailing_trivia)?;
+                    write!(f, [space_token()])?;
                 }
+            }
 
-                Ok(())
-            })]
-        )?;
-
-        let content = buffer.into_vec();
+            match child {
+                SyntaxElement::Node(node) => write!(f, [node.format()])?,
+                SyntaxElement::Token(token) => write!(f, [token.format()])?,
+            }
 
-        let verbatim = Verbatim {
-            content: content.into_boxed_slice(),
-            kind: self.kind,
-        };
+            // Empty nodes don't have any tokens, keep the last token of the previous child
+            if last_token.is_some() {
+                previous_token = last_token;
+            }
+        }
 
-        f.write_element(FormatElement::Verbatim(verbatim))
+        Ok(())
     }
 }
 
-/// Formats unknown nodes. The difference between this method  and `format_verbatim` is that this method
-/// doesn't track nodes/tokens as [FormatElement::Verbatim]. They are just printed as they are.
-pub fn format_unknown_node(node: &JsSyntaxNode) -> FormatUnknownNode {
-    FormatUnknownNode { node }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct FormatUnknownNode<'node> {
-    node: &'node JsSyntaxNode,
+fn has_whitespace(trivia: SyntaxTrivia<JsLanguage>) -> bool {
+    trivia.pieces().any(|piece| piece.is_whitespace())
 }
 
-impl Format<JsFormatContext> for FormatUnknownNode<'_> {
-    fn fmt(&self, f: &mut JsFormatter) -> FormatResult<()> {
-        FormatVerbatimNode {
-            node: self.node,
-            kind: VerbatimKind::Unknown,
-        }
-        .fmt(f)
-    }
+fn has_newline(trivia: SyntaxTrivia<JsLanguage>) -> bool {
+    trivia.pieces().any(|piece| piece.is_newline())
 }
 
 /// Format a node having formatter suppression comment applied to it
@@ -289,10 +254,7 @@ impl Format<JsFormatContext> for FormatSuppressedNode<'_> {
             f,
             [
                 hard_line_break(),
-                FormatVerbatimNode {
-                    node: self.node,
-                    kind: VerbatimKind::Suppressed
-                }
+                format_verbatim(self.node).with_kind(VerbatimKind::Suppressed)
             ]
         )
     }
@@ -349,6 +311,13 @@ impl FormatDelimited<'_, '_> {
         self.with_mode(DelimitedMode::SoftBlockSpaces(None))
     }
 
+    /// Formats a group delimited by braces like [soft_block_spaces](Self::soft_block_spaces)
+    /// if the `bracket_spacing` option is enabled, or like [soft_block_indent](Self::soft_block_indent)
+    /// otherwise
+    pub fn soft_block_bracket_spaces(self) -> Self {
+        self.with_mode(DelimitedMode::SoftBlockBracketSpaces)
+    }
+
     pub fn soft_block_indent_with_group_id(self, group_id: Option<GroupId>) -> Self {
         self.with_mode(DelimitedMode::SoftBlockIndent(group_id))
     }
@@ -363,6 +332,14 @@ impl Format<JsFormatContext> for FormatDelimited<'_, '_> {
             mode,
         } = self;
 
+        let mode = match mode {
+            DelimitedMode::SoftBlockBracketSpaces if f.context().bracket_spacing() => {
+                DelimitedMode::SoftBlockSpaces(None)
+            }
+            DelimitedMode::SoftBlockBracketSpaces => DelimitedMode::SoftBlockIndent(None),
+            mode => *mode,
+        };
+
         let open_delimiter = format_open_delimiter(open_token);
         let close_delimiter = format_close_delimiter(close_token);
 
@@ -414,6 +391,8 @@ impl Format<JsFormatContext> for FormatDelimited<'_, '_> {
                         soft_line_break_or_space().fmt(f)?;
                     }
                 }
+                // Resolved at the start of the function
+                DelimitedMode::SoftBlockBracketSpaces => unreachable!(),
             };
 
             close_delimiter.format_token().fmt(f)
@@ -427,10 +406,12 @@ impl Format<JsFormatContext> for FormatDelimited<'_, '_> {
                     None => write!(f, [group_elements(&delimited)])?,
                     Some(group_id) => write!(
                         f,
-                        [group_elements(&delimited).with_group_id(Some(*group_id))]
+                        [group_elements(&delimited).with_group_id(Some(group_id))]
                     )?,
                 }
             }
+            // Resolved at the start of the function
+            DelimitedMode::SoftBlockBracketSpaces => unreachable!(),
         };
 
         write!(f, [format_trailing_trivia(close_token)])
@@ -442,6 +423,9 @@ enum DelimitedMode {
     BlockIndent,
     SoftBlockIndent(Option<GroupId>),
     SoftBlockSpaces(Option<GroupId>),
+    /// Resolved to [DelimitedMode::SoftBlockSpaces] or [DelimitedMode::SoftBlockIndent]
+    /// depending on the `bracket_spacing` option
+    SoftBlockBracketSpaces,
 }
 
 /// Use this function to create an open delimiter, where you can extract the formatting of
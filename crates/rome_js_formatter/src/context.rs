@@ -1,6 +1,7 @@
-use rome_formatter::printer::PrinterOptions;
+use rome_formatter::printer::{LineEnding, PrinterOptions};
 use rome_formatter::{
-    CommentContext, CommentKind, CommentStyle, FormatContext, IndentStyle, LineWidth,
+    CommentContext, CommentKind, CommentPlacement, CommentStyle, FormatContext, IndentStyle,
+    LineWidth,
 };
 use rome_js_syntax::{JsLanguage, JsSyntaxKind, SourceType};
 use rome_rowan::SyntaxTriviaPieceComments;
@@ -8,7 +9,7 @@ use std::fmt;
 use std::fmt::Debug;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct JsFormatContext {
     /// The indent style.
     indent_style: IndentStyle,
@@ -16,13 +17,61 @@ pub struct JsFormatContext {
     /// What's the max width of a line. Defaults to 80.
     line_width: LineWidth,
 
+    /// The characters printed at the end of each line. Defaults to line feed.
+    line_ending: LineEnding,
+
     /// The style for quotes. Defaults to double.
     quote_style: QuoteStyle,
 
+    /// Where trailing commas are printed. Defaults to all.
+    trailing_comma: TrailingComma,
+
+    /// Whether spaces are printed between the braces and the content of object literals and
+    /// of named imports and exports. Defaults to true.
+    bracket_spacing: bool,
+
+    /// Whether the single parameter of arrow functions is wrapped in parentheses. Defaults to
+    /// always.
+    arrow_parentheses: ArrowParens,
+
+    /// Whether the parentheses written around JSX elements are preserved. Defaults to false.
+    preserve_jsx_parentheses: bool,
+
+    /// Whether each attribute of a JSX element with multiple attributes is printed on its own line.
+    /// Defaults to false.
+    jsx_single_attribute_per_line: bool,
+
+    /// Whether object literals and object types written with a line break after their opening
+    /// brace are collapsed on a single line if they fit. Defaults to false.
+    collapse_multiline_objects: bool,
+
+    /// Additional names of functions that are formatted like test calls, for example `bench`.
+    /// Defaults to none.
+    test_call_identifiers: Vec<String>,
+
     /// Information relative to the current file
     source_type: SourceType,
 }
 
+impl Default for JsFormatContext {
+    fn default() -> Self {
+        Self {
+            indent_style: IndentStyle::default(),
+            line_width: LineWidth::default(),
+            line_ending: LineEnding::default(),
+            quote_style: QuoteStyle::default(),
+            trailing_comma: TrailingComma::default(),
+            bracket_spacing: true,
+            arrow_parentheses: ArrowParens::default(),
+            preserve_jsx_parentheses: false,
+            jsx_single_attribute_per_line: false,
+            collapse_multiline_objects: false,
+            test_call_identifiers: Vec::new(),
+            source_type: SourceType::default(),
+        }
+    }
+}
+
 impl JsFormatContext {
     pub fn new(source_type: SourceType) -> Self {
         Self {
@@ -41,11 +90,58 @@ impl JsFormatContext {
         self
     }
 
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
     pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
         self.quote_style = quote_style;
         self
     }
 
+    pub fn with_trailing_comma(mut self, trailing_comma: TrailingComma) -> Self {
+        self.trailing_comma = trailing_comma;
+        self
+    }
+
+    pub fn with_bracket_spacing(mut self, bracket_spacing: bool) -> Self {
+        self.bracket_spacing = bracket_spacing;
+        self
+    }
+
+    pub fn with_arrow_parentheses(mut self, arrow_parentheses: ArrowParens) -> Self {
+        self.arrow_parentheses = arrow_parentheses;
+        self
+    }
+
+    pub fn with_preserve_jsx_parentheses(mut self, preserve_jsx_parentheses: bool) -> Self {
+        self.preserve_jsx_parentheses = preserve_jsx_parentheses;
+        self
+    }
+
+    pub fn with_jsx_single_attribute_per_line(
+        mut self,
+        jsx_single_attribute_per_line: bool,
+    ) -> Self {
+        self.jsx_single_attribute_per_line = jsx_single_attribute_per_line;
+        self
+    }
+
+    pub fn with_collapse_multiline_objects(mut self, collapse_multiline_objects: bool) -> Self {
+        self.collapse_multiline_objects = collapse_multiline_objects;
+        self
+    }
+
+    pub fn with_test_call_identifiers<I>(mut self, identifiers: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.test_call_identifiers = identifiers.into_iter().map(Into::into).collect();
+        self
+    }
+
     pub fn with_source_type(mut self, source_type: SourceType) -> Self {
         self.source_type = source_type;
         self
@@ -55,10 +151,42 @@ impl JsFormatContext {
         self.line_width
     }
 
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
     pub fn quote_style(&self) -> QuoteStyle {
         self.quote_style
     }
 
+    pub fn trailing_comma(&self) -> TrailingComma {
+        self.trailing_comma
+    }
+
+    pub fn bracket_spacing(&self) -> bool {
+        self.bracket_spacing
+    }
+
+    pub fn arrow_parentheses(&self) -> ArrowParens {
+        self.arrow_parentheses
+    }
+
+    pub fn preserve_jsx_parentheses(&self) -> bool {
+        self.preserve_jsx_parentheses
+    }
+
+    pub fn jsx_single_attribute_per_line(&self) -> bool {
+        self.jsx_single_attribute_per_line
+    }
+
+    pub fn collapse_multiline_objects(&self) -> bool {
+        self.collapse_multiline_objects
+    }
+
+    pub fn test_call_identifiers(&self) -> &[String] {
+        &self.test_call_identifiers
+    }
+
     pub fn source_type(&self) -> SourceType {
         self.source_type
     }
@@ -101,6 +229,7 @@ impl FormatContext for JsFormatContext {
         PrinterOptions::default()
             .with_indent(self.indent_style)
             .with_print_width(self.line_width)
+            .with_line_ending(self.line_ending)
     }
 }
 
@@ -108,7 +237,20 @@ impl fmt::Display for JsFormatContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Indent style: {}", self.indent_style)?;
         writeln!(f, "Line width: {}", self.line_width.value())?;
-        writeln!(f, "Quote style: {}", self.quote_style)
+        writeln!(f, "Quote style: {}", self.quote_style)?;
+        writeln!(f, "Trailing comma: {}", self.trailing_comma)?;
+        writeln!(f, "Bracket spacing: {}", self.bracket_spacing)?;
+        writeln!(f, "Arrow parentheses: {}", self.arrow_parentheses)?;
+        writeln!(
+            f,
+            "JSX single attribute per line: {}",
+            self.jsx_single_attribute_per_line
+        )?;
+        writeln!(
+            f,
+            "Collapse multiline objects: {}",
+            self.collapse_multiline_objects
+        )
     }
 }
 
@@ -155,6 +297,24 @@ impl CommentStyle<JsLanguage> for JsCommentStyle {
                 | JsSyntaxKind::EOF
         )
     }
+
+    fn comment_boundary(
+        &self,
+        prev_kind: Option<JsSyntaxKind>,
+        next_kind: Option<JsSyntaxKind>,
+    ) -> CommentPlacement {
+        match (prev_kind, next_kind) {
+            (_, Some(next)) if self.is_group_end_token(next) => CommentPlacement::AttachedToNext,
+            // The `${` of a template element isn't a group start token but comments after it
+            // are attached to it the same way: `${/* comment */ a}`
+            (Some(prev), _)
+                if self.is_group_start_token(prev) || prev == JsSyntaxKind::DOLLAR_CURLY =>
+            {
+                CommentPlacement::AttachedToPrevious
+            }
+            _ => CommentPlacement::Spaced,
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -234,3 +394,185 @@ impl QuoteStyle {
         }
     }
 }
+
+/// Controls where the formatter prints trailing commas in multi-line lists.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum TrailingComma {
+    /// Trailing commas wherever possible, including function parameters and call arguments.
+    All,
+    /// Trailing commas where valid in ES5 (arrays, objects, named imports and exports), but not
+    /// in function parameters and call arguments.
+    Es5,
+    /// No trailing commas.
+    None,
+}
+
+impl Default for TrailingComma {
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+impl FromStr for TrailingComma {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" | "All" => Ok(Self::All),
+            "es5" | "Es5" | "ES5" => Ok(Self::Es5),
+            "none" | "None" => Ok(Self::None),
+            // TODO: replace this error with a diagnostic
+            _ => Err("Value not supported for TrailingComma"),
+        }
+    }
+}
+
+impl fmt::Display for TrailingComma {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrailingComma::All => write!(f, "All"),
+            TrailingComma::Es5 => write!(f, "ES5"),
+            TrailingComma::None => write!(f, "None"),
+        }
+    }
+}
+
+impl TrailingComma {
+    /// Returns `true` if trailing commas should be printed after function parameters and call
+    /// arguments
+    pub const fn is_all(&self) -> bool {
+        matches!(self, TrailingComma::All)
+    }
+
+    /// Returns `true` if no trailing commas should be printed at all
+    pub const fn is_none(&self) -> bool {
+        matches!(self, TrailingComma::None)
+    }
+}
+
+/// Controls whether the formatter prints parentheses around the single parameter of an arrow
+/// function.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ArrowParens {
+    /// Always print the parentheses: `(x) => x`.
+    Always,
+    /// Omit the parentheses around a single identifier parameter: `x => x`.
+    AsNeeded,
+}
+
+impl Default for ArrowParens {
+    fn default() -> Self {
+        Self::Always
+    }
+}
+
+impl FromStr for ArrowParens {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" | "Always" => Ok(Self::Always),
+            "as-needed" | "AsNeeded" => Ok(Self::AsNeeded),
+            // TODO: replace this error with a diagnostic
+            _ => Err("Value not supported for ArrowParens"),
+        }
+    }
+}
+
+impl fmt::Display for ArrowParens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrowParens::Always => write!(f, "Always"),
+            ArrowParens::AsNeeded => write!(f, "AsNeeded"),
+        }
+    }
+}
+
+impl ArrowParens {
+    /// Returns `true` if the parentheses around a single simple parameter should be omitted
+    pub const fn is_as_needed(&self) -> bool {
+        matches!(self, ArrowParens::AsNeeded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArrowParens, JsCommentStyle, TrailingComma};
+    use rome_formatter::{CommentKind, CommentPlacement, CommentStyle};
+    use rome_js_parser::parse_module;
+    use rome_js_syntax::JsSyntaxKind;
+    use std::str::FromStr;
+
+    fn comment_kind(source: &str) -> CommentKind {
+        let root = parse_module(source, 0).syntax();
+        let token = root.first_token().unwrap();
+        let comment = token
+            .leading_trivia()
+            .pieces()
+            .find_map(|piece| piece.as_comments())
+            .unwrap();
+
+        JsCommentStyle.get_comment_kind(&comment)
+    }
+
+    #[test]
+    fn classifies_comments() {
+        assert_eq!(comment_kind("// line\na;"), CommentKind::Line);
+        assert_eq!(comment_kind("/* inline */ a;"), CommentKind::InlineBlock);
+        assert_eq!(comment_kind("/**\n * block\n */\na;"), CommentKind::Block);
+    }
+
+    #[test]
+    fn trailing_comma_round_trip() {
+        for trailing_comma in [TrailingComma::All, TrailingComma::Es5, TrailingComma::None] {
+            assert_eq!(
+                TrailingComma::from_str(&trailing_comma.to_string()),
+                Ok(trailing_comma)
+            );
+        }
+    }
+
+    #[test]
+    fn arrow_parens_round_trip() {
+        for arrow_parens in [ArrowParens::Always, ArrowParens::AsNeeded] {
+            assert_eq!(
+                ArrowParens::from_str(&arrow_parens.to_string()),
+                Ok(arrow_parens)
+            );
+        }
+    }
+
+    #[test]
+    fn comment_boundaries() {
+        let boundary = |prev, next| JsCommentStyle.comment_boundary(prev, next);
+
+        // `call(/* comment */ a)`
+        assert_eq!(
+            boundary(Some(JsSyntaxKind::L_PAREN), Some(JsSyntaxKind::IDENT)),
+            CommentPlacement::AttachedToPrevious
+        );
+        // `call(a /* comment */)`
+        assert_eq!(
+            boundary(Some(JsSyntaxKind::IDENT), Some(JsSyntaxKind::R_PAREN)),
+            CommentPlacement::AttachedToNext
+        );
+        // `${/* comment */ a}`
+        assert_eq!(
+            boundary(Some(JsSyntaxKind::DOLLAR_CURLY), None),
+            CommentPlacement::AttachedToPrevious
+        );
+        // `<Foo a="1" /* comment */ b="2" />`
+        assert_eq!(
+            boundary(
+                Some(JsSyntaxKind::JSX_STRING_LITERAL),
+                Some(JsSyntaxKind::JSX_IDENT)
+            ),
+            CommentPlacement::Spaced
+        );
+        // `a /* comment */ > b`
+        assert_eq!(
+            boundary(Some(JsSyntaxKind::IDENT), Some(JsSyntaxKind::R_ANGLE)),
+            CommentPlacement::Spaced
+        );
+    }
+}
@@ -2,7 +2,9 @@ use crate::prelude::*;
 use crate::utils::is_simple_expression;
 use rome_formatter::{format_args, write};
 use rome_js_syntax::{
-    JsAnyArrowFunctionParameters, JsAnyExpression, JsAnyFunction, JsAnyFunctionBody,
+    JsAnyArrowFunctionParameters, JsAnyBinding, JsAnyBindingPattern, JsAnyExpression,
+    JsAnyFormalParameter, JsAnyFunction, JsAnyFunctionBody, JsAnyParameter, JsFormalParameter,
+    JsParameters,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -34,7 +36,12 @@ impl FormatRule<JsAnyFunction> for FormatJsAnyFunction {
 
         write!(f, [node.type_parameters().format()])?;
 
+        let omit_parentheses = f.context().arrow_parentheses().is_as_needed();
+
         match node.parameters()? {
+            JsAnyArrowFunctionParameters::JsAnyBinding(binding) if omit_parentheses => {
+                write!(f, [binding.format()])?
+            }
             JsAnyArrowFunctionParameters::JsAnyBinding(binding) => write!(
                 f,
                 [format_parenthesize(
@@ -44,7 +51,19 @@ impl FormatRule<JsAnyFunction> for FormatJsAnyFunction {
                 )
                 .grouped_with_soft_block_indent()]
             )?,
-            JsAnyArrowFunctionParameters::JsParameters(params) => write![f, [params.format()]]?,
+            JsAnyArrowFunctionParameters::JsParameters(params) => {
+                match single_simple_parameter(node, &params) {
+                    Some(parameter) if omit_parentheses => write!(
+                        f,
+                        [
+                            format_removed(&params.l_paren_token()?),
+                            parameter.format(),
+                            format_removed(&params.r_paren_token()?)
+                        ]
+                    )?,
+                    _ => write![f, [params.format()]]?,
+                }
+            }
         }
 
         write![f, [node.return_type_annotation().format(), space_token()]]?;
@@ -110,3 +129,51 @@ impl FormatRule<JsAnyFunction> for FormatJsAnyFunction {
         Ok(())
     }
 }
+
+/// Returns the parameter of an arrow function taking a single identifier parameter, without
+/// type annotation, default value or question mark, whose parentheses can be omitted: `(a) => a`
+fn single_simple_parameter(
+    node: &JsAnyFunction,
+    params: &JsParameters,
+) -> Option<JsFormalParameter> {
+    if !matches!(node, JsAnyFunction::JsArrowFunctionExpression(_))
+        || node.type_parameters().is_some()
+        || node.return_type_annotation().is_some()
+    {
+        return None;
+    }
+
+    let l_paren_token = params.l_paren_token().ok()?;
+    let r_paren_token = params.r_paren_token().ok()?;
+
+    // Removing the parentheses would move the comments out of the parameter list
+    if l_paren_token.has_trailing_comments() || r_paren_token.has_leading_comments() {
+        return None;
+    }
+
+    let items = params.items();
+    if items.len() != 1 || items.trailing_separator().is_some() {
+        return None;
+    }
+
+    let parameter = match items.iter().next()?.ok()? {
+        JsAnyParameter::JsAnyFormalParameter(JsAnyFormalParameter::JsFormalParameter(
+            parameter,
+        )) => parameter,
+        _ => return None,
+    };
+
+    let is_identifier = matches!(
+        parameter.binding(),
+        Ok(JsAnyBindingPattern::JsAnyBinding(
+            JsAnyBinding::JsIdentifierBinding(_)
+        ))
+    );
+
+    let is_simple = is_identifier
+        && parameter.question_mark_token().is_none()
+        && parameter.type_annotation().is_none()
+        && parameter.initializer().is_none();
+
+    is_simple.then(|| parameter)
+}
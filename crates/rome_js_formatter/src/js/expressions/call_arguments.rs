@@ -1,13 +1,14 @@
 use crate::builders::{format_close_delimiter, format_open_delimiter};
 use crate::prelude::*;
-use crate::utils::{is_call_like_expression, write_arguments_multi_line};
+use crate::utils::write_arguments_multi_line;
 use rome_formatter::{format_args, write};
 use rome_js_syntax::{
-    JsAnyCallArgument, JsAnyExpression, JsAnyFunctionBody, JsAnyLiteralExpression, JsAnyName,
-    JsAnyStatement, JsArrayExpression, JsArrowFunctionExpression, JsCallArgumentList,
-    JsCallArguments, JsCallArgumentsFields, JsCallExpression, JsSyntaxKind, TsReferenceType,
+    matches_test_call, JsAnyCallArgument, JsAnyExpression, JsAnyFunctionBody,
+    JsAnyLiteralExpression, JsAnyStatement, JsArrayExpression, JsArrowFunctionExpression,
+    JsCallArgumentList, JsCallArguments, JsCallArgumentsFields, JsCallExpression, JsSyntaxKind,
+    TsReferenceType,
 };
-use rome_rowan::{AstSeparatedList, SyntaxResult, SyntaxTokenText};
+use rome_rowan::{AstSeparatedList, SyntaxResult};
 
 #[derive(Debug, Clone, Default)]
 pub struct FormatJsCallArguments;
@@ -54,6 +55,7 @@ impl FormatNodeRule<JsCallArguments> for FormatJsCallArguments {
                     third_argument: &third_argument,
                     arguments_len,
                     callee: &callee,
+                    test_call_identifiers: f.context().test_call_identifiers(),
                 })?
             } else {
                 false
@@ -235,7 +237,7 @@ impl FormatNodeRule<JsCallArguments> for FormatJsCallArguments {
                             let separated = args
                                 .format_separated(JsSyntaxKind::COMMA)
                                 .with_trailing_separator(TrailingSeparator::Omit)
-                                .nodes_grouped();
+                                .with_each_node_grouped(true);
                             write_arguments_multi_line(separated, f)
                         }),),
                         r_leading_trivia,
@@ -283,7 +285,7 @@ fn should_group_first_argument(list: &JsCallArgumentList) -> SyntaxResult<bool>
     Ok(!has_comments
         && is_function_like
         && !second_arg_is_function_like
-        && !could_group_argument(&second, false)?)
+        && !could_group_argument(&second)?)
 }
 
 /// Checks if the last group requires grouping
@@ -310,19 +312,37 @@ fn should_group_last_argument(list: &JsCallArgumentList) -> SyntaxResult<bool> {
             true
         };
 
+        // A comment after the trailing comma would be moved after the call if the argument was hugged
+        let has_trailing_separator_comments = list
+            .trailing_separator()
+            .map_or(false, |separator| separator.has_trailing_comments());
+
         Ok(!last.syntax().has_comments_direct()
-            && could_group_argument(&last, false)?
+            && !has_trailing_separator_comments
+            && could_group_argument(&last)?
             && check_with_penultimate)
     } else {
         Ok(false)
     }
 }
 
-/// Checks if the current argument could be grouped
-fn could_group_argument(
-    argument: &JsAnyCallArgument,
-    is_arrow_recursion: bool,
-) -> SyntaxResult<bool> {
+/// Checks if the current argument could be grouped, meaning that the formatter may try to hug it
+/// when it is the first or the last argument of a call:
+///
+/// ```js
+/// useEffect(() => {
+///     // ...
+/// }, [a, b]);
+/// ```
+///
+/// The arguments that could be grouped are:
+/// - non empty object and array expressions, or empty ones with comments
+/// - function expressions
+/// - arrow functions with a block, object, array or JSX body, or returning an arrow function that
+///   could be grouped, as long as their parameters don't contain comments and their return type
+///   annotation isn't a reference type like `Provider<Opts>`
+/// - any of the above wrapped in a TypeScript `as` expression or type assertion
+pub(crate) fn could_group_argument(argument: &JsAnyCallArgument) -> SyntaxResult<bool> {
     let result = if let JsAnyCallArgument::JsAnyExpression(argument) = argument {
         match argument {
             JsAnyExpression::JsObjectExpression(object_expression) => {
@@ -339,15 +359,13 @@ fn could_group_argument(
                         .first_or_last_token_have_comments()
             }
             JsAnyExpression::TsTypeAssertionExpression(assertion_expression) => {
-                could_group_argument(
-                    &JsAnyCallArgument::JsAnyExpression(assertion_expression.expression()?),
-                    false,
-                )?
+                could_group_argument(&JsAnyCallArgument::JsAnyExpression(
+                    assertion_expression.expression()?,
+                ))?
             }
 
             JsAnyExpression::TsAsExpression(as_expression) => could_group_argument(
                 &JsAnyCallArgument::JsAnyExpression(as_expression.expression()?),
-                false,
             )?,
             JsAnyExpression::JsArrowFunctionExpression(arrow_function) => {
                 let body = arrow_function.body()?;
@@ -379,51 +397,48 @@ fn could_group_argument(
                     },
                 );
 
-                let body_is_delimited = matches!(
-                    body,
-                    JsAnyFunctionBody::JsFunctionBody(_)
-                        | JsAnyFunctionBody::JsAnyExpression(JsAnyExpression::JsObjectExpression(
-                            _
-                        ))
-                        | JsAnyFunctionBody::JsAnyExpression(JsAnyExpression::JsArrayExpression(_))
-                );
+                let body_could_group = match &body {
+                    JsAnyFunctionBody::JsFunctionBody(_) => true,
+                    JsAnyFunctionBody::JsAnyExpression(expression) => {
+                        let mut expression = expression.clone();
 
-                if let JsAnyFunctionBody::JsAnyExpression(any_expression) = body.clone() {
-                    let is_nested_arrow_function =
-                        if let JsAnyExpression::JsArrowFunctionExpression(
-                            arrow_function_expression,
-                        ) = &any_expression
+                        while let JsAnyExpression::JsParenthesizedExpression(parenthesized) =
+                            &expression
                         {
-                            arrow_function_expression
-                                .body()
-                                .ok()
-                                .and_then(|body| body.as_js_any_expression().cloned())
-                                .and_then(|body| {
-                                    could_group_argument(
-                                        &JsAnyCallArgument::JsAnyExpression(body),
-                                        true,
-                                    )
-                                    .ok()
-                                })
-                                .unwrap_or(false)
-                        } else {
-                            false
-                        };
-
-                    body_is_delimited
-                        && is_nested_arrow_function
-                        && can_group_type
-                        && (!is_arrow_recursion
-                            && (is_call_like_expression(&any_expression)
-                                || matches!(
-                                    body,
-                                    JsAnyFunctionBody::JsAnyExpression(
-                                        JsAnyExpression::JsConditionalExpression(_)
-                                    )
-                                )))
-                } else {
-                    body_is_delimited && can_group_type
-                }
+                            // A comment after the opening parenthesis would be moved after the
+                            // call if the argument was hugged
+                            if parenthesized.l_paren_token()?.has_trailing_comments() {
+                                return Ok(false);
+                            }
+
+                            expression = parenthesized.expression()?;
+                        }
+
+                        // Unlike Prettier, call and conditional bodies aren't grouped because
+                        // the hugged argument would print its closing parenthesis right after
+                        // the body instead of on a line of its own
+                        match expression {
+                            JsAnyExpression::JsObjectExpression(_)
+                            | JsAnyExpression::JsArrayExpression(_)
+                            | JsAnyExpression::JsxTagExpression(_) => true,
+                            expression @ JsAnyExpression::JsArrowFunctionExpression(_) => {
+                                could_group_argument(&JsAnyCallArgument::JsAnyExpression(
+                                    expression,
+                                ))?
+                            }
+                            _ => false,
+                        }
+                    }
+                };
+
+                // Comments in the parameters force them to break, and the hugged argument
+                // doesn't support breaking its parameters
+                let parameters_have_comments = arrow_function
+                    .parameters()?
+                    .syntax()
+                    .has_comments_descendants();
+
+                body_could_group && can_group_type && !parameters_have_comments
             }
 
             JsAnyExpression::JsFunctionExpression(_) => true,
@@ -473,6 +488,7 @@ struct IsTestFrameworkCallPayload<'a> {
     third_argument: &'a Option<SyntaxResult<JsAnyCallArgument>>,
     arguments_len: usize,
     callee: &'a JsAnyExpression,
+    test_call_identifiers: &'a [String],
 }
 /// This is a specialised function that checks if the current [call expression]
 /// is reminds a call expression usually used by the majority of testing frameworks.
@@ -483,7 +499,8 @@ struct IsTestFrameworkCallPayload<'a> {
 /// we need to check its [callee] and its [arguments].
 ///
 /// 1. The [callee] must contain a name or a chain of names that belongs to the
-/// test frameworks, for example: `test()`, `test.only()`, etc.
+/// test frameworks, for example: `test()`, `test.only()`, etc., or one of the
+/// test call identifiers configured in the [crate::JsFormatContext] (see [matches_test_call])
 /// 2. The [arguments] should be at the least 2
 /// 3. The first argument has to be a string literal
 /// 4. The third argument, if present, has to be a number literal
@@ -502,6 +519,7 @@ fn is_framework_test_call(payload: IsTestFrameworkCallPayload) -> SyntaxResult<b
         third_argument,
         arguments_len,
         callee,
+        test_call_identifiers,
     } = payload;
     let first_argument_is_literal_like = matches!(
         first_argument,
@@ -512,7 +530,8 @@ fn is_framework_test_call(payload: IsTestFrameworkCallPayload) -> SyntaxResult<b
         )
     );
 
-    if first_argument_is_literal_like && contains_a_test_pattern(callee)? {
+    if first_argument_is_literal_like && matches_test_call(callee, test_call_identifiers)?.is_some()
+    {
         // if the third argument is not a numeric literal, we bail
         // example: `it("name", () => { ... }, 2500)`
         if let Some(Ok(third_argument)) = third_argument {
@@ -555,183 +574,94 @@ fn is_framework_test_call(payload: IsTestFrameworkCallPayload) -> SyntaxResult<b
     }
 }
 
-/// This function checks if a call expressions has one of the following members:
-/// - `it`
-/// - `it.only`
-/// - `it.skip`
-/// - `describe`
-/// - `describe.only`
-/// - `describe.skip`
-/// - `test`
-/// - `test.only`
-/// - `test.skip`
-/// - `test.step`
-/// - `test.describe`
-/// - `test.describe.only`
-/// - `test.describe.parallel`
-/// - `test.describe.parallel.only`
-/// - `test.describe.serial`
-/// - `test.describe.serial.only`
-/// - `skip`
-/// - `xit`
-/// - `xdescribe`
-/// - `xtest`
-/// - `fit`
-/// - `fdescribe`
-/// - `ftest`
-///
-/// Based on this [article]
-///
-/// [article]: https://craftinginterpreters.com/scanning-on-demand.html#tries-and-state-machines
-fn contains_a_test_pattern(callee: &JsAnyExpression) -> SyntaxResult<bool> {
-    let members: Vec<_> = matches_test_call(callee)?;
-
-    let first = members.get(0).map(|t| t.text());
-    let second = members.get(1).map(|t| t.text());
-    let third = members.get(2).map(|t| t.text());
-    let fourth = members.get(3).map(|t| t.text());
-    let fifth = members.get(4).map(|t| t.text());
-
-    Ok(match first {
-        Some("it" | "describe") => match second {
-            None => true,
-            Some("only" | "skip") => third.is_none(),
-            _ => false,
-        },
-        Some("test") => match second {
-            None => true,
-            Some("only" | "skip" | "step") => third.is_none(),
-            Some("describe") => match third {
-                None => true,
-                Some("only") => true,
-                Some("parallel" | "serial") => match fourth {
-                    None => true,
-                    Some("only") => fifth.is_none(),
-                    _ => false,
-                },
-                _ => false,
-            },
-            _ => false,
-        },
-        Some("skip" | "xit" | "xdescribe" | "xtest" | "fit" | "fdescribe" | "ftest") => true,
-        _ => false,
-    })
-}
+#[cfg(test)]
+mod tests {
+    use super::could_group_argument;
+    use rome_js_parser::parse;
+    use rome_js_syntax::{JsCallArguments, SourceType};
+    use rome_rowan::{AstNode, AstSeparatedList};
 
-/// This is particular used to identify if a [JsCallExpression] has the shape
-/// of a call argument coming from a test framework.
-///
-/// An example are call arguments coming from Mocha, Jest, etc.
-///
-/// ```js
-/// describe("My component", () => {
-///     it("should render", () => {
-///         
-///     });
-/// })
-///
-/// test.only("", testSomething);
-/// ```
-///
-/// This function should accept the `callee` of [JsCallExpression] and the
-/// string pattern to test against. For example "test", "test.only"
-fn matches_test_call(callee: &JsAnyExpression) -> SyntaxResult<Vec<SyntaxTokenText>> {
-    // this the max depth plus one, because we want to catch cases where we have test.only.WRONG
-    const MAX_DEPTH: u8 = 5;
-    let mut test_call = Vec::with_capacity(MAX_DEPTH as usize);
-    let mut current_node = callee.clone();
-    for _ in 0..MAX_DEPTH {
-        if let JsAnyExpression::JsIdentifierExpression(identifier) = &current_node {
-            let value_token = identifier.name()?.value_token()?;
-            let value = value_token.token_text_trimmed();
-            test_call.push(value);
-            break;
-        } else if let JsAnyExpression::JsStaticMemberExpression(member_expression) = &current_node {
-            match member_expression.member()? {
-                JsAnyName::JsName(name) => {
-                    let value = name.value_token()?;
-                    test_call.push(value.token_text_trimmed());
-                    current_node = member_expression.object()?;
-                }
-                _ => break,
-            };
-        } else {
-            break;
-        }
+    /// Returns whether the first argument of the call in `text` could be grouped
+    fn could_group_first_argument(text: &str) -> bool {
+        could_group_first_argument_with_source_type(text, SourceType::ts())
     }
-    test_call.reverse();
-    Ok(test_call)
-}
 
-#[cfg(test)]
-mod test {
-    use super::contains_a_test_pattern;
-    use rome_js_parser::parse;
-    use rome_js_syntax::{JsCallExpression, SourceType};
-    use rome_rowan::AstNodeList;
-
-    fn extract_call_expression(src: &str) -> JsCallExpression {
-        let source_type = SourceType::js_module();
-        let result = parse(src, 0, source_type);
-        let module = result
-            .tree()
-            .as_js_module()
-            .unwrap()
-            .items()
-            .first()
+    fn could_group_first_argument_with_source_type(text: &str, source_type: SourceType) -> bool {
+        let parse = parse(text, 0, source_type);
+        assert!(!parse.has_errors(), "{text} contains syntax errors");
+
+        let arguments = parse
+            .syntax()
+            .descendants()
+            .find_map(JsCallArguments::cast)
             .unwrap();
+        let first = arguments.args().iter().next().unwrap().unwrap();
 
-        module
-            .as_js_any_statement()
-            .unwrap()
-            .as_js_expression_statement()
-            .unwrap()
-            .expression()
-            .unwrap()
-            .as_js_call_expression()
-            .unwrap()
-            .clone()
+        could_group_argument(&first).unwrap()
     }
 
     #[test]
-    fn matches_simple_call() {
-        let call_expression = extract_call_expression("test();");
-        assert_eq!(
-            contains_a_test_pattern(&call_expression.callee().unwrap()),
-            Ok(true)
-        );
-
-        let call_expression = extract_call_expression("it();");
-        assert_eq!(
-            contains_a_test_pattern(&call_expression.callee().unwrap()),
-            Ok(true)
-        );
+    fn object_and_array_arguments() {
+        assert!(could_group_first_argument("f({ a })"));
+        assert!(could_group_first_argument("f([a])"));
+        assert!(could_group_first_argument("f({ /* comment */ })"));
+
+        assert!(!could_group_first_argument("f({})"));
+        assert!(!could_group_first_argument("f([])"));
+        assert!(!could_group_first_argument("f(a)"));
     }
 
     #[test]
-    fn matches_static_member_expression() {
-        let call_expression = extract_call_expression("test.only();");
-        assert_eq!(
-            contains_a_test_pattern(&call_expression.callee().unwrap()),
-            Ok(true)
-        );
+    fn function_arguments() {
+        assert!(could_group_first_argument("f(function () {})"));
+        assert!(could_group_first_argument("f(() => {})"));
+        assert!(could_group_first_argument("f(() => ({ a }))"));
+        assert!(could_group_first_argument("f(() => [a])"));
+
+        assert!(!could_group_first_argument("f(() => a)"));
+        assert!(!could_group_first_argument("f(() => a())"));
+        assert!(!could_group_first_argument("f((a /* comment */) => {})"));
+        assert!(!could_group_first_argument("f(() => ( // comment\n[a]))"));
     }
 
     #[test]
-    fn matches_static_member_expression_deep() {
-        let call_expression = extract_call_expression("test.describe.parallel.only();");
-        assert_eq!(
-            contains_a_test_pattern(&call_expression.callee().unwrap()),
-            Ok(true)
-        );
+    fn arrow_with_jsx_body_arguments() {
+        assert!(could_group_first_argument_with_source_type(
+            "f(() => <div />)",
+            SourceType::tsx()
+        ));
+        assert!(could_group_first_argument_with_source_type(
+            "f(() => (<div />))",
+            SourceType::tsx()
+        ));
     }
 
     #[test]
-    fn doesnt_static_member_expression_deep() {
-        let call_expression = extract_call_expression("test.describe.parallel.only.AHAHA();");
-        assert_eq!(
-            contains_a_test_pattern(&call_expression.callee().unwrap()),
-            Ok(false)
-        );
+    fn type_assertion_wrapped_arguments() {
+        assert!(could_group_first_argument("f(function () {} as Callback)"));
+        assert!(could_group_first_argument("f(<Callback>function () {})"));
+        assert!(could_group_first_argument("f({ a } as Options)"));
+
+        assert!(!could_group_first_argument("f(a as Options)"));
+        assert!(!could_group_first_argument("f(<Options>{})"));
+    }
+
+    #[test]
+    fn arrow_with_return_type_arguments() {
+        assert!(could_group_first_argument(
+            "f((req, res): void => { res.send(); })"
+        ));
+
+        assert!(!could_group_first_argument(
+            "f((type: ObjectType): Provider<Opts> => {})"
+        ));
+    }
+
+    #[test]
+    fn nested_arrow_chain_arguments() {
+        assert!(could_group_first_argument("f((a) => (b) => {})"));
+        assert!(could_group_first_argument("f((a) => (b) => ({ c }))"));
+
+        assert!(!could_group_first_argument("f((a) => (b) => c())"));
     }
 }
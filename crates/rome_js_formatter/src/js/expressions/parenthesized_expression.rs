@@ -1,14 +1,13 @@
 use crate::prelude::*;
-use crate::utils::{is_simple_expression, FormatPrecedence};
+use crate::utils::is_simple_expression;
+use crate::utils::parentheses::needs_parentheses;
 use rome_formatter::write;
 
-use crate::utils::JsAnyBinaryLikeExpression;
-
 use rome_js_syntax::{
     JsAnyExpression, JsAnyLiteralExpression, JsParenthesizedExpression,
-    JsParenthesizedExpressionFields, JsSyntaxKind,
+    JsParenthesizedExpressionFields,
 };
-use rome_rowan::{AstNode, SyntaxResult};
+use rome_rowan::SyntaxResult;
 
 #[derive(Debug, Clone, Default)]
 pub struct FormatJsParenthesizedExpression;
@@ -25,10 +24,33 @@ impl FormatNodeRule<JsParenthesizedExpression> for FormatJsParenthesizedExpressi
             r_paren_token,
         } = node.as_fields();
 
-        let parenthesis_can_be_omitted = parenthesis_can_be_omitted(node)?;
-
         let expression = expression?;
 
+        if let JsAnyExpression::JsxTagExpression(expression) = &expression {
+            // The JSX tag expression decides whether it gets wrapped in parentheses,
+            // unless the author's parentheses should be preserved
+            return if f.context().preserve_jsx_parentheses() {
+                write![
+                    f,
+                    [
+                        format_delimited(&l_paren_token?, &expression.format(), &r_paren_token?,)
+                            .soft_block_indent()
+                    ]
+                ]
+            } else {
+                write![
+                    f,
+                    [
+                        format_removed(&l_paren_token?),
+                        expression.format(),
+                        format_removed(&r_paren_token?),
+                    ]
+                ]
+            };
+        }
+
+        let parenthesis_can_be_omitted = !needs_parentheses(node)?;
+
         if is_simple_parenthesized_expression(node)? {
             if parenthesis_can_be_omitted {
                 write!(f, [format_removed(&l_paren_token?)])?;
@@ -86,16 +108,6 @@ impl FormatNodeRule<JsParenthesizedExpression> for FormatJsParenthesizedExpressi
                         ]
                     ]
                 }
-                JsAnyExpression::JsxTagExpression(expression) => {
-                    write![
-                        f,
-                        [
-                            format_removed(&l_paren_token?),
-                            expression.format(),
-                            format_removed(&r_paren_token?),
-                        ]
-                    ]
-                }
                 _ => write![
                     f,
                     [
@@ -127,63 +139,3 @@ fn is_simple_parenthesized_expression(node: &JsParenthesizedExpression) -> Synta
 
     Ok(true)
 }
-
-fn parenthesis_can_be_omitted(node: &JsParenthesizedExpression) -> SyntaxResult<bool> {
-    let expression = node.expression()?;
-    let parent = node.syntax().parent();
-
-    // if expression is a StringLiteralExpression, we need to check it before precedence comparison, here is an example:
-    // ```js
-    // a[("test")]
-    // ```
-    // if we use precedence comparison, we will get:
-    // parent_precedence should be `High` due to the parenthesized_expression's parent is ComputedMemberExpression,
-    // and node_precedence should be `Low` due to expression is StringLiteralExpression. `parent_precedence > node_precedence` will return false,
-    // the parenthesis will not be omitted.
-    // But the expected behavior is that the parenthesis will be omitted. The code above should be formatted as:
-    // ```js
-    // a["test"]
-    // ```
-    // So we need to add extra branch to handle this case.
-    if matches!(
-        expression,
-        JsAnyExpression::JsAnyLiteralExpression(JsAnyLiteralExpression::JsStringLiteralExpression(
-            _
-        ))
-    ) {
-        return Ok(!matches!(
-            parent.map(|p| p.kind()),
-            Some(JsSyntaxKind::JS_EXPRESSION_STATEMENT)
-        ));
-    }
-    let parent_precedence = FormatPrecedence::with_precedence_for_parenthesis(parent.as_ref());
-    let node_precedence = FormatPrecedence::with_precedence_for_parenthesis(Some(node.syntax()));
-
-    if parent_precedence > node_precedence {
-        return Ok(false);
-    }
-    // Here we handle cases where we have binary/logical expressions.
-    // We want to remove the parenthesis only in cases where `left` and `right` are not other
-    // binary/logical expressions.
-    //
-    // From another point of view, logical/binary expressions with the same operator can stay without
-    // parenthesis.
-    match expression {
-        JsAnyExpression::JsBinaryExpression(expression) => {
-            let left = expression.left()?;
-            let right = expression.right()?;
-
-            Ok(!JsAnyBinaryLikeExpression::can_cast(left.syntax().kind())
-                && !JsAnyBinaryLikeExpression::can_cast(right.syntax().kind()))
-        }
-
-        JsAnyExpression::JsLogicalExpression(expression) => {
-            let left = expression.left()?;
-            let right = expression.right()?;
-
-            Ok(!JsAnyBinaryLikeExpression::can_cast(left.syntax().kind())
-                && !JsAnyBinaryLikeExpression::can_cast(right.syntax().kind()))
-        }
-        _ => Ok(false),
-    }
-}
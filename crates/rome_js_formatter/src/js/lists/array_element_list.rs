@@ -27,11 +27,18 @@ impl FormatRule<JsArrayElementList> for FormatJsArrayElementList {
 
     fn fmt(&self, node: &JsArrayElementList, f: &mut JsFormatter) -> FormatResult<()> {
         if !has_formatter_trivia(node.syntax()) && can_print_fill(node) {
+            let trailing_separator = if f.context().trailing_comma().is_none() {
+                TrailingSeparator::Omit
+            } else {
+                TrailingSeparator::Allowed
+            };
+
             // Using format_separated is valid in this case as can_print_fill does not allow holes
             return f
                 .fill(soft_line_break_or_space())
                 .entries(
                     node.format_separated(JsSyntaxKind::COMMA)
+                        .with_trailing_separator(trailing_separator)
                         .with_group_id(self.group_id),
                 )
                 .finish();
@@ -16,6 +16,8 @@ impl FormatRule<JsConstructorParameterList> for FormatJsConstructorParameterList
 
         let trailing_separator = if has_trailing_rest {
             TrailingSeparator::Disallowed
+        } else if !f.context().trailing_comma().is_all() {
+            TrailingSeparator::Omit
         } else {
             TrailingSeparator::Allowed
         };
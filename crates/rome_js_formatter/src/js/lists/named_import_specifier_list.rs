@@ -8,8 +8,17 @@ impl FormatRule<JsNamedImportSpecifierList> for FormatJsNamedImportSpecifierList
     type Context = JsFormatContext;
 
     fn fmt(&self, node: &JsNamedImportSpecifierList, f: &mut JsFormatter) -> FormatResult<()> {
+        let trailing_separator = if f.context().trailing_comma().is_none() {
+            TrailingSeparator::Omit
+        } else {
+            TrailingSeparator::Allowed
+        };
+
         f.join_with(&soft_line_break_or_space())
-            .entries(node.format_separated(JsSyntaxKind::COMMA))
+            .entries(
+                node.format_separated(JsSyntaxKind::COMMA)
+                    .with_trailing_separator(trailing_separator),
+            )
             .finish()
     }
 }
@@ -27,6 +27,8 @@ impl FormatRule<JsObjectAssignmentPatternPropertyList>
 
         let trailing_separator = if has_trailing_rest {
             TrailingSeparator::Disallowed
+        } else if f.context().trailing_comma().is_none() {
+            TrailingSeparator::Omit
         } else {
             TrailingSeparator::Allowed
         };
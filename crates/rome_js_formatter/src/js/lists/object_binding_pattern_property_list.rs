@@ -25,6 +25,8 @@ impl FormatRule<JsObjectBindingPatternPropertyList> for FormatJsObjectBindingPat
 
         let trailing_separator = if has_trailing_rest {
             TrailingSeparator::Disallowed
+        } else if f.context().trailing_comma().is_none() {
+            TrailingSeparator::Omit
         } else {
             TrailingSeparator::Allowed
         };
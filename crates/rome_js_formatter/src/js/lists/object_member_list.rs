@@ -9,12 +9,18 @@ impl FormatRule<JsObjectMemberList> for FormatJsObjectMemberList {
     type Context = JsFormatContext;
 
     fn fmt(&self, node: &JsObjectMemberList, f: &mut JsFormatter) -> FormatResult<()> {
+        let trailing_separator = if f.context().trailing_comma().is_none() {
+            TrailingSeparator::Omit
+        } else {
+            TrailingSeparator::Allowed
+        };
+
         let mut join = f.join_nodes_with_soft_line();
 
-        for (element, formatted) in node
-            .elements()
-            .zip(node.format_separated(JsSyntaxKind::COMMA))
-        {
+        for (element, formatted) in node.elements().zip(
+            node.format_separated(JsSyntaxKind::COMMA)
+                .with_trailing_separator(trailing_separator),
+        ) {
             join.entry(element.node()?.syntax(), &formatted);
         }
 
@@ -16,6 +16,9 @@ impl FormatRule<JsParameterList> for FormatJsParameterList {
 
         let trailing_separator = if has_trailing_rest {
             TrailingSeparator::Disallowed
+        } else if !f.context().trailing_comma().is_all() {
+            // Trailing commas in parameter lists aren't valid in ES5
+            TrailingSeparator::Omit
         } else {
             TrailingSeparator::Allowed
         };
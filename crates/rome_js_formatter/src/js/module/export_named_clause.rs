@@ -31,7 +31,7 @@ impl FormatNodeRule<JsExportNamedClause> for FormatJsExportNamedClause {
                     &specifiers.format(),
                     r_curly_token.as_ref()?
                 )
-                .soft_block_spaces()]
+                .soft_block_bracket_spaces()]
             )
         });
 
@@ -45,7 +45,7 @@ impl FormatNodeRule<JsExportNamedFromClause> for FormatJsExportNamedFromClause {
                         &specifiers.format(),
                         r_curly_token.as_ref()?,
                     )
-                    .soft_block_spaces()]
+                    .soft_block_bracket_spaces()]
                 )?;
             };
 
@@ -19,7 +19,7 @@ impl FormatNodeRule<JsNamedImportSpecifiers> for FormatJsNamedImportSpecifiers {
             f,
             [
                 format_delimited(&l_curly_token?, &specifiers.format(), &r_curly_token?,)
-                    .soft_block_spaces()
+                    .soft_block_bracket_spaces()
             ]
         )
     }
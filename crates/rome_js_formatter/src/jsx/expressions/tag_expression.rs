@@ -1,22 +1,42 @@
 use crate::prelude::*;
-use crate::utils::jsx_utils::{get_wrap_state, WrapState};
+use crate::utils::jsx_utils::{
+    get_wrap_state, is_jsx_inside_arrow_function_inside_call_inside_expression_child, WrapState,
+};
 use rome_formatter::{format_args, write};
-use rome_js_syntax::JsxTagExpression;
+use rome_js_syntax::{JsParenthesizedExpression, JsxTagExpression};
 
 #[derive(Debug, Clone, Default)]
 pub struct FormatJsxTagExpression;
 
 impl FormatNodeRule<JsxTagExpression> for FormatJsxTagExpression {
     fn fmt_fields(&self, node: &JsxTagExpression, f: &mut JsFormatter) -> FormatResult<()> {
-        match get_wrap_state(node.syntax()) {
-            WrapState::WrapOnBreak => write![
-                f,
-                [group_elements(&format_args![
-                    if_group_breaks(&token("(")),
-                    soft_block_indent(&format_args![node.tag().format()]),
-                    if_group_breaks(&token(")"))
-                ])]
-            ],
+        let is_parenthesized = node.syntax().parent().map_or(false, |parent| {
+            JsParenthesizedExpression::can_cast(parent.kind())
+        });
+
+        // The parentheses written by the author are formatted by the parenthesized expression
+        let wrap_state = if is_parenthesized && f.context().preserve_jsx_parentheses() {
+            WrapState::NoWrap
+        } else {
+            get_wrap_state(node.syntax())
+        };
+
+        match wrap_state {
+            WrapState::WrapOnBreak => {
+                let expand_if_special_case =
+                    is_jsx_inside_arrow_function_inside_call_inside_expression_child(node.syntax())
+                        .then(expand_parent);
+
+                write![
+                    f,
+                    [group_elements(&format_args![
+                        if_group_breaks(&token("(")),
+                        expand_if_special_case,
+                        soft_block_indent(&format_args![node.tag().format()]),
+                        if_group_breaks(&token(")"))
+                    ])]
+                ]
+            }
             WrapState::AlwaysWrap => write![
                 f,
                 [group_elements(&format_args![
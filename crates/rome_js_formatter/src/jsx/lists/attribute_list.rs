@@ -9,8 +9,14 @@ impl FormatRule<JsxAttributeList> for FormatJsxAttributeList {
     type Context = JsFormatContext;
 
     fn fmt(&self, node: &JsxAttributeList, f: &mut JsFormatter) -> FormatResult<()> {
-        let attributes = format_with(|f| {
-            f.join_with(&soft_line_break_or_space())
+        let attributes = format_with(|f: &mut JsFormatter| {
+            let separator = if f.context().jsx_single_attribute_per_line() && node.len() > 1 {
+                hard_line_break()
+            } else {
+                soft_line_break_or_space()
+            };
+
+            f.join_with(&separator)
                 .entries(node.iter().formatted())
                 .finish()
         });
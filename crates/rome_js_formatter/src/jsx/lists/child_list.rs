@@ -1,20 +1,34 @@
+use crate::context::QuoteStyle;
 use crate::prelude::*;
-use crate::utils::jsx_utils::contains_meaningful_jsx_text;
+use crate::utils::jsx_utils::{
+    contains_meaningful_jsx_text, is_meaningful_jsx_text, jsx_space_tokens, split_jsx_text,
+    JsxTextPiece,
+};
 use crate::JsFormatter;
-use rome_js_syntax::JsxChildList;
+use rome_formatter::{format_args, write, FormatRuleWithOptions, GroupId};
+use rome_js_syntax::{JsSyntaxToken, JsxAnyChild, JsxChildList};
+use rome_rowan::{TextRange, TextSize};
 
 #[derive(Debug, Clone, Default)]
-pub struct FormatJsxChildList;
+pub struct FormatJsxChildList {
+    group_id: Option<GroupId>,
+}
+
+impl FormatRuleWithOptions<JsxChildList> for FormatJsxChildList {
+    type Options = Option<GroupId>;
+
+    fn with_options(mut self, options: Self::Options) -> Self {
+        self.group_id = options;
+        self
+    }
+}
 
 impl FormatRule<JsxChildList> for FormatJsxChildList {
     type Context = JsFormatContext;
 
     fn fmt(&self, node: &JsxChildList, formatter: &mut JsFormatter) -> FormatResult<()> {
         if contains_meaningful_jsx_text(node) {
-            formatter
-                .fill(soft_line_break())
-                .flatten_entries(node.iter().formatted())
-                .finish()
+            format_jsx_children(node, self.group_id, formatter)
         } else {
             formatter
                 .join_with(soft_line_break())
@@ -23,3 +37,258 @@ impl FormatRule<JsxChildList> for FormatJsxChildList {
         }
     }
 }
+
+/// Formats the children of a JSX element that contain meaningful text, filling each line with
+/// as many words and children as fit in the line width.
+///
+/// The whitespace between the children, as defined by [crate::utils::jsx_utils::JSX_WHITESPACE_CHARS],
+/// is handled as follows:
+/// - whitespace between two words becomes a single space, or a line break;
+/// - whitespace containing a newline between text and any other child isn't meaningful:
+///   it's removed and the formatter is free to insert a line break;
+/// - whitespace without a newline between text and any other child is meaningful:
+///   it becomes a single space and the two children are kept on the same line;
+/// - meaningful whitespace at the start or the end of the children becomes `{" "}` when the
+///   group with `group_id` breaks, because the whitespace next to a line break is ignored.
+///
+/// A `{" "}` child is handled like meaningful whitespace, so that formatting the output again
+/// gives the same result.
+///
+/// ```jsx
+/// <div> Chungking Express </div>;
+///
+/// <div>
+///     {" "}
+///     Ashes of Time <b>Redux</b>, a wuxia film directed by Wong Kar-wai and starring
+///     Leslie Cheung{" "}
+/// </div>;
+/// ```
+pub(crate) fn format_jsx_children(
+    children: &JsxChildList,
+    group_id: Option<GroupId>,
+    f: &mut JsFormatter,
+) -> FormatResult<()> {
+    let mut entries = JsxFillEntries::default();
+    let last_child_index = children.len().saturating_sub(1);
+
+    // Index of the last child that isn't whitespace containing a newline
+    let last_content_index = children
+        .iter()
+        .enumerate()
+        .filter(|(_, child)| match child {
+            JsxAnyChild::JsxText(text) => text
+                .value_token()
+                .map_or(true, |token| is_meaningful_jsx_text(token.text())),
+            _ => true,
+        })
+        .map(|(index, _)| index)
+        .last();
+
+    for (child_index, child) in children.iter().enumerate() {
+        let text = match child {
+            JsxAnyChild::JsxText(text) => text,
+            JsxAnyChild::JsxExpressionChild(expression) => {
+                match jsx_space_tokens(&expression) {
+                    Some(tokens) => {
+                        let is_list_start = !entries.has_content();
+                        let is_list_end = Some(child_index) == last_content_index;
+
+                        for token in tokens {
+                            entries.push(JsxFillPiece::Removed(token));
+                        }
+
+                        entries.push_space(group_id, is_list_start, is_list_end);
+                    }
+                    None => {
+                        entries.push_content(JsxFillPiece::Child(JsxAnyChild::JsxExpressionChild(
+                            expression,
+                        )));
+                    }
+                }
+                continue;
+            }
+            child => {
+                entries.push_content(JsxFillPiece::Child(child));
+                continue;
+            }
+        };
+
+        let token = text.value_token()?;
+        let start = token.text_range().start();
+        let pieces = split_jsx_text(token.text());
+        let last_piece_index = pieces.len().saturating_sub(1);
+
+        entries.push(JsxFillPiece::Removed(token.clone()));
+
+        for (piece_index, piece) in pieces.into_iter().enumerate() {
+            let has_newline = match piece {
+                JsxTextPiece::Word(offset, word) => {
+                    let word_start = start + TextSize::from(offset as u32);
+                    let range = TextRange::at(word_start, TextSize::of(word));
+                    entries.push_content(JsxFillPiece::Word(token.clone(), range));
+                    continue;
+                }
+                JsxTextPiece::Whitespace { has_newline } => has_newline,
+            };
+
+            let is_leading = piece_index == 0;
+            let is_trailing = piece_index == last_piece_index;
+
+            if !is_leading && !is_trailing {
+                // Whitespace between two words
+                entries.push_separator();
+                continue;
+            }
+
+            if has_newline {
+                continue;
+            }
+
+            let is_list_start = is_leading && child_index == 0;
+            let is_list_end = is_trailing && child_index == last_child_index;
+
+            entries.push_space(group_id, is_list_start, is_list_end);
+        }
+    }
+
+    f.fill(soft_line_break()).entries(entries.finish()).finish()
+}
+
+/// Part of an entry of the fill created by [format_jsx_children]
+enum JsxFillPiece {
+    /// Tracks a text token that gets printed in [JsxFillPiece::Word] slices
+    Removed(JsSyntaxToken),
+    Word(JsSyntaxToken, TextRange),
+    Child(JsxAnyChild),
+    Space,
+    LeadingSpace(GroupId),
+    TrailingSpace(GroupId),
+}
+
+impl JsxFillPiece {
+    const fn is_content(&self) -> bool {
+        !matches!(self, JsxFillPiece::Removed(_))
+    }
+}
+
+impl Format<JsFormatContext> for JsxFillPiece {
+    fn fmt(&self, f: &mut JsFormatter) -> FormatResult<()> {
+        let jsx_space = match f.context().quote_style() {
+            QuoteStyle::Double => "{\" \"}",
+            QuoteStyle::Single => "{\' \'}",
+        };
+
+        match self {
+            JsxFillPiece::Removed(token) => write!(f, [format_removed(token)]),
+            JsxFillPiece::Word(token, range) => {
+                write!(f, [syntax_token_text_slice(token, *range)])
+            }
+            JsxFillPiece::Child(child) => write!(f, [child.format()]),
+            JsxFillPiece::Space => write!(f, [space_token()]),
+            JsxFillPiece::LeadingSpace(group_id) => write!(
+                f,
+                [
+                    if_group_breaks(&format_args![token(jsx_space), hard_line_break()])
+                        .with_group_id(Some(*group_id)),
+                    if_group_fits_on_line(&space_token()).with_group_id(Some(*group_id))
+                ]
+            ),
+            JsxFillPiece::TrailingSpace(group_id) => write!(
+                f,
+                [
+                    if_group_breaks(&token(jsx_space)).with_group_id(Some(*group_id)),
+                    if_group_fits_on_line(&space_token()).with_group_id(Some(*group_id))
+                ]
+            ),
+        }
+    }
+}
+
+/// Collects the pieces of the JSX children into fill entries.
+///
+/// The fill may insert a line break between two entries but never inside of an entry.
+#[derive(Default)]
+struct JsxFillEntries {
+    entries: Vec<Vec<JsxFillPiece>>,
+    current: Vec<JsxFillPiece>,
+    /// Whether the next content must be part of the current entry
+    glue: bool,
+}
+
+impl JsxFillEntries {
+    /// Adds a piece to the current entry
+    fn push(&mut self, piece: JsxFillPiece) {
+        self.current.push(piece);
+    }
+
+    /// Adds a word or a child, starting a new entry unless the previous piece is glued to it
+    fn push_content(&mut self, piece: JsxFillPiece) {
+        if !self.glue {
+            self.flush();
+        }
+
+        self.current.push(piece);
+        self.glue = false;
+    }
+
+    /// Adds the space between two words: the fill may break the line after it
+    fn push_separator(&mut self) {
+        self.current.push(JsxFillPiece::Space);
+        self.glue = false;
+    }
+
+    /// Adds a meaningful space that keeps the next content on the same line
+    fn push_glued_space(&mut self) {
+        self.current.push(JsxFillPiece::Space);
+        self.glue = true;
+    }
+
+    /// Adds a meaningful space, which becomes `{" "}` if it's at the start or the end
+    /// of the children and the group with `group_id` breaks
+    fn push_space(&mut self, group_id: Option<GroupId>, is_list_start: bool, is_list_end: bool) {
+        match group_id {
+            Some(group_id) if is_list_start && !is_list_end => {
+                self.push_entry(JsxFillPiece::LeadingSpace(group_id));
+            }
+            Some(group_id) if is_list_end && !is_list_start => {
+                self.push(JsxFillPiece::TrailingSpace(group_id));
+            }
+            _ => self.push_glued_space(),
+        }
+    }
+
+    /// Returns `true` if any word or child has been added
+    fn has_content(&self) -> bool {
+        !self.entries.is_empty() || self.current.iter().any(JsxFillPiece::is_content)
+    }
+
+    /// Adds a piece as an entry of its own
+    fn push_entry(&mut self, piece: JsxFillPiece) {
+        self.flush();
+        self.current.push(piece);
+        self.flush();
+        self.glue = false;
+    }
+
+    fn flush(&mut self) {
+        if self.current.iter().any(JsxFillPiece::is_content) {
+            self.entries.push(std::mem::take(&mut self.current));
+        }
+    }
+
+    fn finish(mut self) -> impl Iterator<Item = JsxFillEntry> {
+        if !self.current.is_empty() {
+            self.entries.push(self.current);
+        }
+
+        self.entries.into_iter().map(JsxFillEntry)
+    }
+}
+
+struct JsxFillEntry(Vec<JsxFillPiece>);
+
+impl Format<JsFormatContext> for JsxFillEntry {
+    fn fmt(&self, f: &mut JsFormatter) -> FormatResult<()> {
+        f.join().entries(self.0.iter()).finish()
+    }
+}
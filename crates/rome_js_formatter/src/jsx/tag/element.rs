@@ -1,6 +1,5 @@
 use crate::prelude::*;
 use crate::soft_block_indent;
-use crate::utils::jsx_utils::is_jsx_inside_arrow_function_inside_call_inside_expression_child;
 use rome_formatter::{format_args, write, FormatResult};
 use rome_js_syntax::{JsxElement, JsxElementFields};
 
@@ -15,18 +14,16 @@ impl FormatNodeRule<JsxElement> for FormatJsxElement {
             closing_element,
         } = node.as_fields();
 
-        let expand_if_special_case =
-            is_jsx_inside_arrow_function_inside_call_inside_expression_child(node.syntax())
-                .then(expand_parent);
+        let group_id = formatter.group_id("jsx_children");
 
         write![
             formatter,
             [group_elements(&format_args![
                 opening_element.format(),
-                expand_if_special_case,
-                soft_block_indent(&children.format()),
+                soft_block_indent(&children.format().with_options(Some(group_id))),
                 closing_element.format()
-            ])]
+            ])
+            .with_group_id(Some(group_id))]
         ]
     }
 }
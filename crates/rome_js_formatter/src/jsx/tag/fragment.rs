@@ -1,6 +1,6 @@
 use crate::prelude::*;
-
-use rome_formatter::write;
+use crate::soft_block_indent;
+use rome_formatter::{format_args, write};
 use rome_js_syntax::{JsxFragment, JsxFragmentFields};
 
 #[derive(Debug, Clone, Default)]
@@ -14,13 +14,16 @@ impl FormatNodeRule<JsxFragment> for FormatJsxFragment {
             closing_fragment,
         } = node.as_fields();
 
+        let group_id = f.group_id("jsx_children");
+
         write![
             f,
-            [
+            [group_elements(&format_args![
                 opening_fragment.format(),
-                children.format(),
+                soft_block_indent(&children.format().with_options(Some(group_id))),
                 closing_fragment.format()
-            ]
+            ])
+            .with_group_id(Some(group_id))]
         ]
     }
 }
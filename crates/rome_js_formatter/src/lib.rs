@@ -18,7 +18,7 @@ use rome_rowan::AstNode;
 use rome_rowan::SyntaxResult;
 use rome_rowan::TextRange;
 
-use crate::builders::format_suppressed_node;
+use crate::builders::{format_children, format_suppressed_node};
 use crate::context::JsFormatContext;
 use crate::cst::FormatJsSyntaxNode;
 use std::iter::FusedIterator;
@@ -180,7 +180,12 @@ where
     }
 
     /// Formats the node's fields.
-    fn fmt_fields(&self, item: &N, f: &mut JsFormatter) -> FormatResult<()>;
+    ///
+    /// Defaults to formatting the children of the node one after the other, see
+    /// [format_children]. Nodes that need a specific layout should override this method.
+    fn fmt_fields(&self, item: &N, f: &mut JsFormatter) -> FormatResult<()> {
+        format_children(item.syntax()).fmt(f)
+    }
 }
 
 /// Format implementation specific to JavaScript tokens.
@@ -266,6 +271,17 @@ pub fn format_node(context: JsFormatContext, root: &JsSyntaxNode) -> FormatResul
     rome_formatter::format_node(context, &root.format())
 }
 
+/// Formats a JavaScript (and its super languages) file and returns the pretty-printed
+/// [FormatElement](rome_formatter::FormatElement) tree of the document before it gets printed.
+///
+/// This is meant for debugging the layout decisions of the formatter, for example to find out
+/// why a group breaks unexpectedly.
+pub fn format_node_ir(context: JsFormatContext, root: &JsSyntaxNode) -> FormatResult<String> {
+    let formatted = format_node(context, root)?;
+
+    Ok(std::format!("{:#?}", formatted.into_format_element()))
+}
+
 /// Formats a single node within a file, supported by Rome.
 ///
 /// This runs a simple heuristic to determine the initial indentation
@@ -283,11 +299,13 @@ pub fn format_sub_tree(context: JsFormatContext, root: &JsSyntaxNode) -> FormatR
 #[cfg(test)]
 mod tests {
 
-    use super::format_range;
+    use super::{format_node, format_node_ir, format_range};
 
     use crate::context::JsFormatContext;
+    use rome_formatter::printer::LineEnding;
     use rome_formatter::IndentStyle;
-    use rome_js_parser::parse_script;
+    use rome_js_parser::{parse, parse_script};
+    use rome_js_syntax::SourceType;
     use rome_rowan::{TextRange, TextSize};
 
     #[test]
@@ -435,6 +453,124 @@ function() {
         assert_eq!(result.as_code(), "");
         assert_eq!(result.range(), Some(TextRange::new(range_start, range_end)));
     }
+
+    #[test]
+    fn test_preserve_jsx_parentheses() {
+        let input = "const a = (<div />);";
+
+        let tree = parse(input, 0, SourceType::jsx());
+
+        let result =
+            format_node(JsFormatContext::default(), &tree.syntax()).expect("formatting failed");
+        assert_eq!(result.print().as_code(), "const a = <div />;\n");
+
+        let result = format_node(
+            JsFormatContext::default().with_preserve_jsx_parentheses(true),
+            &tree.syntax(),
+        )
+        .expect("formatting failed");
+        assert_eq!(result.print().as_code(), "const a = (<div />);\n");
+    }
+
+    #[test]
+    fn test_custom_test_call_identifiers() {
+        let test_call = r#"it("does something really long and complicated so I have to write a very long name for the test", () => {
+    console.log("hello!");
+});"#;
+        let bench_call = test_call.replacen("it(", "bench(", 1);
+
+        let format = |input: &str, context: JsFormatContext| {
+            let tree = parse(input, 0, SourceType::js_module());
+            format_node(context, &tree.syntax())
+                .expect("formatting failed")
+                .print()
+                .as_code()
+                .to_string()
+        };
+
+        let expected = format(test_call, JsFormatContext::default()).replacen("it(", "bench(", 1);
+
+        assert_ne!(format(&bench_call, JsFormatContext::default()), expected);
+        assert_eq!(
+            format(
+                &bench_call,
+                JsFormatContext::default().with_test_call_identifiers(["bench"])
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let input = "function f() {\n\treturn 1;\n}\n";
+
+        let tree = parse(input, 0, SourceType::js_module());
+
+        let result = format_node(
+            JsFormatContext::default().with_line_ending(LineEnding::CarriageReturnLineFeed),
+            &tree.syntax(),
+        )
+        .expect("formatting failed");
+        assert_eq!(
+            result.print().as_code(),
+            "function f() {\r\n\treturn 1;\r\n}\r\n"
+        );
+    }
+
+    #[test]
+    fn test_format_node_ir() {
+        let tree = parse("call(a, b);", 0, SourceType::js_module());
+        let ir =
+            format_node_ir(JsFormatContext::default(), &tree.syntax()).expect("formatting failed");
+
+        assert_eq!(
+            ir,
+            r#"List [
+    SyntaxTokenSlice("call"),
+    Group(
+        [
+            SyntaxTokenSlice("("),
+            Indent(
+                [
+                    Line(
+                        Soft,
+                    ),
+                    Group(
+                        [
+                            SyntaxTokenSlice("a"),
+                        ],
+                    ),
+                    SyntaxTokenSlice(","),
+                    Line(
+                        SoftOrSpace,
+                    ),
+                    Group(
+                        [
+                            SyntaxTokenSlice("b"),
+                        ],
+                    ),
+                    ConditionalGroupContent {
+                        content: [
+                            StaticToken(","),
+                        ],
+                        mode: Expanded,
+                        group_id: None,
+                    },
+                ],
+            ),
+            Line(
+                Soft,
+            ),
+            SyntaxTokenSlice(")"),
+        ],
+    ),
+    SyntaxTokenSlice(";"),
+    Line(
+        Hard,
+    ),
+]"#
+        );
+    }
 }
 
 #[cfg(test)]
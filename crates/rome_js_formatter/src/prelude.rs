@@ -8,9 +8,9 @@ pub use rome_formatter::prelude::*;
 pub use rome_rowan::{AstNode as _, AstNodeList as _, AstSeparatedList as _};
 
 pub use crate::builders::{
-    format_delimited, format_inserted, format_inserted_close_paren, format_inserted_open_paren,
-    format_or_verbatim, format_parenthesize, format_suppressed_node, format_unknown_node,
-    format_verbatim_node,
+    format_children, format_delimited, format_inserted, format_inserted_close_paren,
+    format_inserted_open_paren, format_or_verbatim, format_parenthesize, format_suppressed_node,
+    format_unknown_node, format_verbatim_node,
 };
 
 pub use crate::separated::{
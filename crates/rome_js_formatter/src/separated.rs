@@ -25,7 +25,7 @@ where
         let node = self.element.node()?;
         let separator = self.element.trailing_separator()?;
 
-        if !self.options.nodes_grouped {
+        if !self.options.each_node_grouped {
             node.format().fmt(f)?;
         } else {
             group_elements(&node.format()).fmt(f)?;
@@ -107,9 +107,14 @@ where
         }
     }
 
-    /// Wraps every node inside of a group
-    pub fn nodes_grouped(mut self) -> Self {
-        self.options.nodes_grouped = true;
+    /// Sets whether every node of the list is wrapped inside of its own group. Defaults to `false`.
+    ///
+    /// Without per-node grouping, the line breaks of a node that aren't inside of a group of the
+    /// node itself belong to the group enclosing the list: breaking the list breaks them in every
+    /// node. Grouping each node lets the printer measure the nodes separately, so that a single
+    /// node that doesn't fit on the line can break without forcing the other nodes to break.
+    pub fn with_each_node_grouped(mut self, each_node_grouped: bool) -> Self {
+        self.options.each_node_grouped = each_node_grouped;
         self
     }
 
@@ -204,5 +209,120 @@ impl Default for TrailingSeparator {
 pub struct FormatSeparatedOptions {
     trailing_separator: TrailingSeparator,
     group_id: Option<GroupId>,
-    nodes_grouped: bool,
+    each_node_grouped: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::AsFormat;
+    use rome_formatter::{format, format_args, write, LineWidth};
+    use rome_js_parser::parse_module;
+    use rome_js_syntax::{
+        JsAnyArrayElement, JsArrayElementList, JsLanguage, JsSyntaxKind, JsSyntaxNode,
+    };
+    use rome_rowan::{AstNode, AstSeparatedList, SyntaxKindSet, SyntaxList};
+
+    /// Array element that is formatted as `element + 1` with a line break before the `+` that
+    /// isn't inside of a group of the element itself.
+    #[derive(Debug, Clone)]
+    struct PlusOneElement(JsAnyArrayElement);
+
+    impl AstNode for PlusOneElement {
+        type Language = JsLanguage;
+
+        const KIND_SET: SyntaxKindSet<JsLanguage> = JsAnyArrayElement::KIND_SET;
+
+        fn can_cast(kind: JsSyntaxKind) -> bool {
+            JsAnyArrayElement::can_cast(kind)
+        }
+
+        fn cast(syntax: JsSyntaxNode) -> Option<Self> {
+            JsAnyArrayElement::cast(syntax).map(PlusOneElement)
+        }
+
+        fn syntax(&self) -> &JsSyntaxNode {
+            self.0.syntax()
+        }
+
+        fn into_syntax(self) -> JsSyntaxNode {
+            self.0.into_syntax()
+        }
+    }
+
+    struct FormatPlusOneElement<'a>(&'a PlusOneElement);
+
+    impl Format<JsFormatContext> for FormatPlusOneElement<'_> {
+        fn fmt(&self, f: &mut JsFormatter) -> FormatResult<()> {
+            let FormatPlusOneElement(PlusOneElement(element)) = self;
+
+            write!(
+                f,
+                [
+                    element.format(),
+                    indent(&format_args![soft_line_break_or_space(), token("+ 1")])
+                ]
+            )
+        }
+    }
+
+    impl<'a> AsFormat<'a> for PlusOneElement {
+        type Format = FormatPlusOneElement<'a>;
+
+        fn format(&'a self) -> Self::Format {
+            FormatPlusOneElement(self)
+        }
+    }
+
+    struct PlusOneElementList(JsArrayElementList);
+
+    impl AstSeparatedList for PlusOneElementList {
+        type Language = JsLanguage;
+        type Node = PlusOneElement;
+
+        fn syntax_list(&self) -> &SyntaxList<JsLanguage> {
+            self.0.syntax_list()
+        }
+
+        fn into_syntax_list(self) -> SyntaxList<JsLanguage> {
+            self.0.into_syntax_list()
+        }
+    }
+
+    /// Prints the elements of `[aaaaaaaaaaaaaaaa, b]` on separate lines
+    fn format_elements(each_node_grouped: bool) -> String {
+        let tree = parse_module("[aaaaaaaaaaaaaaaa, b]", 0);
+        let list = tree
+            .syntax()
+            .descendants()
+            .find_map(JsArrayElementList::cast)
+            .map(PlusOneElementList)
+            .unwrap();
+
+        let formatted = format!(
+            JsFormatContext::default().with_line_width(LineWidth::try_from(20).unwrap()),
+            [format_with(|f: &mut JsFormatter| {
+                f.join_with(hard_line_break())
+                    .entries(
+                        list.format_separated(JsSyntaxKind::COMMA)
+                            .with_each_node_grouped(each_node_grouped),
+                    )
+                    .finish()
+            })]
+        )
+        .unwrap();
+
+        formatted.print().as_code().to_string()
+    }
+
+    #[test]
+    fn each_node_grouped() {
+        // Only the element that exceeds the line width breaks
+        assert_eq!(format_elements(true), "aaaaaaaaaaaaaaaa\n\t+ 1,\nb + 1,");
+        // All elements break with the enclosing (root) group
+        assert_eq!(
+            format_elements(false),
+            "aaaaaaaaaaaaaaaa\n\t+ 1,\nb\n\t+ 1,"
+        );
+    }
 }
@@ -16,7 +16,10 @@ impl FormatRule<TsEnumMemberList> for FormatTsEnumMemberList {
         } else {
             soft_line_break_or_space()
         })
-        .entries(node.format_separated(JsSyntaxKind::COMMA).nodes_grouped())
+        .entries(
+            node.format_separated(JsSyntaxKind::COMMA)
+                .with_each_node_grouped(true),
+        )
         .finish()
     }
 }
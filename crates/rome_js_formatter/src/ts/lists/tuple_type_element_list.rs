@@ -9,7 +9,10 @@ impl FormatRule<TsTupleTypeElementList> for FormatTsTupleTypeElementList {
 
     fn fmt(&self, node: &TsTupleTypeElementList, f: &mut JsFormatter) -> FormatResult<()> {
         f.join_with(&soft_line_break_or_space())
-            .entries(node.format_separated(JsSyntaxKind::COMMA).nodes_grouped())
+            .entries(
+                node.format_separated(JsSyntaxKind::COMMA)
+                    .with_each_node_grouped(true),
+            )
             .finish()
     }
 }
@@ -17,6 +17,8 @@ impl FormatRule<TsTypeParameterList> for FormatTsTypeParameterList {
         // It's an edge case, while waiting for a better solution,
         let trailing_separator = if node.len() == 1 && node.trailing_separator().is_some() {
             TrailingSeparator::Mandatory
+        } else if !f.context().trailing_comma().is_all() {
+            TrailingSeparator::Omit
         } else {
             TrailingSeparator::default()
         };
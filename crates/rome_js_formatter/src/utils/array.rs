@@ -18,6 +18,7 @@ where
     // inserted after holes regardless of the formatting since this makes a
     // semantic difference
 
+    let trailing_comma = f.context().trailing_comma();
     let mut join = f.join_nodes_with_soft_line();
     let last_index = node.len().saturating_sub(1);
 
@@ -44,6 +45,11 @@ where
                         Some(trailing) => write!(f, [trailing.format()])?,
                         None => format_inserted(JsSyntaxKind::COMMA).fmt(f)?,
                     };
+                } else if trailing_comma.is_none() {
+                    // Trailing commas are disabled, remove the separator of the last element
+                    if let Some(separator) = element.trailing_separator()? {
+                        write!(f, [format_removed(separator)])?;
+                    }
                 } else if let Some(separator) = element.trailing_separator()? {
                     write!(f, [format_only_if_breaks(separator, &separator.format())])?;
                 } else {
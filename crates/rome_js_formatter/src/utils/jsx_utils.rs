@@ -1,6 +1,9 @@
 use rome_js_syntax::kind::JsSyntaxKind;
-use rome_js_syntax::{JsLanguage, JsxAnyChild, JsxChildList};
-use rome_rowan::{AstNodeList, SyntaxNode};
+use rome_js_syntax::{
+    JsAnyExpression, JsAnyLiteralExpression, JsLanguage, JsSyntaxToken, JsxAnyChild, JsxChildList,
+    JsxExpressionChild,
+};
+use rome_rowan::{AstNode, AstNodeList, SyntaxNode};
 
 /// Checks if the children of an element contain meaningful text. See [is_meaningful_jsx_text] for
 /// definition of meaningful JSX text.
@@ -48,6 +51,92 @@ pub fn is_meaningful_jsx_text(text: &str) -> bool {
     !has_newline
 }
 
+/// A piece of JSX text, as returned by [split_jsx_text].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum JsxTextPiece<'a> {
+    /// A run of non-whitespace characters and its offset from the start of the text.
+    Word(usize, &'a str),
+    /// A run of [JSX_WHITESPACE_CHARS], and whether it contains a newline.
+    Whitespace { has_newline: bool },
+}
+
+/// Splits JSX text into words and the runs of whitespace between them.
+///
+/// ```
+/// use rome_js_formatter::utils::jsx_utils::{split_jsx_text, JsxTextPiece};
+///
+/// assert_eq!(
+///     split_jsx_text(" Chungking \n  Express"),
+///     vec![
+///         JsxTextPiece::Whitespace { has_newline: false },
+///         JsxTextPiece::Word(1, "Chungking"),
+///         JsxTextPiece::Whitespace { has_newline: true },
+///         JsxTextPiece::Word(14, "Express"),
+///     ]
+/// );
+/// ```
+pub fn split_jsx_text(text: &str) -> Vec<JsxTextPiece> {
+    let mut pieces = Vec::new();
+    let mut word_start = None;
+    let mut whitespace = None;
+
+    for (index, c) in text.char_indices() {
+        if JSX_WHITESPACE_CHARS.contains(&c) {
+            if let Some(start) = word_start.take() {
+                pieces.push(JsxTextPiece::Word(start, &text[start..index]));
+            }
+
+            let has_newline = whitespace.unwrap_or(false);
+            whitespace = Some(has_newline || c == '\n');
+        } else {
+            if let Some(has_newline) = whitespace.take() {
+                pieces.push(JsxTextPiece::Whitespace { has_newline });
+            }
+
+            word_start.get_or_insert(index);
+        }
+    }
+
+    if let Some(start) = word_start {
+        pieces.push(JsxTextPiece::Word(start, &text[start..]));
+    }
+
+    if let Some(has_newline) = whitespace {
+        pieces.push(JsxTextPiece::Whitespace { has_newline });
+    }
+
+    pieces
+}
+
+/// Returns the curly tokens and the string token of an expression child that is a JSX space,
+/// `{" "}` or `{' '}`, or `None` if the expression child is anything else or has comments.
+pub fn jsx_space_tokens(child: &JsxExpressionChild) -> Option<[JsSyntaxToken; 3]> {
+    let l_curly_token = child.l_curly_token().ok()?;
+    let r_curly_token = child.r_curly_token().ok()?;
+
+    let string_literal = match child.expression()? {
+        JsAnyExpression::JsAnyLiteralExpression(
+            JsAnyLiteralExpression::JsStringLiteralExpression(string_literal),
+        ) => string_literal,
+        _ => return None,
+    };
+
+    if string_literal.syntax().has_comments_direct()
+        || l_curly_token.has_trailing_comments()
+        || r_curly_token.has_leading_comments()
+    {
+        return None;
+    }
+
+    let str_token = string_literal.value_token().ok()?;
+
+    if matches!(str_token.text_trimmed(), "\" \"" | "' '") {
+        Some([l_curly_token, str_token, r_curly_token])
+    } else {
+        None
+    }
+}
+
 /// Indicates that an element should always be wrapped in parentheses, should be wrapped
 /// only when it's line broken, or should not be wrapped at all.
 pub enum WrapState {
@@ -108,24 +197,28 @@ pub fn get_wrap_state(node: &SyntaxNode<JsLanguage>) -> WrapState {
         .unwrap_or(WrapState::NoWrap)
 }
 
-/// This is a very special situation where we're returning a JsxElement
+/// This is a very special situation where we're returning a JSX tag expression
 /// from an arrow function that's passed as an argument to a function,
 /// which is itself inside a JSX expression child.
 ///
 /// If you're wondering why this is the only other case, it's because
 /// Prettier defines it to be that way.
 ///
+/// In this case, the tag expression is always wrapped in parentheses and printed on its own line:
+///
 /// ```jsx
 ///  let bar = <div>
-///    {foo(() => <div> the quick brown fox jumps over the lazy dog </div>)}
+///    {foo(() => (
+///      <div> the quick brown fox jumps over the lazy dog </div>
+///    ))}
 ///  </div>;
 /// ```
 pub fn is_jsx_inside_arrow_function_inside_call_inside_expression_child(
     node: &SyntaxNode<JsLanguage>,
 ) -> bool {
     // We skip the first item because the first item in ancestors is the node itself, i.e.
-    // the JSX Element in this case.
-    let mut ancestors = node.ancestors().skip(2).peekable();
+    // the JSX tag expression in this case.
+    let mut ancestors = node.ancestors().skip(1).peekable();
 
     // This matching should work with or without parentheses around the JSX element
     // therefore we ignore parenthesized expressions.
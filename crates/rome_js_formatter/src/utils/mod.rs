@@ -11,6 +11,7 @@ mod member_chain;
 mod object;
 mod object_like;
 mod object_pattern_like;
+pub mod parentheses;
 #[cfg(test)]
 mod quickcheck_utils;
 mod typescript;
@@ -465,6 +466,8 @@ pub(crate) fn is_call_like_expression(expression: &JsAnyExpression) -> bool {
 }
 
 /// This function is in charge to format the call arguments.
+///
+/// A trailing comma is only printed after the last argument if the [crate::context::TrailingComma] option is `All`.
 pub(crate) fn write_arguments_multi_line<S: Format<JsFormatContext>, I>(
     separated: I,
     f: &mut JsFormatter,
@@ -472,13 +475,14 @@ pub(crate) fn write_arguments_multi_line<S: Format<JsFormatContext>, I>(
 where
     I: Iterator<Item = S>,
 {
+    let trailing_comma = f.context().trailing_comma();
     let mut iterator = separated.peekable();
     let mut join_with = f.join_with(soft_line_break_or_space());
 
     while let Some(element) = iterator.next() {
         let last = iterator.peek().is_none();
 
-        if last {
+        if last && trailing_comma.is_all() {
             join_with.entry(&format_args![&element, &if_group_breaks(&token(","))]);
         } else {
             join_with.entry(&element);
@@ -60,7 +60,7 @@ impl Format<JsFormatContext> for JsObjectLike {
                         .soft_block_indent()
                 ]
             )
-        } else if self.members_have_leading_newline() {
+        } else if !f.context().collapse_multiline_objects() && self.members_have_leading_newline() {
             write!(
                 f,
                 [
@@ -73,7 +73,7 @@ impl Format<JsFormatContext> for JsObjectLike {
                 f,
                 [
                     format_delimited(&self.l_curly_token()?, &members, &self.r_curly_token()?)
-                        .soft_block_spaces()
+                        .soft_block_bracket_spaces()
                 ]
             )
         }
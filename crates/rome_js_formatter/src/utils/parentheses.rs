@@ -0,0 +1,131 @@
+use crate::utils::{FormatPrecedence, JsAnyBinaryLikeExpression};
+use rome_js_syntax::{
+    JsAnyExpression, JsAnyLiteralExpression, JsParenthesizedExpression, JsSyntaxKind,
+};
+use rome_rowan::{AstNode, SyntaxResult};
+
+/// Returns `true` if the parentheses of a [JsParenthesizedExpression] must be kept, `false`
+/// if the formatter can remove them without changing the semantics of the program.
+///
+/// Parentheses are kept when:
+/// - the parent has a higher precedence than the parenthesized expression, for example
+///   `(a + b) * c`, `(() => {}) ? a : b` or `(a || b)?.c`;
+/// - the expression is a binary or logical expression that has another binary-like
+///   expression as operand, for example `const a = (b + c * d)`;
+/// - the parentheses may be a closure type cast, for example `/** @type {string} */ (a)`.
+///
+/// Parentheses around a primary expression, such as `const a = (b)`, are removed.
+///
+/// JSX tag expressions are excluded: the formatter decides whether they are wrapped in
+/// parentheses with [crate::utils::jsx_utils::get_wrap_state].
+///
+/// ```
+/// use rome_js_formatter::utils::parentheses::needs_parentheses;
+/// use rome_js_parser::parse_module;
+/// use rome_js_syntax::JsParenthesizedExpression;
+/// use rome_rowan::AstNode;
+///
+/// let parse = parse_module("(a + b) * c; const d = (e);", 0);
+/// let mut parenthesized = parse
+///     .syntax()
+///     .descendants()
+///     .filter_map(JsParenthesizedExpression::cast);
+///
+/// assert!(needs_parentheses(&parenthesized.next().unwrap()).unwrap());
+/// assert!(!needs_parentheses(&parenthesized.next().unwrap()).unwrap());
+/// ```
+pub fn needs_parentheses(node: &JsParenthesizedExpression) -> SyntaxResult<bool> {
+    let expression = node.expression()?;
+    let parent = node.syntax().parent();
+
+    // if expression is a StringLiteralExpression, we need to check it before precedence comparison, here is an example:
+    // ```js
+    // a[("test")]
+    // ```
+    // if we use precedence comparison, we will get:
+    // parent_precedence should be `High` due to the parenthesized_expression's parent is ComputedMemberExpression,
+    // and node_precedence should be `Low` due to expression is StringLiteralExpression. `parent_precedence > node_precedence` will return false,
+    // the parenthesis will not be omitted.
+    // But the expected behavior is that the parenthesis will be omitted. The code above should be formatted as:
+    // ```js
+    // a["test"]
+    // ```
+    // So we need to add extra branch to handle this case.
+    if matches!(
+        expression,
+        JsAnyExpression::JsAnyLiteralExpression(JsAnyLiteralExpression::JsStringLiteralExpression(
+            _
+        ))
+    ) {
+        return Ok(matches!(
+            parent.map(|p| p.kind()),
+            Some(JsSyntaxKind::JS_EXPRESSION_STATEMENT)
+        ));
+    }
+    let parent_precedence = FormatPrecedence::with_precedence_for_parenthesis(parent.as_ref());
+    let node_precedence = FormatPrecedence::with_precedence_for_parenthesis(Some(node.syntax()));
+
+    if parent_precedence > node_precedence {
+        return Ok(true);
+    }
+    // Here we handle cases where we have binary/logical expressions.
+    // We want to remove the parenthesis only in cases where `left` and `right` are not other
+    // binary/logical expressions.
+    //
+    // From another point of view, logical/binary expressions with the same operator can stay without
+    // parenthesis.
+    match expression {
+        JsAnyExpression::JsBinaryExpression(expression) => {
+            let left = expression.left()?;
+            let right = expression.right()?;
+
+            Ok(JsAnyBinaryLikeExpression::can_cast(left.syntax().kind())
+                || JsAnyBinaryLikeExpression::can_cast(right.syntax().kind()))
+        }
+
+        JsAnyExpression::JsLogicalExpression(expression) => {
+            let left = expression.left()?;
+            let right = expression.right()?;
+
+            Ok(JsAnyBinaryLikeExpression::can_cast(left.syntax().kind())
+                || JsAnyBinaryLikeExpression::can_cast(right.syntax().kind()))
+        }
+
+        JsAnyExpression::JsIdentifierExpression(_)
+        | JsAnyExpression::JsThisExpression(_)
+        | JsAnyExpression::JsAnyLiteralExpression(_) => {
+            Ok(is_nested_in_parentheses(node) || has_type_cast_comment(node)?)
+        }
+
+        JsAnyExpression::JsxTagExpression(_) => Ok(false),
+
+        _ => Ok(true),
+    }
+}
+
+/// Returns `true` if the parent of `node` is another parenthesized expression, for example `((a))`
+fn is_nested_in_parentheses(node: &JsParenthesizedExpression) -> bool {
+    node.syntax().parent().map_or(false, |parent| {
+        parent.kind() == JsSyntaxKind::JS_PARENTHESIZED_EXPRESSION
+    })
+}
+
+/// Returns `true` if a comment precedes the opening parenthesis of `node`.
+///
+/// Closure Compiler uses a JSDoc comment followed by a parenthesized expression as a type cast,
+/// the parentheses are then part of the cast and must be kept:
+///
+/// ```js
+/// const a = /** @type {string} */ (b);
+/// ```
+fn has_type_cast_comment(node: &JsParenthesizedExpression) -> SyntaxResult<bool> {
+    let l_paren_token = node.l_paren_token()?;
+
+    if l_paren_token.has_leading_comments() {
+        return Ok(true);
+    }
+
+    Ok(l_paren_token
+        .prev_token()
+        .map_or(false, |token| token.has_trailing_comments()))
+}
@@ -14,6 +14,7 @@ use std::{
 };
 
 use rome_diagnostics::{file::SimpleFiles, termcolor, Emitter};
+use rome_formatter::printer::char_width;
 use rome_formatter::IndentStyle;
 use rome_js_formatter::context::JsFormatContext;
 use rome_js_parser::parse;
@@ -154,10 +155,14 @@ fn test_snapshot(input: &'static str, _: &str, _: &str, _: &str) {
     }
 
     let max_width = context.line_width().value() as usize;
+    let tab_width = context.tab_width().into();
     let mut lines_exceeding_max_width = formatted
         .lines()
         .enumerate()
-        .filter(|(_, line)| line.len() > max_width)
+        .filter(|(_, line)| {
+            let width: usize = line.chars().map(|char| char_width(char, tab_width)).sum();
+            width > max_width
+        })
         .peekable();
 
     if lines_exceeding_max_width.peek().is_some() {
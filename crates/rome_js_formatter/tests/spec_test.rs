@@ -1,10 +1,11 @@
+use rome_formatter::printer::char_width;
 use rome_formatter::LineWidth;
 use rome_formatter::{IndentStyle, Printed};
 use rome_fs::RomePath;
-use rome_js_formatter::context::{JsFormatContext, QuoteStyle};
+use rome_js_formatter::context::{ArrowParens, JsFormatContext, QuoteStyle, TrailingComma};
 use rome_js_formatter::format_node;
 use rome_js_parser::parse;
-use rome_js_syntax::{ModuleKind, SourceType};
+use rome_js_syntax::SourceType;
 use rome_service::workspace::{FeatureName, SupportsFeatureParams};
 use rome_service::App;
 use serde::{Deserialize, Serialize};
@@ -49,6 +50,38 @@ impl From<SerializableQuoteStyle> for QuoteStyle {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize, Serialize)]
+pub enum SerializableTrailingComma {
+    All,
+    Es5,
+    None,
+}
+
+impl From<SerializableTrailingComma> for TrailingComma {
+    fn from(test: SerializableTrailingComma) -> Self {
+        match test {
+            SerializableTrailingComma::All => TrailingComma::All,
+            SerializableTrailingComma::Es5 => TrailingComma::Es5,
+            SerializableTrailingComma::None => TrailingComma::None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize, Serialize)]
+pub enum SerializableArrowParens {
+    Always,
+    AsNeeded,
+}
+
+impl From<SerializableArrowParens> for ArrowParens {
+    fn from(test: SerializableArrowParens) -> Self {
+        match test {
+            SerializableArrowParens::Always => ArrowParens::Always,
+            SerializableArrowParens::AsNeeded => ArrowParens::AsNeeded,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Copy)]
 pub struct SerializableFormatContext {
     /// The indent style.
@@ -59,6 +92,21 @@ pub struct SerializableFormatContext {
 
     // The style for quotes. Defaults to double.
     pub quote_style: Option<SerializableQuoteStyle>,
+
+    // Where to print trailing commas. Defaults to all.
+    pub trailing_comma: Option<SerializableTrailingComma>,
+
+    // Whether to print spaces inside the braces of objects. Defaults to true.
+    pub bracket_spacing: Option<bool>,
+
+    // Whether to print parentheses around a single arrow function parameter. Defaults to always.
+    pub arrow_parentheses: Option<SerializableArrowParens>,
+
+    // Whether to print each JSX attribute on its own line. Defaults to false.
+    pub jsx_single_attribute_per_line: Option<bool>,
+
+    // Whether to collapse objects that start with a newline when they fit. Defaults to false.
+    pub collapse_multiline_objects: Option<bool>,
 }
 
 impl From<SerializableFormatContext> for JsFormatContext {
@@ -68,15 +116,24 @@ impl From<SerializableFormatContext> for JsFormatContext {
                 test.indent_style
                     .map_or_else(|| IndentStyle::Tab, |value| value.into()),
             )
-            .with_line_width(
-                test.line_width
-                    .and_then(|width| LineWidth::try_from(width).ok())
-                    .unwrap_or_default(),
-            )
+            .with_line_width(test.line_width.map_or_else(LineWidth::default, |width| {
+                LineWidth::try_from(width).unwrap_or_else(|error| panic!("{error}"))
+            }))
             .with_quote_style(
                 test.quote_style
                     .map_or_else(|| QuoteStyle::Double, |value| value.into()),
             )
+            .with_trailing_comma(
+                test.trailing_comma
+                    .map_or_else(|| TrailingComma::All, |value| value.into()),
+            )
+            .with_bracket_spacing(test.bracket_spacing.unwrap_or(true))
+            .with_arrow_parentheses(
+                test.arrow_parentheses
+                    .map_or_else(|| ArrowParens::Always, |value| value.into()),
+            )
+            .with_jsx_single_attribute_per_line(test.jsx_single_attribute_per_line.unwrap_or(false))
+            .with_collapse_multiline_objects(test.collapse_multiline_objects.unwrap_or(false))
     }
 }
 
@@ -105,10 +162,14 @@ impl SnapshotContent {
         }
 
         let line_width_limit = context.line_width().value() as usize;
+        let tab_width = context.tab_width().into();
         let mut exceeding_lines = code
             .lines()
             .enumerate()
-            .filter(|(_, line)| line.len() > line_width_limit)
+            .filter(|(_, line)| {
+                let width: usize = line.chars().map(|char| char_width(char, tab_width)).sum();
+                width > line_width_limit
+            })
             .peekable();
 
         if exceeding_lines.peek().is_some() {
@@ -170,7 +231,7 @@ impl SnapshotContent {
 ///
 /// * `json/null` -> input: `tests/specs/json/null.json`, expected output: `tests/specs/json/null.json.snap`
 /// * `null` -> input: `tests/specs/null.json`, expected output: `tests/specs/null.json.snap`
-pub fn run(spec_input_file: &str, _expected_file: &str, test_directory: &str, file_type: &str) {
+pub fn run(spec_input_file: &str, _expected_file: &str, test_directory: &str, _file_type: &str) {
     let app = App::from_env(false);
 
     let file_path = &spec_input_file;
@@ -191,10 +252,7 @@ pub fn run(spec_input_file: &str, _expected_file: &str, test_directory: &str, fi
     if can_format {
         let mut snapshot_content = SnapshotContent::default();
         let buffer = rome_path.get_buffer_from_file();
-        let mut source_type: SourceType = rome_path.as_path().try_into().unwrap();
-        if file_type != "module" {
-            source_type = source_type.with_module_kind(ModuleKind::Script);
-        }
+        let source_type: SourceType = rome_path.as_path().try_into().unwrap();
 
         let input = fs::read_to_string(file_path).unwrap();
         snapshot_content.set_input(input.as_str());
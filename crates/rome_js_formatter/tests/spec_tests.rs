@@ -7,7 +7,7 @@ mod formatter {
     }
 
     mod js_script {
-        tests_macros::gen_tests! {"tests/specs/js/script/**/*.js", crate::spec_test::run, "script"}
+        tests_macros::gen_tests! {"tests/specs/js/script/**/*.cjs", crate::spec_test::run, "script"}
     }
 
     mod ts_module {
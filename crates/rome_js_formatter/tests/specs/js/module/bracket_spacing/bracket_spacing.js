@@ -0,0 +1,5 @@
+import {a, b} from "a";
+export { c } from "c";
+
+const object = { a: 1, b: {c: 2} };
+const empty = { };
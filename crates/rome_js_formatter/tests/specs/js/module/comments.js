@@ -69,6 +69,7 @@ function test /* spacing before parentheses*/ () {}
 
 a /* before member */.test;
 statement /* comment */;
+statement /* comment */ ;
 
 /* leading */ "test" /*trailing*/ ;
 
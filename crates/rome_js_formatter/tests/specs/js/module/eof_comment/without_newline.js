@@ -0,0 +1 @@
+a; // end
\ No newline at end of file
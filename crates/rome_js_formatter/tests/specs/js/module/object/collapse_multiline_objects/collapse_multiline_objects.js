@@ -0,0 +1,3 @@
+let a = { a: 1 };
+let b = {
+ b: 1 };
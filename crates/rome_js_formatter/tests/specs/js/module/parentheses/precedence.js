@@ -0,0 +1,4 @@
+(a + b) * c;
+const d = (a);
+foo((a), (1), (this));
+const e = /** @type {string} */ (a);
@@ -26,4 +26,16 @@ const    expr2    =    {
 
 let a =
     // rome-ignore format: test
-function () {}
\ No newline at end of file
+function () {}
+
+statement(  a  );
+// rome-ignore format: keep the spacing
+statement(  b  );
+statement(  c  );
+
+/*
+ * rome-ignore lint: allow the unused value
+ * rome-ignore format: keep the spacing
+ */
+statement(  d  );
+statement(  e  );
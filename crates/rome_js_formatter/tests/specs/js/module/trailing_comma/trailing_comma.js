@@ -0,0 +1,17 @@
+callSomething(someVeryLongArgumentNameNumberOne, someVeryLongArgumentNameNumberTwo, three);
+
+const array = [someVeryLongArrayElementNumberOne, someVeryLongArrayElementNumberTwo, three];
+
+const object = { someVeryLongPropertyNameOne: 1, someVeryLongPropertyNameTwo: 2, three: 3, };
+
+function declaration(someVeryLongParameterNameOne, someVeryLongParameterNameTwo, three) {}
+
+class Class {
+	constructor(someVeryLongParameterNameOne, someVeryLongParameterNameTwo, three) {}
+}
+
+import { someVeryLongImportNameOne, someVeryLongImportNameTwo, someVeryLongImportNameThree } from "module";
+
+export { someVeryLongImportNameOne, someVeryLongImportNameTwo, someVeryLongImportNameThree };
+
+export { someVeryLongExportNameOne, someVeryLongExportNameTwo, someVeryLongExportNameThree } from "module";
@@ -0,0 +1,7 @@
+let bar = <div>
+	{foo(() => <div>short</div>)}
+</div>;
+
+let baz = <div>
+	{foo(() => (<span>parenthesized</span>))}
+</div>;
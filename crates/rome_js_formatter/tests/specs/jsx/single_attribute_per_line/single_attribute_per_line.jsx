@@ -0,0 +1,2 @@
+<Foo a="1" b="2" c="3" />;
+<Foo a="1" />;
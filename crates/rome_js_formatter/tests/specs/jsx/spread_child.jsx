@@ -0,0 +1,3 @@
+<div>{...children}</div>;
+<div>{...[a,b,  c]}</div>;
+<div>{   ...children}</div>;
@@ -0,0 +1,13 @@
+<div> Chungking Express </div>;
+
+<div>In the Mood for Love <b>2000</b></div>;
+
+<div>
+	Days of Being Wild, <i>As Tears Go By</i>, Fallen Angels, Happy Together, <b>2046</b> and The Grandmaster
+</div>;
+
+<div> Ashes of Time <b>Redux</b>, a wuxia film directed by Wong Kar-wai and starring Leslie Cheung </div>;
+
+<> Happy Together </>;
+
+<> In the Mood for Love, Chungking Express, Fallen Angels, Happy Together, Days of Being Wild </>;
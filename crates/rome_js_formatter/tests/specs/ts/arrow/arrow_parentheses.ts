@@ -0,0 +1,8 @@
+x => x;
+(x) => x;
+async (x) => x;
+(x: number) => x;
+(x = 1) => x;
+({ x }) => x;
+(x): number => x;
+(x, y) => x;
@@ -0,0 +1,3 @@
+function generic<SomeVeryLongTypeParameterOne, SomeVeryLongTypeParameterTwo, Three>() {}
+
+function parameters(someVeryLongParameterNameOne: string, someVeryLongParameterNameTwo: string) {}
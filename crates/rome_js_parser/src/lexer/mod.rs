@@ -192,6 +192,32 @@ impl<'src> Lexer<'src> {
         }
     }
 
+    /// Makes a new lexer that starts lexing `string` at the byte offset `position` instead of the
+    /// start of the source, for instance to only re-lex the tokens of an edited range of a document.
+    ///
+    /// `position` must be the start of a token or trivia, and `after_newline` must be `true` if
+    /// there's a line break between the last non-trivia token before `position` and `position`.
+    #[allow(unused)]
+    pub fn from_str_at(
+        string: &'src str,
+        file_id: FileId,
+        position: TextSize,
+        after_newline: bool,
+    ) -> Self {
+        assert!(
+            string.is_char_boundary(usize::from(position)),
+            "position {:?} is not a char boundary of the source",
+            position
+        );
+
+        Self {
+            position: usize::from(position),
+            current_start: position,
+            after_newline,
+            ..Self::from_str(string, file_id)
+        }
+    }
+
     /// Returns the source code
     pub fn source(&self) -> &'src str {
         self.source
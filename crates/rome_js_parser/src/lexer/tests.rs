@@ -1370,3 +1370,40 @@ fn keywords() {
         assert_eq!(lexer.next_token(LexContext::default()), EOF);
     }
 }
+
+#[test]
+fn lex_from_offset() {
+    let source = "let a = 'b';\n/* c */ foo(a, 1)";
+
+    let mut lexer = Lexer::from_str(source, 0);
+    let mut tokens = vec![];
+
+    while lexer.next_token(LexContext::default()) != EOF {
+        tokens.push((
+            lexer.current(),
+            lexer.current_range(),
+            lexer.has_preceding_line_break(),
+        ));
+    }
+
+    // Re-lex from the comment, which follows a line break
+    let start = TextSize::from(13);
+    let mut lexer = Lexer::from_str_at(source, 0, start, true);
+    let mut relexed = vec![];
+
+    while lexer.next_token(LexContext::default()) != EOF {
+        relexed.push((
+            lexer.current(),
+            lexer.current_range(),
+            lexer.has_preceding_line_break(),
+        ));
+    }
+
+    let expected: Vec<_> = tokens
+        .into_iter()
+        .filter(|(_, range, _)| range.start() >= start)
+        .collect();
+
+    assert_eq!(relexed[0].0, JsSyntaxKind::COMMENT);
+    assert_eq!(relexed, expected);
+}
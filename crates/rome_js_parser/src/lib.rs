@@ -63,7 +63,9 @@ mod event;
 mod lexer;
 mod lossless_tree_sink;
 mod parse;
+mod reparse;
 mod state;
+mod stats_tree_sink;
 
 #[cfg(test)]
 mod tests;
@@ -76,6 +78,8 @@ pub use crate::{
     lexer::{LexContext, ReLexContext},
     lossless_tree_sink::LosslessTreeSink,
     parse::*,
+    reparse::reparse_token,
+    stats_tree_sink::{GreenReuseStats, StatsTreeSink},
     token_set::TokenSet,
 };
 pub(crate) use parser::{Checkpoint, CompletedMarker, Marker, ParseRecovery, Parser};
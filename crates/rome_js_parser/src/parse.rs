@@ -176,6 +176,9 @@ pub fn parse_module(text: &str, file_id: usize) -> Parse<JsModule> {
 }
 
 /// Parses the provided string as a EcmaScript program using the provided syntax features.
+///
+/// Parsing never fails: any input, including input that isn't valid for `source_type`,
+/// results in a lossless tree with the syntax errors reported in the diagnostics of the [Parse].
 pub fn parse(text: &str, file_id: usize, source_type: SourceType) -> Parse<JsAnyRoot> {
     tracing::debug_span!("parse", file_id = file_id).in_scope(move || {
         let (events, errors, tokens) = parse_common(text, file_id, source_type);
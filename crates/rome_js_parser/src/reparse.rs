@@ -0,0 +1,143 @@
+//! Incremental reparsing of an edited document.
+
+use crate::lexer::{LexContext, Lexer};
+use rome_js_syntax::{JsLanguage, JsSyntaxKind, JsSyntaxNode, JsSyntaxToken, TextRange, TextSize};
+use rome_rowan::{SyntaxTriviaPiece, TriviaPiece};
+
+/// Tries to update the tree of `root` for an edit replacing the text in `range` with `replacement`
+/// without reparsing the whole document.
+///
+/// An edit can be applied incrementally if it's inside of a single string literal and the edited
+/// text remains a valid string literal: the token gets re-lexed and replaced in the tree.
+/// String literals of directives (`"use strict"`) and import assertion keys are excluded because
+/// their content changes how the parser handles the rest of the document.
+///
+/// Returns `None` if the document must be fully reparsed instead. `root` must be the root of a
+/// tree without parse errors.
+///
+/// ```
+/// use rome_js_parser::{parse_module, reparse_token};
+/// use rome_js_syntax::{TextRange, TextSize};
+///
+/// let root = parse_module("let a = \"abc\";", 0).syntax();
+///
+/// // Replace `b` with `xyz`
+/// let range = TextRange::new(TextSize::from(10), TextSize::from(11));
+/// let reparsed = reparse_token(&root, range, "xyz").unwrap();
+///
+/// assert_eq!(reparsed.text().to_string(), "let a = \"axyzc\";");
+///
+/// // The edit introduces a new token
+/// let range = TextRange::new(TextSize::from(13), TextSize::from(13));
+/// assert!(reparse_token(&root, range, " + 1").is_none());
+/// ```
+pub fn reparse_token(
+    root: &JsSyntaxNode,
+    range: TextRange,
+    replacement: &str,
+) -> Option<JsSyntaxNode> {
+    let token = root
+        .token_at_offset(range.start())
+        .find(|token| token.text_trimmed_range().contains_range(range))?;
+
+    if token.kind() != JsSyntaxKind::JS_STRING_LITERAL {
+        return None;
+    }
+
+    if matches!(
+        token.parent()?.kind(),
+        JsSyntaxKind::JS_DIRECTIVE | JsSyntaxKind::JS_IMPORT_ASSERTION_ENTRY
+    ) {
+        return None;
+    }
+
+    let relative_range = range - token.text_trimmed_range().start();
+    let trimmed = token.text_trimmed();
+
+    let mut text_trimmed = String::from(&trimmed[..usize::from(relative_range.start())]);
+    text_trimmed.push_str(replacement);
+    text_trimmed.push_str(&trimmed[usize::from(relative_range.end())..]);
+
+    let mut lexer = Lexer::from_str(&text_trimmed, 0);
+    let kind = lexer.next_token(LexContext::Regular);
+
+    if kind != JsSyntaxKind::JS_STRING_LITERAL
+        || lexer.current_range().end() != TextSize::of(&text_trimmed)
+        || !lexer.finish().is_empty()
+    {
+        return None;
+    }
+
+    let leading: Vec<_> = token.leading_trivia().pieces().collect();
+    let trailing: Vec<_> = token.trailing_trivia().pieces().collect();
+
+    let mut text = String::new();
+    for piece in &leading {
+        text.push_str(piece.text());
+    }
+    text.push_str(&text_trimmed);
+    for piece in &trailing {
+        text.push_str(piece.text());
+    }
+
+    fn to_trivia_piece(piece: &SyntaxTriviaPiece<JsLanguage>) -> TriviaPiece {
+        TriviaPiece::new(piece.kind(), piece.text_len())
+    }
+
+    let new_token = JsSyntaxToken::new_detached(
+        kind,
+        &text,
+        leading.iter().map(to_trivia_piece),
+        trailing.iter().map(to_trivia_piece),
+    );
+
+    root.clone().replace_child(token.into(), new_token.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reparse_token;
+    use crate::parse_module;
+    use rome_js_syntax::{TextRange, TextSize};
+
+    #[track_caller]
+    fn assert_reparse(source: &str, range: TextRange, replacement: &str) {
+        let root = parse_module(source, 0).syntax();
+        let reparsed = reparse_token(&root, range, replacement).expect("incremental reparse");
+
+        let mut expected = String::from(source);
+        expected.replace_range(std::ops::Range::<usize>::from(range), replacement);
+
+        let parsed = parse_module(&expected, 0);
+        assert!(!parsed.has_errors());
+        assert_eq!(
+            format!("{:#?}", reparsed),
+            format!("{:#?}", parsed.syntax())
+        );
+    }
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(TextSize::from(start), TextSize::from(end))
+    }
+
+    #[test]
+    fn reparses_string_literal() {
+        assert_reparse("a = 'b' /* c */;\nd;", range(5, 6), "xyz");
+        assert_reparse("a = 'b';", range(5, 5), "\\u0041");
+        assert_reparse("import x from \"./y\";", range(17, 18), "z");
+    }
+
+    #[test]
+    fn falls_back_to_full_parse() {
+        let root = parse_module("\"use strict\";\na = 'b';\nc;", 0).syntax();
+
+        // Directive
+        assert!(reparse_token(&root, range(1, 4), "USE").is_none());
+        // Closes the string
+        assert!(reparse_token(&root, range(20, 20), "' + '").is_none());
+        // Identifier
+        assert!(reparse_token(&root, range(14, 15), "x").is_none());
+        // Spans multiple tokens
+        assert!(reparse_token(&root, range(18, 23), "'").is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::token_source::Trivia;
+use crate::{LosslessTreeSink, ParseDiagnostic, TreeSink};
+use rome_js_syntax::{JsSyntaxKind, JsSyntaxNode, TextSize};
+use rome_rowan::{Direction, NodeOrToken};
+use std::collections::HashSet;
+
+/// Number of green nodes and tokens in a tree, split between the elements that were newly
+/// allocated and the elements reused from the node cache of the tree builder.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct GreenReuseStats {
+    /// Number of distinct green nodes allocated for the tree
+    pub allocated_nodes: usize,
+    /// Number of nodes in the tree sharing their green node with another node
+    pub reused_nodes: usize,
+    /// Number of distinct green tokens allocated for the tree
+    pub allocated_tokens: usize,
+    /// Number of tokens in the tree sharing their green token with another token
+    pub reused_tokens: usize,
+}
+
+impl GreenReuseStats {
+    /// Total number of nodes in the tree
+    pub fn nodes(&self) -> usize {
+        self.allocated_nodes + self.reused_nodes
+    }
+
+    /// Total number of tokens in the tree
+    pub fn tokens(&self) -> usize {
+        self.allocated_tokens + self.reused_tokens
+    }
+}
+
+/// [TreeSink] wrapping a [LosslessTreeSink] that records how many green nodes and tokens of the
+/// resulting tree are shared instead of being allocated for every occurrence.
+///
+/// This is meant to measure the benefits of structural sharing when tuning the reparse heuristics.
+#[derive(Debug)]
+pub struct StatsTreeSink<'a> {
+    inner: LosslessTreeSink<'a>,
+}
+
+impl<'a> StatsTreeSink<'a> {
+    pub fn new(text: &'a str, trivia: &'a [Trivia]) -> Self {
+        Self {
+            inner: LosslessTreeSink::new(text, trivia),
+        }
+    }
+
+    /// Finishes the tree and returns the root node with possible parser errors, and the reuse
+    /// statistics of the green elements of the tree.
+    pub fn finish(self) -> (JsSyntaxNode, Vec<ParseDiagnostic>, GreenReuseStats) {
+        let (root, errors) = self.inner.finish();
+
+        let mut stats = GreenReuseStats::default();
+        let mut seen = HashSet::new();
+
+        for element in root.descendants_with_tokens(Direction::Next) {
+            match element {
+                NodeOrToken::Node(node) => {
+                    if seen.insert(node.key().0) {
+                        stats.allocated_nodes += 1;
+                    } else {
+                        stats.reused_nodes += 1;
+                    }
+                }
+                NodeOrToken::Token(token) => {
+                    if seen.insert(token.key().0) {
+                        stats.allocated_tokens += 1;
+                    } else {
+                        stats.reused_tokens += 1;
+                    }
+                }
+            }
+        }
+
+        (root, errors, stats)
+    }
+}
+
+impl<'a> TreeSink for StatsTreeSink<'a> {
+    fn token(&mut self, kind: JsSyntaxKind, end: TextSize) {
+        self.inner.token(kind, end);
+    }
+
+    fn start_node(&mut self, kind: JsSyntaxKind) {
+        self.inner.start_node(kind);
+    }
+
+    fn finish_node(&mut self) {
+        self.inner.finish_node();
+    }
+
+    fn errors(&mut self, errors: Vec<ParseDiagnostic>) {
+        self.inner.errors(errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatsTreeSink;
+    use crate::parse_common;
+    use rome_js_syntax::SourceType;
+    use rome_rowan::Direction;
+
+    #[test]
+    fn records_green_reuse() {
+        let text = "a;\na;\na;\n";
+
+        let (events, errors, trivia) = parse_common(text, 0, SourceType::js_module());
+        let mut sink = StatsTreeSink::new(text, &trivia);
+        crate::process(&mut sink, events, errors);
+        let (root, errors, stats) = sink.finish();
+
+        assert!(errors.is_empty());
+        assert_eq!(root.text().to_string(), text);
+
+        assert_eq!(stats.nodes(), root.descendants().count());
+        assert_eq!(
+            stats.tokens(),
+            root.descendants_tokens(Direction::Next).count()
+        );
+
+        // The last two statements share their green node
+        assert!(stats.allocated_nodes > 0);
+        assert!(stats.reused_nodes > 0);
+        assert!(stats.allocated_tokens > 0);
+        assert!(stats.reused_tokens > 0);
+    }
+}
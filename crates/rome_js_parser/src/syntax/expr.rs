@@ -1475,10 +1475,23 @@ fn parse_template_literal(
     // test_err template_literal_unterminated
     // let a = `${foo} bar
 
-    // The lexer emits an error for unterminated template literals
-    p.eat(BACKTICK);
+    // The lexer emits an error for unterminated template literals ending with a chunk
+    let ends_with_chunk = p.last() == Some(TEMPLATE_CHUNK);
+    let terminated = p.eat(BACKTICK);
     let mut completed = marker.complete(p, JS_TEMPLATE);
 
+    // test_err template_literal_unterminated_substitution
+    // let a = `${
+
+    // A template ending with a substitution, for example `${foo}` or `${`, isn't reported by the
+    // lexer because it stops lexing the template before reaching the end of the file
+    if !terminated && !ends_with_chunk {
+        p.error(
+            p.err_builder("unterminated template literal")
+                .primary(completed.range(p), ""),
+        );
+    }
+
     // test_err template_after_optional_chain
     // obj.val?.prop`template`
     // obj.val?.[expr]`template`
@@ -724,15 +724,21 @@ impl ParseSeparatedList for ExportNamedSpecifierList {
     }
 
     fn recover(&mut self, p: &mut Parser, parsed_element: ParsedSyntax) -> RecoveryResult {
-        parsed_element.or_recover(
-            p,
-            &ParseRecovery::new(
-                JS_UNKNOWN,
-                STMT_RECOVERY_SET.union(token_set![T![,], T!['}'], T![;]]),
-            )
-            .enable_recovery_on_line_break(),
-            expected_export_name_specifier,
-        )
+        let recovery = ParseRecovery::new(
+            JS_UNKNOWN,
+            STMT_RECOVERY_SET.union(token_set![T![,], T!['}'], T![;]]),
+        );
+
+        // String names are only valid in an `export ... from` clause. Recover over them even if
+        // they're on a new line, so that the clause gets re-parsed as an `export ... from` clause
+        // if it's followed by `from`.
+        let recovery = if p.at(JS_STRING_LITERAL) {
+            recovery
+        } else {
+            recovery.enable_recovery_on_line_break()
+        };
+
+        parsed_element.or_recover(p, &recovery, expected_export_name_specifier)
     }
 
     fn list_kind() -> JsSyntaxKind {
@@ -935,6 +941,12 @@ fn parse_export_from_clause(p: &mut Parser) -> ParsedSyntax {
 // export { "a" as b } from "mod";
 // export { a } from "mod" assert { type: "json" }
 //
+// test export_named_from_clause_multiline
+// export {
+//   "a",
+//   "b" as "c",
+// } from "mod";
+//
 // test_err export_named_from_clause_err
 // export { as b } from "mod";
 // export { a as 5 } from "mod";
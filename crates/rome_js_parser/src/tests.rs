@@ -1,11 +1,12 @@
 use crate::{parse, parse_module, Parse};
 use expect_test::expect_file;
+use quickcheck_macros::quickcheck;
 use rome_diagnostics::file::SimpleFile;
 use rome_diagnostics::termcolor::Buffer;
 use rome_diagnostics::{file::SimpleFiles, Emitter};
 use rome_js_syntax::{JsAnyRoot, JsLanguage, JsSyntaxKind, SourceType};
 use rome_js_syntax::{JsCallArguments, JsLogicalExpression, JsSyntaxNode, JsSyntaxToken};
-use rome_rowan::{AstNode, Direction, SyntaxKind, TextSize};
+use rome_rowan::{AstNode, AstSeparatedList, Direction, SyntaxKind, TextSize};
 use std::fmt::{Debug, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
@@ -396,3 +397,47 @@ pub fn node_has_comments() {
 
     assert!(right.syntax().has_comments_direct());
 }
+
+#[test]
+pub fn node_has_inner_comments() {
+    fn call_argument_list(text: &str) -> JsSyntaxNode {
+        let root = parse_module(text, 0);
+        let arguments = root
+            .syntax()
+            .descendants()
+            .find_map(JsCallArguments::cast)
+            .unwrap();
+
+        arguments.args().syntax_list().node().clone()
+    }
+
+    let between = call_argument_list("f(a, /* comment */ b)");
+    assert!(between.has_inner_comments());
+    assert!(!between.first_or_last_token_have_comments());
+
+    let leading = call_argument_list(
+        r"f(
+// comment
+a, b)",
+    );
+    assert!(!leading.has_inner_comments());
+    assert!(leading.first_or_last_token_have_comments());
+}
+
+// Parses random input with every source type and checks that the parser doesn't panic and
+// that the resulting tree contains the whole input
+#[quickcheck]
+fn parse_arbitrary_input(bytes: Vec<u8>) -> bool {
+    let text = String::from_utf8_lossy(&bytes);
+
+    [
+        SourceType::js_script(),
+        SourceType::js_module(),
+        SourceType::jsx(),
+        SourceType::ts(),
+        SourceType::tsx(),
+        SourceType::d_ts(),
+    ]
+    .into_iter()
+    .all(|source_type| parse(&text, 0, source_type).syntax().to_string() == text)
+}
@@ -830,4 +830,72 @@ mod test {
         assert!(b_declaration.all_reads().all(|r| r.is_read()));
         assert!(b_declaration.all_writes().all(|r| r.is_write()));
     }
+
+    #[test]
+    pub fn ok_semantic_model_nested_functions() {
+        let r = rome_js_parser::parse(
+            "function outer() { let a = 1; function inner() { let a = 2; return a; } return a + inner(); }",
+            0,
+            SourceType::js_module(),
+        );
+        let model = semantic_model(&r.tree());
+
+        let bindings = r
+            .syntax()
+            .descendants()
+            .filter_map(|x| x.cast::<JsIdentifierBinding>())
+            .filter(|x| x.text() == "a")
+            .collect::<Vec<_>>();
+
+        let (outer_a, inner_a) = match bindings.as_slice() {
+            [outer_a, inner_a] => (outer_a, inner_a),
+            _ => panic!("wrong number of bindings"),
+        };
+
+        let references = r
+            .syntax()
+            .descendants()
+            .filter_map(|x| x.cast::<JsReferenceIdentifier>())
+            .filter(|x| x.text() == "a")
+            .collect::<Vec<_>>();
+
+        let (inner_read, outer_read) = match references.as_slice() {
+            [inner_read, outer_read] => (inner_read, outer_read),
+            _ => panic!("wrong number of references"),
+        };
+
+        // Binding resolution
+
+        let declaration = inner_read.declaration(&model).unwrap();
+        assert_eq!(declaration.syntax(), inner_a.syntax());
+
+        let declaration = outer_read.declaration(&model).unwrap();
+        assert_eq!(declaration.syntax(), outer_a.syntax());
+
+        // Bindings of the inner scope
+
+        let inner_scope = inner_a.scope(&model);
+        let bindings = inner_scope.bindings().collect::<Vec<_>>();
+        match bindings.as_slice() {
+            [a] => assert_eq!(a.syntax(), inner_a.syntax()),
+            _ => panic!("wrong number of bindings"),
+        }
+
+        // Shadowing
+
+        // The inner `a` shadows the `a` of the outer function
+        let shadowed = inner_scope
+            .ancestors()
+            .skip(1)
+            .find_map(|scope| scope.get_binding("a"))
+            .unwrap();
+        assert_eq!(shadowed.syntax(), outer_a.syntax());
+
+        // The outer `a` doesn't shadow any binding
+        assert!(outer_a
+            .scope(&model)
+            .ancestors()
+            .skip(1)
+            .all(|scope| scope.get_binding("a").is_none()));
+    }
 }
@@ -1,14 +1,15 @@
 //! Extensions for things which are not easily generated in ast expr nodes
 use crate::numbers::parse_js_number;
 use crate::{
-    JsAnyExpression, JsAnyLiteralExpression, JsArrayExpression, JsArrayHole,
+    JsAnyExpression, JsAnyLiteralExpression, JsAnyName, JsArrayExpression, JsArrayHole,
     JsAssignmentExpression, JsBinaryExpression, JsLiteralMemberName, JsLogicalExpression,
     JsNumberLiteralExpression, JsObjectExpression, JsRegexLiteralExpression,
     JsStringLiteralExpression, JsSyntaxToken, JsTemplate, JsUnaryExpression, T,
 };
 use crate::{JsPreUpdateExpression, JsSyntaxKind::*};
 use rome_rowan::{
-    AstNode, AstSeparatedList, NodeOrToken, SyntaxNodeText, SyntaxResult, TextRange, TextSize,
+    AstNode, AstSeparatedList, NodeOrToken, SyntaxNodeText, SyntaxResult, SyntaxTokenText,
+    TextRange, TextSize,
 };
 use std::cmp::Ordering;
 
@@ -457,3 +458,109 @@ impl JsRegexLiteralExpression {
         Ok(String::from(&text_trimmed[1..end_slash_pos]))
     }
 }
+
+/// Returns the chain of names of `callee` if it's the callee of a call usually made to a test
+/// framework, like Mocha or Jest. For example, the chain of `test.describe.only` is
+/// `["test", "describe", "only"]`.
+///
+/// The callee must be one of the following:
+/// - `it`, `it.only`, `it.skip`
+/// - `describe`, `describe.only`, `describe.skip`
+/// - `test`, `test.only`, `test.skip`, `test.step`
+/// - `test.describe`, `test.describe.only`
+/// - `test.describe.parallel`, `test.describe.parallel.only`
+/// - `test.describe.serial`, `test.describe.serial.only`
+/// - `skip`, `xit`, `xdescribe`, `xtest`, `fit`, `fdescribe`, `ftest`
+///
+/// Each name of `test_call_identifiers` is matched like `it`: on its own, or followed by
+/// `only` or `skip`.
+///
+/// Based on this [article]
+///
+/// [article]: https://craftinginterpreters.com/scanning-on-demand.html#tries-and-state-machines
+pub fn matches_test_call(
+    callee: &JsAnyExpression,
+    test_call_identifiers: &[String],
+) -> SyntaxResult<Option<Vec<SyntaxTokenText>>> {
+    let members = test_call_member_chain(callee)?;
+
+    let first = members.get(0).map(|t| t.text());
+    let second = members.get(1).map(|t| t.text());
+    let third = members.get(2).map(|t| t.text());
+    let fourth = members.get(3).map(|t| t.text());
+    let fifth = members.get(4).map(|t| t.text());
+
+    let is_custom_test_call = first.map_or(false, |first| {
+        test_call_identifiers.iter().any(|name| name == first)
+    });
+
+    let matches = if is_custom_test_call {
+        match second {
+            None => true,
+            Some("only" | "skip") => third.is_none(),
+            _ => false,
+        }
+    } else {
+        match first {
+            Some("it" | "describe") => match second {
+                None => true,
+                Some("only" | "skip") => third.is_none(),
+                _ => false,
+            },
+            Some("test") => match second {
+                None => true,
+                Some("only" | "skip" | "step") => third.is_none(),
+                Some("describe") => match third {
+                    None => true,
+                    Some("only") => true,
+                    Some("parallel" | "serial") => match fourth {
+                        None => true,
+                        Some("only") => fifth.is_none(),
+                        _ => false,
+                    },
+                    _ => false,
+                },
+                _ => false,
+            },
+            Some("skip" | "xit" | "xdescribe" | "xtest" | "fit" | "fdescribe" | "ftest") => true,
+            _ => false,
+        }
+    };
+
+    if matches {
+        Ok(Some(members))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the names of the identifier and static members that make up `callee`, starting with
+/// the identifier. Stops at the first expression that is neither an identifier nor a static
+/// member expression.
+fn test_call_member_chain(callee: &JsAnyExpression) -> SyntaxResult<Vec<SyntaxTokenText>> {
+    // this the max depth plus one, because we want to catch cases where we have test.only.WRONG
+    const MAX_DEPTH: u8 = 5;
+    let mut test_call = Vec::with_capacity(MAX_DEPTH as usize);
+    let mut current_node = callee.clone();
+    for _ in 0..MAX_DEPTH {
+        if let JsAnyExpression::JsIdentifierExpression(identifier) = &current_node {
+            let value_token = identifier.name()?.value_token()?;
+            let value = value_token.token_text_trimmed();
+            test_call.push(value);
+            break;
+        } else if let JsAnyExpression::JsStaticMemberExpression(member_expression) = &current_node {
+            match member_expression.member()? {
+                JsAnyName::JsName(name) => {
+                    let value = name.value_token()?;
+                    test_call.push(value.token_text_trimmed());
+                    current_node = member_expression.object()?;
+                }
+                _ => break,
+            };
+        } else {
+            break;
+        }
+    }
+    test_call.reverse();
+    Ok(test_call)
+}
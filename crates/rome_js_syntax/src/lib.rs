@@ -241,6 +241,11 @@ impl rome_rowan::SyntaxKind for JsSyntaxKind {
     fn from_raw(raw: RawSyntaxKind) -> Self {
         Self::from(raw.0)
     }
+
+    #[inline]
+    fn is_valid_raw(raw: RawSyntaxKind) -> bool {
+        raw.0 <= JsSyntaxKind::__LAST as u16
+    }
 }
 
 impl TryFrom<JsSyntaxKind> for TriviaPieceKind {
@@ -186,6 +186,95 @@ impl SourceType {
     pub fn is_module(&self) -> bool {
         self.module_kind.is_module()
     }
+
+    /// Returns `true` if `source` appears to contain JSX.
+    ///
+    /// This is a heuristic for files whose extension doesn't tell if they use JSX: it looks for
+    /// a `<` directly followed by an identifier or a `>` (a fragment) in a position where an
+    /// expression starts, for example after a `(`, a `=` or the `return` keyword. A `<` following
+    /// an identifier or a literal is a comparison and is ignored, as well as a `<` inside of a
+    /// string, a template literal or a comment.
+    ///
+    /// ```
+    /// use rome_js_syntax::SourceType;
+    ///
+    /// assert!(SourceType::infer_jsx("const a = <div />;"));
+    /// assert!(!SourceType::infer_jsx("if (a <b) {}"));
+    /// ```
+    pub fn infer_jsx(source: &str) -> bool {
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((index, c)) = chars.next() {
+            match c {
+                // Skip strings and template literals, a `<` inside of them isn't a tag
+                '\'' | '"' | '`' => {
+                    while let Some((_, next)) = chars.next() {
+                        match next {
+                            '\\' => {
+                                chars.next();
+                            }
+                            // Unterminated string, resume scanning on the next line
+                            '\n' if c != '`' => break,
+                            next if next == c => break,
+                            _ => {}
+                        }
+                    }
+                }
+                '/' => match chars.peek() {
+                    Some((_, '/')) => {
+                        for (_, next) in chars.by_ref() {
+                            if next == '\n' {
+                                break;
+                            }
+                        }
+                    }
+                    Some((_, '*')) => {
+                        chars.next();
+                        let mut previous = None;
+                        for (_, next) in chars.by_ref() {
+                            if previous == Some('*') && next == '/' {
+                                break;
+                            }
+                            previous = Some(next);
+                        }
+                    }
+                    _ => {}
+                },
+                '<' if is_tag_start(source, index) => return true,
+                _ => {}
+            }
+        }
+
+        false
+    }
+}
+
+/// Returns `true` if the `<` at `index` is followed by a tag name or a `>` and is in a position
+/// where an expression starts
+fn is_tag_start(source: &str, index: usize) -> bool {
+    let after = &source[index + 1..];
+    let starts_tag = match after.chars().next() {
+        Some(c) => c == '>' || c == '_' || c == '$' || c.is_alphabetic(),
+        None => false,
+    };
+
+    if !starts_tag {
+        return false;
+    }
+
+    let before = source[..index].trim_end();
+
+    match before.chars().last() {
+        None => true,
+        Some('(' | '[' | '{' | ',' | ';' | '=' | ':' | '?' | '!' | '&' | '|' | '>' | '}') => true,
+        Some(_) => before
+            .strip_suffix("return")
+            .map_or(false, |rest| !rest.ends_with(is_identifier_char)),
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c == '_' || c == '$' || c.is_alphanumeric()
 }
 
 impl TryFrom<&Path> for SourceType {
@@ -248,3 +337,85 @@ fn compute_source_type_from_path_or_extension(
     };
     Ok(source_type)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LanguageVariant::{Jsx, Standard};
+    use super::ModuleKind::{Module, Script};
+    use super::{Language, LanguageVariant, ModuleKind, SourceType};
+    use std::path::Path;
+
+    fn source_type(file_name: &str) -> SourceType {
+        SourceType::try_from(Path::new(file_name)).unwrap()
+    }
+
+    #[track_caller]
+    fn assert_source_type(
+        file_name: &str,
+        language: Language,
+        module_kind: ModuleKind,
+        variant: LanguageVariant,
+    ) {
+        let source_type = source_type(file_name);
+
+        assert_eq!(source_type.language(), language, "{file_name}");
+        assert_eq!(source_type.module_kind(), module_kind, "{file_name}");
+        assert_eq!(source_type.variant(), variant, "{file_name}");
+    }
+
+    #[test]
+    fn javascript_extensions() {
+        let js = Language::JavaScript;
+
+        assert_source_type("a.js", js, Module, Jsx);
+        assert_source_type("a.jsx", js, Module, Jsx);
+        assert_source_type("a.mjs", js, Module, Jsx);
+        assert_source_type("a.cjs", js, Script, Standard);
+    }
+
+    #[test]
+    fn typescript_extensions() {
+        let ts = Language::TypeScript {
+            definition_file: false,
+        };
+        let d_ts = Language::TypeScript {
+            definition_file: true,
+        };
+
+        assert_source_type("a.ts", ts, Module, Standard);
+        assert_source_type("a.tsx", ts, Module, Jsx);
+        assert_source_type("a.mts", ts, Module, Standard);
+        assert_source_type("a.cts", ts, Script, Standard);
+        assert_source_type("a.d.ts", d_ts, Module, Standard);
+        assert_source_type("a.d.mts", d_ts, Module, Standard);
+        assert_source_type("a.d.cts", d_ts, Script, Standard);
+    }
+
+    #[test]
+    fn infer_jsx() {
+        assert!(SourceType::infer_jsx(
+            "import React from 'react';\n\nexport default function App() {\n\treturn <App.Root title=\"a\" />;\n}\n"
+        ));
+        assert!(SourceType::infer_jsx("render(<>\n\t<b />\n</>);"));
+        assert!(SourceType::infer_jsx("const a = () => <div />;"));
+        assert!(SourceType::infer_jsx(
+            "const message = \"it's \\\"<b>\\\"\"; // <b>\nconst a = <b />;"
+        ));
+
+        assert!(!SourceType::infer_jsx(
+            "for (let i = 0; i <length; i++) {\n\tif (a<b || c < d) {}\n}\n"
+        ));
+        assert!(!SourceType::infer_jsx("const message = \"a <b> c\";"));
+        assert!(!SourceType::infer_jsx("const message = \"<b>\" + '<b>';"));
+        assert!(!SourceType::infer_jsx("const message = `${a}<b>`;"));
+        assert!(!SourceType::infer_jsx(
+            "// returns <div>\n/* (<div>) */\nf();"
+        ));
+        assert!(!SourceType::infer_jsx("const a = 1 << 2;"));
+    }
+
+    #[test]
+    fn unknown_extension() {
+        assert!(SourceType::try_from(Path::new("a.json")).is_err());
+    }
+}
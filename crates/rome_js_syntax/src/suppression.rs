@@ -26,6 +26,16 @@ pub struct Suppression<'a> {
     pub reason: &'a str,
 }
 
+impl Suppression<'_> {
+    /// Returns `true` if the suppression comment doesn't specify a reason
+    ///
+    /// The suppression is still active, but the comment fails to document why
+    /// the feature is being disabled and should be reported to the user
+    pub fn is_missing_reason(&self) -> bool {
+        self.reason.is_empty()
+    }
+}
+
 pub fn parse_suppression_comment(comment: &str) -> impl Iterator<Item = Suppression> {
     let (head, mut comment) = comment.split_at(2);
     let is_block_comment = match head {
@@ -254,4 +264,49 @@ mod tests {
             }],
         );
     }
+
+    #[test]
+    fn parse_suppression_without_reason() {
+        let suppressions: Vec<_> = parse_suppression_comment("// rome-ignore lint:").collect();
+
+        assert_eq!(
+            suppressions,
+            vec![Suppression {
+                categories: vec![("lint", None)],
+                reason: ""
+            }],
+        );
+        assert!(suppressions[0].is_missing_reason());
+
+        let suppressions: Vec<_> =
+            parse_suppression_comment("/* rome-ignore format:   */").collect();
+        assert!(suppressions[0].is_missing_reason());
+
+        let suppressions: Vec<_> =
+            parse_suppression_comment("// rome-ignore lint: false positive").collect();
+        assert!(!suppressions[0].is_missing_reason());
+    }
+
+    #[test]
+    fn parse_suppressions_on_multiple_lines() {
+        assert_eq!(
+            parse_suppression_comment(
+                "/*
+                  * rome-ignore format: explanation1
+                  * rome-ignore lint(js/noDebugger): explanation2
+                  */"
+            )
+            .collect::<Vec<_>>(),
+            vec![
+                Suppression {
+                    categories: vec![("format", None)],
+                    reason: "explanation1"
+                },
+                Suppression {
+                    categories: vec![("lint", Some("js/noDebugger"))],
+                    reason: "explanation2"
+                }
+            ],
+        );
+    }
 }
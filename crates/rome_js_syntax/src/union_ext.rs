@@ -1,8 +1,8 @@
 use crate::{
     JsAnyArrowFunctionParameters, JsAnyBinding, JsAnyClass, JsAnyFunction, JsAnyFunctionBody,
-    JsClassMemberList, JsExtendsClause, JsSyntaxToken, TsAnyPropertyAnnotation,
-    TsAnyVariableAnnotation, TsImplementsClause, TsReturnTypeAnnotation, TsTypeAnnotation,
-    TsTypeParameters,
+    JsAnyImportClause, JsClassMemberList, JsExtendsClause, JsModuleSource, JsSyntaxToken,
+    TsAnyPropertyAnnotation, TsAnyVariableAnnotation, TsImplementsClause, TsReturnTypeAnnotation,
+    TsTypeAnnotation, TsTypeParameters,
 };
 use rome_rowan::{AstSeparatedList, SyntaxResult};
 
@@ -192,6 +192,17 @@ impl JsAnyFunction {
     }
 }
 
+impl JsAnyImportClause {
+    pub fn source(&self) -> SyntaxResult<JsModuleSource> {
+        match self {
+            JsAnyImportClause::JsImportBareClause(clause) => clause.source(),
+            JsAnyImportClause::JsImportDefaultClause(clause) => clause.source(),
+            JsAnyImportClause::JsImportNamedClause(clause) => clause.source(),
+            JsAnyImportClause::JsImportNamespaceClause(clause) => clause.source(),
+        }
+    }
+}
+
 impl TsAnyVariableAnnotation {
     pub fn type_annotation(&self) -> SyntaxResult<Option<TsTypeAnnotation>> {
         match self {
@@ -0,0 +1,312 @@
+//! Rome's official JSON formatter.
+//!
+//! The formatter reuses the [rome_formatter] IR: objects and arrays are wrapped in a group with
+//! their content inside of a [soft_block_indent], so that they remain on a single line if they
+//! fit and get expanded with one member (or element) per line otherwise.
+//!
+//! The JavaScript-only options of the formatter don't apply to JSON. Most notably, the bracket
+//! spacing option is ignored: objects are always printed without spaces inside their braces
+//! (`{"a": 1}`), the same as arrays.
+
+pub mod context;
+
+use crate::context::JsonFormatContext;
+use rome_formatter::prelude::*;
+use rome_formatter::token::FormatInserted;
+use rome_formatter::{format_args, write, FormatRefWithRule, Formatted};
+use rome_json_syntax::{
+    JsonArray, JsonLanguage, JsonMember, JsonObject, JsonRoot, JsonSyntaxKind, JsonSyntaxNode,
+    JsonSyntaxToken,
+};
+use rome_rowan::{AstNode, AstSeparatedList, AstSeparatedListElementsIterator, NodeOrToken};
+
+pub(crate) type JsonFormatter<'buf> = Formatter<'buf, JsonFormatContext>;
+
+/// Used to get an object that knows how to format this object.
+pub trait AsFormat<'a> {
+    type Format: Format<JsonFormatContext>;
+
+    /// Returns an object that is able to format this object.
+    fn format(&'a self) -> Self::Format;
+}
+
+/// Format implementation specific to JSON tokens.
+pub struct FormatJsonSyntaxToken;
+
+impl FormatRule<JsonSyntaxToken> for FormatJsonSyntaxToken {
+    type Context = JsonFormatContext;
+
+    fn fmt(&self, token: &JsonSyntaxToken, f: &mut JsonFormatter) -> FormatResult<()> {
+        f.state_mut().track_token(token);
+
+        write!(
+            f,
+            [
+                format_leading_trivia(token),
+                format_trimmed_token(token),
+                format_trailing_trivia(token),
+            ]
+        )
+    }
+}
+
+impl<'a> AsFormat<'a> for JsonSyntaxToken {
+    type Format = FormatRefWithRule<'a, JsonSyntaxToken, FormatJsonSyntaxToken>;
+
+    fn format(&'a self) -> Self::Format {
+        FormatRefWithRule::new(self, FormatJsonSyntaxToken)
+    }
+}
+
+/// Format implementation for any JSON node, dispatching on the kind of the node.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FormatJsonSyntaxNode;
+
+impl FormatRule<JsonSyntaxNode> for FormatJsonSyntaxNode {
+    type Context = JsonFormatContext;
+
+    fn fmt(&self, node: &JsonSyntaxNode, f: &mut JsonFormatter) -> FormatResult<()> {
+        match node.kind() {
+            JsonSyntaxKind::JSON_ROOT => format_root(&JsonRoot::unwrap_cast(node.clone()), f),
+            JsonSyntaxKind::JSON_OBJECT => format_object(&JsonObject::unwrap_cast(node.clone()), f),
+            JsonSyntaxKind::JSON_ARRAY => format_array(&JsonArray::unwrap_cast(node.clone()), f),
+            JsonSyntaxKind::JSON_MEMBER => format_member(&JsonMember::unwrap_cast(node.clone()), f),
+            // Literals and unknown nodes are printed as they are in the source
+            _ => {
+                for element in node.children_with_tokens() {
+                    match element {
+                        NodeOrToken::Node(node) => write!(f, [node.format()])?,
+                        NodeOrToken::Token(token) => write!(f, [token.format()])?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> AsFormat<'a> for JsonSyntaxNode {
+    type Format = FormatRefWithRule<'a, JsonSyntaxNode, FormatJsonSyntaxNode>;
+
+    fn format(&'a self) -> Self::Format {
+        FormatRefWithRule::new(self, FormatJsonSyntaxNode)
+    }
+}
+
+fn format_root(node: &JsonRoot, f: &mut JsonFormatter) -> FormatResult<()> {
+    let value = node.json_value()?;
+
+    write!(f, [value.syntax().format(), hard_line_break()])
+}
+
+fn format_object(node: &JsonObject, f: &mut JsonFormatter) -> FormatResult<()> {
+    let l_curly_token = node.l_curly_token()?;
+    let members = node.json_member_list();
+    let r_curly_token = node.r_curly_token()?;
+
+    if members.is_empty() {
+        write!(f, [l_curly_token.format(), r_curly_token.format()])
+    } else {
+        write!(
+            f,
+            [group_elements(&format_args![
+                l_curly_token.format(),
+                soft_block_indent(&format_with(|f: &mut JsonFormatter| format_separated(
+                    members.elements(),
+                    f
+                ))),
+                r_curly_token.format(),
+            ])]
+        )
+    }
+}
+
+fn format_array(node: &JsonArray, f: &mut JsonFormatter) -> FormatResult<()> {
+    let l_brack_token = node.l_brack_token()?;
+    let elements = node.elements();
+    let r_brack_token = node.r_brack_token()?;
+
+    if elements.is_empty() {
+        write!(f, [l_brack_token.format(), r_brack_token.format()])
+    } else {
+        write!(
+            f,
+            [group_elements(&format_args![
+                l_brack_token.format(),
+                soft_block_indent(&format_with(|f: &mut JsonFormatter| format_separated(
+                    elements.elements(),
+                    f
+                ))),
+                r_brack_token.format(),
+            ])]
+        )
+    }
+}
+
+fn format_member(node: &JsonMember, f: &mut JsonFormatter) -> FormatResult<()> {
+    let key = node.key()?;
+    let colon_token = node.colon_token()?;
+    let value = node.value()?;
+
+    write!(
+        f,
+        [
+            key.syntax().format(),
+            colon_token.format(),
+            space_token(),
+            value.syntax().format()
+        ]
+    )
+}
+
+/// Formats the elements of a separated list, one element per line if the enclosing group breaks.
+///
+/// JSON doesn't allow trailing commas: a trailing comma present in the source is removed
+/// and missing separators between two elements are inserted.
+fn format_separated<N>(
+    elements: AstSeparatedListElementsIterator<JsonLanguage, N>,
+    f: &mut JsonFormatter,
+) -> FormatResult<()>
+where
+    N: AstNode<Language = JsonLanguage>,
+{
+    let mut join = f.join_nodes_with_soft_line();
+    let mut elements = elements.peekable();
+
+    while let Some(element) = elements.next() {
+        let node = element.node()?;
+        let separator = element.trailing_separator()?;
+        let is_last = elements.peek().is_none();
+
+        let format_separator = format_with(|f: &mut JsonFormatter| match separator {
+            Some(separator) if is_last => write!(f, [format_removed(separator)]),
+            Some(separator) => write!(f, [separator.format()]),
+            None if is_last => Ok(()),
+            None => write!(f, [FormatInserted::new(JsonSyntaxKind::COMMA, ",")]),
+        });
+
+        join.entry(
+            node.syntax(),
+            &format_args![node.syntax().format(), format_separator],
+        );
+    }
+
+    join.finish()
+}
+
+/// Formats a JSON document.
+///
+/// It returns a [Formatted] result, which the user can use to override a file.
+pub fn format_json(context: JsonFormatContext, root: &JsonSyntaxNode) -> FormatResult<Formatted> {
+    rome_formatter::format_node(context, &root.format())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_json;
+    use crate::context::JsonFormatContext;
+    use rome_json_factory::make;
+    use rome_json_syntax::{
+        JsonArrayElementList, JsonLanguage, JsonMember, JsonMemberList, JsonSyntaxKind,
+        JsonSyntaxNode, JsonSyntaxToken, JsonValue, T,
+    };
+    use rome_rowan::AstNode;
+
+    fn token(kind: JsonSyntaxKind) -> JsonSyntaxToken {
+        JsonSyntaxToken::new_detached(kind, kind.to_string().unwrap(), [], [])
+    }
+
+    fn string(text: &str) -> rome_json_syntax::JsonString {
+        make::json_string(JsonSyntaxToken::new_detached(
+            JsonSyntaxKind::JSON_STRING_LITERAL,
+            &format!("\"{text}\""),
+            [],
+            [],
+        ))
+    }
+
+    fn number(text: &str) -> JsonValue {
+        JsonValue::JsonNumber(make::json_number(JsonSyntaxToken::new_detached(
+            JsonSyntaxKind::JSON_NUMBER_LITERAL,
+            text,
+            [],
+            [],
+        )))
+    }
+
+    fn member(key: &str, value: JsonValue) -> JsonMember {
+        make::json_member(string(key), token(T![:]), value)
+    }
+
+    /// Creates a separated list without a trailing separator. The list factories always
+    /// reserve a slot for the trailing separator, which makes the last element invalid if empty.
+    fn separated_list<N>(kind: JsonSyntaxKind, nodes: Vec<N>) -> JsonSyntaxNode
+    where
+        N: AstNode<Language = JsonLanguage>,
+    {
+        let mut slots = Vec::new();
+
+        for (index, node) in nodes.into_iter().enumerate() {
+            if index > 0 {
+                slots.push(Some(token(T![,]).into()));
+            }
+
+            slots.push(Some(node.into_syntax().into()));
+        }
+
+        JsonSyntaxNode::new_detached(kind, slots)
+    }
+
+    fn object(members: Vec<JsonMember>) -> JsonValue {
+        let members = separated_list(JsonSyntaxKind::JSON_MEMBER_LIST, members);
+
+        JsonValue::JsonObject(
+            make::json_object(token(T!['{']), token(T!['}']))
+                .with_json_member_list(JsonMemberList::unwrap_cast(members))
+                .build(),
+        )
+    }
+
+    fn array(elements: Vec<JsonValue>) -> JsonValue {
+        let elements = separated_list(JsonSyntaxKind::JSON_ARRAY_ELEMENT_LIST, elements);
+
+        JsonValue::JsonArray(
+            make::json_array(token(T!['[']), token(T![']']))
+                .with_elements(JsonArrayElementList::unwrap_cast(elements))
+                .build(),
+        )
+    }
+
+    fn format(value: JsonValue) -> String {
+        let root = make::json_root(value);
+
+        format_json(JsonFormatContext::default(), root.syntax())
+            .expect("formatting failed")
+            .print()
+            .into_code()
+    }
+
+    #[test]
+    fn empty_object() {
+        assert_eq!(format(object(vec![])), "{}\n");
+    }
+
+    #[test]
+    fn nested_object() {
+        let value = object(vec![
+            member("a", object(vec![member("b", number("1"))])),
+            member("c", number("2")),
+        ]);
+
+        assert_eq!(format(value), "{\"a\": {\"b\": 1}, \"c\": 2}\n");
+    }
+
+    #[test]
+    fn long_array() {
+        let value = array((0..30).map(|_| number("1000")).collect());
+
+        let expected = std::format!("[\n{}\t1000\n]\n", "\t1000,\n".repeat(29));
+        assert_eq!(format(value), expected);
+    }
+}
@@ -50,6 +50,11 @@ impl rome_rowan::SyntaxKind for JsonSyntaxKind {
     fn from_raw(raw: RawSyntaxKind) -> Self {
         Self::from(raw.0)
     }
+
+    #[inline]
+    fn is_valid_raw(raw: RawSyntaxKind) -> bool {
+        raw.0 <= JsonSyntaxKind::__LAST as u16
+    }
 }
 
 impl TryFrom<JsonSyntaxKind> for TriviaPieceKind {
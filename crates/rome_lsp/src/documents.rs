@@ -1,6 +1,6 @@
 use anyhow::bail;
 
-use crate::line_index::LineIndex;
+use rome_diagnostics::line_index::LineIndex;
 
 /// Internal representation of supported [language identifiers]
 ///
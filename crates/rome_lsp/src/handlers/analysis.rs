@@ -3,15 +3,15 @@ use std::collections::HashMap;
 use anyhow::Result;
 use rome_analyze::ActionCategory;
 use rome_fs::RomePath;
-use rome_service::workspace::{FixFileParams, PullActionsParams};
+use rome_service::workspace::{FixFileMode, FixFileParams, PullActionsParams};
 use rome_service::RomeError;
 use tower_lsp::lsp_types::{
     self as lsp, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
 };
 
-use crate::line_index::LineIndex;
 use crate::session::Session;
 use crate::utils;
+use rome_diagnostics::line_index::LineIndex;
 
 const FIX_ALL: CodeActionKind = CodeActionKind::new("source.fixAll");
 
@@ -106,9 +106,10 @@ fn fix_all(
     line_index: &LineIndex,
     diagnostics: &[lsp::Diagnostic],
 ) -> Result<Option<CodeActionOrCommand>, RomeError> {
-    let fixed = session
-        .workspace
-        .fix_file(FixFileParams { path: rome_path })?;
+    let fixed = session.workspace.fix_file(FixFileParams {
+        path: rome_path,
+        fix_file_mode: FixFileMode::SafeFixes,
+    })?;
 
     if fixed.rules.is_empty() {
         return Ok(None);
@@ -142,10 +143,7 @@ fn fix_all(
         vec![lsp::TextEdit {
             range: lsp::Range {
                 start: lsp::Position::new(0, 0),
-                end: lsp::Position::new(
-                    line_index.newlines.len().try_into().unwrap_or(u32::MAX),
-                    0,
-                ),
+                end: lsp::Position::new(line_index.line_count().try_into().unwrap_or(u32::MAX), 0),
             },
             new_text: fixed.code,
         }],
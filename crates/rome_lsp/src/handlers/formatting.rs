@@ -1,8 +1,7 @@
-use crate::line_index::LineCol;
 use crate::session::Session;
+use crate::utils;
 use anyhow::Result;
 use rome_formatter::IndentStyle;
-use rome_rowan::TextRange;
 use rome_service::workspace::{FormatFileParams, FormatOnTypeParams, FormatRangeParams};
 use tower_lsp::lsp_types::*;
 use tracing::trace;
@@ -28,7 +27,7 @@ pub(crate) fn format(
         indent_style,
     })?;
 
-    let num_lines: u32 = doc.line_index.newlines.len().try_into()?;
+    let num_lines: u32 = doc.line_index.line_count().try_into()?;
 
     let range = Range {
         start: Position::default(),
@@ -60,16 +59,7 @@ pub(crate) fn format_range(
         IndentStyle::Tab
     };
 
-    let start_index = doc.line_index.offset(LineCol {
-        line: params.range.start.line,
-        col: params.range.start.character,
-    });
-    let end_index = doc.line_index.offset(LineCol {
-        line: params.range.end.line,
-        col: params.range.end.character,
-    });
-
-    let format_range = TextRange::new(start_index, end_index);
+    let format_range = utils::text_range(&doc.line_index, params.range);
     let formatted = session.workspace.format_range(FormatRangeParams {
         path: rome_path,
         range: format_range,
@@ -78,24 +68,11 @@ pub(crate) fn format_range(
 
     // Recalculate the actual range that was reformatted from the formatter result
     let formatted_range = match formatted.range() {
-        Some(range) => {
-            let start_loc = doc.line_index.line_col(range.start());
-            let end_loc = doc.line_index.line_col(range.end());
-            Range {
-                start: Position {
-                    line: start_loc.line,
-                    character: start_loc.col,
-                },
-                end: Position {
-                    line: end_loc.line,
-                    character: end_loc.col,
-                },
-            }
-        }
+        Some(range) => utils::range(&doc.line_index, range),
         None => Range {
             start: Position::default(),
             end: Position {
-                line: doc.line_index.newlines.len().try_into()?,
+                line: doc.line_index.line_count().try_into()?,
                 character: 0,
             },
         },
@@ -123,10 +100,7 @@ pub(crate) fn format_on_type(
         IndentStyle::Tab
     };
 
-    let offset = doc.line_index.offset(LineCol {
-        line: position.line,
-        col: position.character,
-    });
+    let offset = utils::offset(&doc.line_index, position);
 
     let formatted = session.workspace.format_on_type(FormatOnTypeParams {
         path: rome_path,
@@ -136,24 +110,11 @@ pub(crate) fn format_on_type(
 
     // Recalculate the actual range that was reformatted from the formatter result
     let formatted_range = match formatted.range() {
-        Some(range) => {
-            let start_loc = doc.line_index.line_col(range.start());
-            let end_loc = doc.line_index.line_col(range.end());
-            Range {
-                start: Position {
-                    line: start_loc.line,
-                    character: start_loc.col,
-                },
-                end: Position {
-                    line: end_loc.line,
-                    character: end_loc.col,
-                },
-            }
-        }
+        Some(range) => utils::range(&doc.line_index, range),
         None => Range {
             start: Position::default(),
             end: Position {
-                line: doc.line_index.newlines.len().try_into()?,
+                line: doc.line_index.line_count().try_into()?,
                 character: 0,
             },
         },
@@ -4,7 +4,6 @@ pub mod server;
 mod capabilities;
 mod documents;
 mod handlers;
-mod line_index;
 mod requests;
 mod session;
 mod url_interner;
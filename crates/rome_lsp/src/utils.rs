@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 
-use crate::line_index::{LineCol, LineIndex};
 use rome_analyze::{ActionCategory, AnalyzerAction};
 use rome_console::fmt::Termcolor;
 use rome_console::fmt::{self, Formatter};
 use rome_console::MarkupBuf;
+use rome_diagnostics::line_index::{LineColUtf16, LineIndex};
 use rome_diagnostics::termcolor::NoColor;
 use rome_diagnostics::{Applicability, Diagnostic, SuggestionChange};
 use rome_diagnostics::{CodeSuggestion, Severity};
@@ -15,22 +15,23 @@ use tower_lsp::lsp_types::{self as lsp};
 use tracing::error;
 
 pub(crate) fn position(line_index: &LineIndex, offset: TextSize) -> lsp::Position {
-    let line_col = line_index.line_col(offset);
+    let line_col = line_index.to_utf16(line_index.line_col(offset));
     lsp::Position::new(line_col.line, line_col.col)
 }
 
 pub(crate) fn range(line_index: &LineIndex, range: TextRange) -> lsp::Range {
-    let start = position(line_index, range.start());
-    let end = position(line_index, range.end());
+    let range = line_index.range_utf16(range);
+    let start = lsp::Position::new(range.start.line, range.start.col);
+    let end = lsp::Position::new(range.end.line, range.end.col);
     lsp::Range::new(start, end)
 }
 
 pub(crate) fn offset(line_index: &LineIndex, position: lsp::Position) -> TextSize {
-    let line_col = LineCol {
+    let line_col = LineColUtf16 {
         line: position.line as u32,
         col: position.character as u32,
     };
-    line_index.offset(line_col)
+    line_index.offset(line_index.to_utf8(line_col))
 }
 
 pub(crate) fn text_range(line_index: &LineIndex, range: lsp::Range) -> TextRange {
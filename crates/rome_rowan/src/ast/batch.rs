@@ -1,5 +1,5 @@
-use crate::{AstNode, Language, NodeOrToken, SyntaxNode, SyntaxNodeCast};
-use std::{collections::BinaryHeap, iter::once};
+use crate::{AstNode, Language, SyntaxElement, SyntaxNode, SyntaxNodeCast, SyntaxToken};
+use std::{cmp::Reverse, collections::BinaryHeap, iter::once};
 
 pub trait BatchMutationExt<L>: AstNode<Language = L>
 where
@@ -33,7 +33,10 @@ struct CommitChange<L: Language> {
     parent: Option<SyntaxNode<L>>,
     parent_range: Option<(u32, u32)>,
     new_node_slot: usize,
-    new_node: Option<SyntaxNode<L>>,
+    new_node: Option<SyntaxElement<L>>,
+    /// Position of the change in the order the changes were requested. When
+    /// several changes target the same slot, the last requested one wins
+    order: usize,
 }
 
 impl<L: Language> PartialEq for CommitChange<L> {
@@ -52,21 +55,21 @@ impl<L: Language> Eq for CommitChange<L> {}
 /// to see the same node in case of two or more nodes having the same depth.
 impl<L: Language> PartialOrd for CommitChange<L> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<L: Language> Ord for CommitChange<L> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         let self_range = self.parent_range.unwrap_or((0u32, 0u32));
         let other_range = other.parent_range.unwrap_or((0u32, 0u32));
 
-        (self.parent_depth, self_range.0, self_range.1).partial_cmp(&(
+        (self.parent_depth, self_range.0, self_range.1).cmp(&(
             other.parent_depth,
             other_range.0,
             other_range.1,
         ))
     }
 }
-impl<L: Language> Ord for CommitChange<L> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.parent_depth.cmp(&other.parent_depth)
-    }
-}
 
 pub struct BatchMutation<L, N>
 where
@@ -86,9 +89,29 @@ where
     where
         T: AstNode<Language = L>,
     {
-        let prev_node = prev_node.into_syntax();
-        let new_node_slot = prev_node.index();
-        let parent = prev_node.parent();
+        self.replace_element(
+            prev_node.into_syntax().into(),
+            next_node.into_syntax().into(),
+        );
+    }
+
+    /// Replaces the token `prev_token` with `next_token`
+    pub fn replace_token(&mut self, prev_token: SyntaxToken<L>, next_token: SyntaxToken<L>) {
+        self.replace_element(prev_token.into(), next_token.into());
+    }
+
+    /// Replaces the node or token `prev_element` with `next_element`. Unlike
+    /// [BatchMutation::replace_node], the two elements don't need to be of the same type
+    pub fn replace_element(
+        &mut self,
+        prev_element: SyntaxElement<L>,
+        next_element: SyntaxElement<L>,
+    ) {
+        let new_node_slot = match &prev_element {
+            SyntaxElement::Node(node) => node.index(),
+            SyntaxElement::Token(token) => token.index(),
+        };
+        let parent = prev_element.parent();
         let parent_range: Option<(u32, u32)> = parent.as_ref().map(|p| {
             let range = p.text_range();
             (range.start().into(), range.end().into())
@@ -100,7 +123,8 @@ where
             parent,
             parent_range,
             new_node_slot,
-            new_node: Some(next_node.into_syntax()),
+            new_node: Some(next_element),
+            order: self.changes.len(),
         });
     }
 
@@ -120,6 +144,9 @@ where
     /// To address this case at step 3, when we pop a new change to apply it, we actually aggregate all changes to the current
     /// parent together. This is done by the heap because we also sort by node and it's range.
     ///
+    /// When several changes replace the same element, the last requested change wins. This is also the case
+    /// when both a node and one of its descendants are replaced: the change that was requested last wins.
+    ///
     pub fn commit(self) -> N {
         let BatchMutation { root, mut changes } = self;
         // Fill the heap with the requested changes
@@ -140,14 +167,18 @@ where
                 // Aggregate all modifications to the current parent
                 // This works because of the Ord we defined in the [CommitChange] struct
 
-                let mut modifications = vec![(item.new_node_slot, item.new_node)];
+                let mut modifications = vec![(item.new_node_slot, item.new_node, item.order)];
                 loop {
                     if let Some(next_change_parent) = changes.peek().and_then(|i| i.parent.as_ref())
                     {
                         if *next_change_parent == current_parent {
                             // SAFETY: We can .pop().unwrap() because we .peek() above
                             let next_change = changes.pop().unwrap();
-                            modifications.push((next_change.new_node_slot, next_change.new_node));
+                            modifications.push((
+                                next_change.new_node_slot,
+                                next_change.new_node,
+                                next_change.order,
+                            ));
                             continue;
                         }
                     }
@@ -157,11 +188,20 @@ where
                 // Now we detach the current parent, make all the modifications
                 // and push a pending change to its parent.
 
+                // Only keep the last requested change of each slot
+                modifications.sort_by_key(|(index, _, order)| (*index, Reverse(*order)));
+                modifications.dedup_by_key(|(index, _, _)| *index);
+
+                let order = modifications
+                    .iter()
+                    .map(|(_, _, order)| *order)
+                    .max()
+                    .unwrap_or(item.order);
+
                 let mut current_parent = current_parent.detach();
 
-                for (index, node) in modifications {
-                    let replace_with = node.map(NodeOrToken::Node);
-                    current_parent = current_parent.splice_slots(index..=index, once(replace_with));
+                for (index, element, _) in modifications {
+                    current_parent = current_parent.splice_slots(index..=index, once(element));
                 }
 
                 changes.push(CommitChange {
@@ -169,10 +209,23 @@ where
                     parent: grandparent,
                     parent_range: grandparent_range,
                     new_node_slot: currentparent_slot,
-                    new_node: Some(current_parent),
+                    new_node: Some(current_parent.into()),
+                    order,
                 });
             } else {
-                return item.new_node.and_then(|x| x.cast()).unwrap();
+                // All the remaining changes replace the root
+                let mut item = item;
+                while let Some(next_change) = changes.pop() {
+                    if next_change.order > item.order {
+                        item = next_change;
+                    }
+                }
+
+                return item
+                    .new_node
+                    .and_then(|x| x.into_node())
+                    .and_then(|x| x.cast())
+                    .unwrap();
             }
         }
 
@@ -183,8 +236,10 @@ where
 #[cfg(test)]
 pub mod tests {
     use crate::{
-        raw_language::{LiteralExpression, RawLanguageKind, RawLanguageRoot, RawSyntaxTreeBuilder},
-        AstNode, BatchMutationExt, SyntaxNodeCast,
+        raw_language::{
+            LiteralExpression, RawLanguage, RawLanguageKind, RawLanguageRoot, RawSyntaxTreeBuilder,
+        },
+        AstNode, BatchMutationExt, SyntaxNodeCast, SyntaxToken,
     };
 
     /// ```
@@ -289,4 +344,66 @@ pub mod tests {
 
         assert_eq!(expected_debug, format!("{:#?}", after.syntax()));
     }
+
+    fn token(text: &str) -> SyntaxToken<RawLanguage> {
+        SyntaxToken::new_detached(RawLanguageKind::STRING_TOKEN, text, [], [])
+    }
+
+    #[test]
+    pub fn ok_batch_mutation_replace_token() {
+        let (before, _) = tree_two("a", "b");
+        let (expected, _) = tree_one("d");
+
+        let a = find(&before, "a").syntax().first_token().unwrap();
+        let b = find(&before, "b");
+        let d = clone_detach(&expected, "d");
+
+        let mut batch = before.begin();
+        batch.replace_token(a, token("c"));
+        batch.replace_node(b, d);
+        let after = batch.commit();
+
+        assert_eq!(after.syntax().text(), "cd");
+    }
+
+    #[test]
+    pub fn ok_batch_mutation_same_node_last_wins() {
+        let (before, _) = tree_two("a", "b");
+        let (expected, _) = tree_two("c", "d");
+
+        let a = find(&before, "a");
+        let c = clone_detach(&expected, "c");
+        let d = clone_detach(&expected, "d");
+
+        let mut batch = before.begin();
+        batch.replace_node(a.clone(), c);
+        batch.replace_node(a, d);
+        let after = batch.commit();
+
+        assert_eq!(after.syntax().text(), "db");
+    }
+
+    #[test]
+    pub fn ok_batch_mutation_node_and_descendant_last_wins() {
+        let (before, _) = tree_two("a", "b");
+        let (expected, _) = tree_one("c");
+
+        let a = find(&before, "a");
+        let a_token = a.syntax().first_token().unwrap();
+        let c = clone_detach(&expected, "c");
+
+        let mut batch = before.clone().begin();
+        batch.replace_node(a.clone(), c.clone());
+        batch.replace_token(a_token.clone(), token("e"));
+        let after = batch.commit();
+
+        assert_eq!(after.syntax().text(), "eb");
+
+        let mut batch = before.begin();
+        batch.replace_token(a_token, token("e"));
+        batch.replace_node(a, c);
+        let after = batch.commit();
+
+        assert_eq!(after.syntax().text(), "cb");
+    }
 }
@@ -0,0 +1,456 @@
+//! Compact binary encoding of syntax trees.
+//!
+//! This allows persisting parsed trees, for example in a cache of the CLI, and restoring them
+//! without reparsing the source text. The encoding stores the green tree in pre-order:
+//! every node is written as its kind and number of slots followed by the content of its slots,
+//! and every token as its kind, text and trivia pieces. Integers are written as
+//! LEB128 variable length integers.
+//!
+//! The encoding doesn't store the [Language] of the tree. Decoding rejects kinds that don't exist
+//! in the language it decodes to, but it can't detect kinds that changed their meaning: the cache
+//! key of a serialized tree should include the version of the parser that created it.
+
+use crate::green::{GreenElement, GreenNode, GreenToken, GreenTrivia, Slot};
+use crate::{Language, RawSyntaxKind, SyntaxKind, SyntaxNode, TriviaPiece, TriviaPieceKind};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+use std::str;
+use text_size::TextSize;
+
+/// Version of the encoding, written at the start of every serialized tree
+const FORMAT_VERSION: u8 = 1;
+
+const EMPTY_SLOT: u8 = 0;
+const NODE: u8 = 1;
+const TOKEN: u8 = 2;
+
+/// Serializes the tree rooted at `root` in a compact binary format that can be restored
+/// with [deserialize].
+///
+/// `root` doesn't have to be the root of its tree: only the sub-tree of `root` is serialized.
+pub fn serialize<L: Language>(root: &SyntaxNode<L>) -> Vec<u8> {
+    let green = root.green_node();
+
+    let mut bytes = vec![FORMAT_VERSION];
+    write_node_header(&mut bytes, &green);
+
+    let mut stack = vec![green.slots()];
+
+    while let Some(slots) = stack.last_mut() {
+        match slots.next() {
+            None => {
+                stack.pop();
+            }
+            Some(Slot::Empty { .. }) => bytes.push(EMPTY_SLOT),
+            Some(Slot::Token { token, .. }) => write_token(&mut bytes, token),
+            Some(Slot::Node { node, .. }) => {
+                write_node_header(&mut bytes, node);
+                stack.push(node.slots());
+            }
+        }
+    }
+
+    bytes
+}
+
+/// Restores a tree serialized with [serialize]. The returned node is the root of a new tree.
+///
+/// Returns an error if `bytes` isn't a valid encoding of a tree.
+pub fn deserialize<L: Language>(bytes: &[u8]) -> Result<SyntaxNode<L>, DeserializeError> {
+    let mut reader = Reader::<L>::new(bytes);
+
+    let version = reader.read_byte()?;
+    if version != FORMAT_VERSION {
+        return Err(DeserializeError::UnsupportedVersion(version));
+    }
+
+    if reader.read_byte()? != NODE {
+        return Err(DeserializeError::InvalidRoot);
+    }
+
+    let mut stack = vec![reader.read_node_header()?];
+
+    loop {
+        // SAFETY: The stack is never empty at the start of an iteration
+        let top = stack.last_mut().unwrap();
+
+        if top.slots.len() < top.slot_count {
+            let slot = match reader.read_byte()? {
+                EMPTY_SLOT => None,
+                TOKEN => Some(GreenElement::Token(reader.read_token()?)),
+                NODE => {
+                    let header = reader.read_node_header()?;
+                    stack.push(header);
+                    continue;
+                }
+                tag => return Err(DeserializeError::InvalidTag(tag)),
+            };
+
+            top.slots.push(slot);
+            continue;
+        }
+
+        // SAFETY: `top` is the last element of the stack
+        let finished = stack.pop().unwrap();
+        let node = GreenNode::new(finished.kind, finished.slots);
+
+        match stack.last_mut() {
+            Some(parent) => parent.slots.push(Some(GreenElement::Node(node))),
+            None => {
+                if !reader.bytes.is_empty() {
+                    return Err(DeserializeError::TrailingBytes);
+                }
+
+                return Ok(SyntaxNode::new_root(node));
+            }
+        }
+    }
+}
+
+/// Error returned by [deserialize] when the bytes aren't a valid encoding of a tree
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum DeserializeError {
+    /// The bytes were serialized with an incompatible version of the encoding
+    UnsupportedVersion(u8),
+    /// The bytes end before the end of the tree
+    UnexpectedEnd,
+    /// The root of the tree isn't a node
+    InvalidRoot,
+    /// A slot starts with an unknown tag
+    InvalidTag(u8),
+    /// A node or token has a kind that doesn't exist in the language of the tree
+    InvalidKind(u16),
+    /// An integer is too large for the value it encodes
+    InvalidInteger,
+    /// A token text isn't valid UTF-8
+    InvalidText,
+    /// A trivia piece has an unknown kind
+    InvalidTriviaKind(u8),
+    /// The trivia pieces of a token aren't contained in the token text
+    InvalidTrivia,
+    /// The bytes continue after the end of the tree
+    TrailingBytes,
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::UnsupportedVersion(version) => {
+                write!(fmt, "unsupported encoding version {version}")
+            }
+            DeserializeError::UnexpectedEnd => fmt.write_str("unexpected end of the input"),
+            DeserializeError::InvalidRoot => fmt.write_str("the root of the tree isn't a node"),
+            DeserializeError::InvalidTag(tag) => write!(fmt, "invalid slot tag {tag}"),
+            DeserializeError::InvalidKind(kind) => write!(fmt, "invalid syntax kind {kind}"),
+            DeserializeError::InvalidInteger => fmt.write_str("invalid integer"),
+            DeserializeError::InvalidText => fmt.write_str("token text isn't valid UTF-8"),
+            DeserializeError::InvalidTriviaKind(kind) => {
+                write!(fmt, "invalid trivia kind {kind}")
+            }
+            DeserializeError::InvalidTrivia => fmt.write_str("trivia pieces exceed the token text"),
+            DeserializeError::TrailingBytes => fmt.write_str("unexpected bytes after the tree"),
+        }
+    }
+}
+
+impl Error for DeserializeError {}
+
+fn write_node_header(bytes: &mut Vec<u8>, node: &GreenNode) {
+    bytes.push(NODE);
+    write_integer(bytes, node.kind().0 as u32);
+    write_integer(bytes, node.slots().len() as u32);
+}
+
+fn write_token(bytes: &mut Vec<u8>, token: &GreenToken) {
+    bytes.push(TOKEN);
+    write_integer(bytes, token.kind().0 as u32);
+
+    let text = token.text();
+    write_integer(bytes, text.len() as u32);
+    bytes.extend_from_slice(text.as_bytes());
+
+    write_trivia(bytes, token.leading_trivia().pieces());
+    write_trivia(bytes, token.trailing_trivia().pieces());
+}
+
+fn write_trivia(bytes: &mut Vec<u8>, pieces: &[TriviaPiece]) {
+    write_integer(bytes, pieces.len() as u32);
+
+    for piece in pieces {
+        let kind = match piece.kind {
+            TriviaPieceKind::Newline => 0,
+            TriviaPieceKind::Whitespace => 1,
+            TriviaPieceKind::SingleLineComment => 2,
+            TriviaPieceKind::MultiLineComment => 3,
+            TriviaPieceKind::Skipped => 4,
+        };
+
+        bytes.push(kind);
+        write_integer(bytes, piece.length.into());
+    }
+}
+
+/// Writes `value` as a LEB128 variable length integer
+fn write_integer(bytes: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            break;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Node whose slots are being read
+struct NodeHeader {
+    kind: RawSyntaxKind,
+    slot_count: usize,
+    slots: Vec<Option<GreenElement>>,
+}
+
+struct Reader<'a, L> {
+    bytes: &'a [u8],
+    language: PhantomData<L>,
+}
+
+impl<'a, L: Language> Reader<'a, L> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            language: PhantomData,
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<u8, DeserializeError> {
+        let (first, rest) = self
+            .bytes
+            .split_first()
+            .ok_or(DeserializeError::UnexpectedEnd)?;
+        self.bytes = rest;
+        Ok(*first)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DeserializeError> {
+        if self.bytes.len() < len {
+            return Err(DeserializeError::UnexpectedEnd);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Reads a LEB128 variable length integer
+    fn read_integer(&mut self) -> Result<u32, DeserializeError> {
+        let mut value: u32 = 0;
+
+        for shift in (0..32).step_by(7) {
+            let byte = self.read_byte()?;
+            let bits = u32::from(byte & 0x7f);
+
+            if bits.leading_zeros() < shift {
+                return Err(DeserializeError::InvalidInteger);
+            }
+
+            value |= bits << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(DeserializeError::InvalidInteger)
+    }
+
+    fn read_kind(&mut self) -> Result<RawSyntaxKind, DeserializeError> {
+        let kind =
+            u16::try_from(self.read_integer()?).map_err(|_| DeserializeError::InvalidInteger)?;
+        let kind = RawSyntaxKind(kind);
+
+        if L::Kind::is_valid_raw(kind) {
+            Ok(kind)
+        } else {
+            Err(DeserializeError::InvalidKind(kind.0))
+        }
+    }
+
+    fn read_node_header(&mut self) -> Result<NodeHeader, DeserializeError> {
+        let kind = self.read_kind()?;
+        let slot_count = self.read_integer()? as usize;
+
+        // Every slot takes at least one byte, don't trust the slot count of malformed inputs
+        let capacity = slot_count.min(self.bytes.len());
+
+        Ok(NodeHeader {
+            kind,
+            slot_count,
+            slots: Vec::with_capacity(capacity),
+        })
+    }
+
+    fn read_token(&mut self) -> Result<GreenToken, DeserializeError> {
+        let kind = self.read_kind()?;
+
+        let text_len = self.read_integer()? as usize;
+        let text = str::from_utf8(self.read_bytes(text_len)?)
+            .map_err(|_| DeserializeError::InvalidText)?;
+
+        let leading = self.read_trivia()?;
+        let trailing = self.read_trivia()?;
+
+        // The trimmed text of the token is a slice of the text without the trivia
+        let leading_len = trivia_len(&leading)?;
+        let trailing_len = trivia_len(&trailing)?;
+        let trimmed_end = text_len
+            .checked_sub(trailing_len)
+            .filter(|end| *end >= leading_len)
+            .ok_or(DeserializeError::InvalidTrivia)?;
+
+        if !text.is_char_boundary(leading_len) || !text.is_char_boundary(trimmed_end) {
+            return Err(DeserializeError::InvalidTrivia);
+        }
+
+        Ok(GreenToken::with_trivia(
+            kind,
+            text,
+            GreenTrivia::new(leading),
+            GreenTrivia::new(trailing),
+        ))
+    }
+
+    fn read_trivia(&mut self) -> Result<Vec<TriviaPiece>, DeserializeError> {
+        let count = self.read_integer()? as usize;
+        let mut pieces = Vec::with_capacity(count.min(self.bytes.len()));
+
+        for _ in 0..count {
+            let kind = match self.read_byte()? {
+                0 => TriviaPieceKind::Newline,
+                1 => TriviaPieceKind::Whitespace,
+                2 => TriviaPieceKind::SingleLineComment,
+                3 => TriviaPieceKind::MultiLineComment,
+                4 => TriviaPieceKind::Skipped,
+                kind => return Err(DeserializeError::InvalidTriviaKind(kind)),
+            };
+
+            let length = TextSize::from(self.read_integer()?);
+            pieces.push(TriviaPiece::new(kind, length));
+        }
+
+        Ok(pieces)
+    }
+}
+
+fn trivia_len(pieces: &[TriviaPiece]) -> Result<usize, DeserializeError> {
+    pieces.iter().try_fold(0usize, |len, piece| {
+        len.checked_add(u32::from(piece.length) as usize)
+            .ok_or(DeserializeError::InvalidTrivia)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deserialize, serialize, DeserializeError};
+    use crate::raw_language::{RawLanguage, RawLanguageKind, RawSyntaxTreeBuilder};
+    use crate::{SyntaxNode, TriviaPiece};
+
+    fn build_tree() -> SyntaxNode<RawLanguage> {
+        let mut builder = RawSyntaxTreeBuilder::new();
+
+        builder.start_node(RawLanguageKind::ROOT);
+
+        builder.start_node(RawLanguageKind::SEPARATED_EXPRESSION_LIST);
+
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.token_with_trivia(
+            RawLanguageKind::STRING_TOKEN,
+            "\n// comment\n\"a\" ",
+            &[
+                TriviaPiece::newline(1),
+                TriviaPiece::single_line_comment(10),
+                TriviaPiece::newline(1),
+            ],
+            &[TriviaPiece::whitespace(1)],
+        );
+        builder.finish_node();
+
+        builder.token(RawLanguageKind::COMMA_TOKEN, ",");
+
+        // Literal expression with a missing token
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.finish_node();
+
+        builder.token(RawLanguageKind::COMMA_TOKEN, ",");
+
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.token(RawLanguageKind::NUMBER_TOKEN, "1");
+        builder.finish_node();
+
+        builder.finish_node();
+
+        builder.finish_node();
+
+        builder.finish()
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = build_tree();
+
+        let bytes = serialize(&root);
+        let restored = deserialize::<RawLanguage>(&bytes).unwrap();
+
+        assert_eq!(restored.text(), root.text());
+        assert_eq!(format!("{restored:#?}"), format!("{root:#?}"));
+    }
+
+    #[test]
+    fn round_trip_sub_tree() {
+        let root = build_tree();
+        let list = root.first_child().unwrap();
+
+        let restored = deserialize::<RawLanguage>(&serialize(&list)).unwrap();
+
+        assert_eq!(restored.kind(), RawLanguageKind::SEPARATED_EXPRESSION_LIST);
+        assert_eq!(restored.text(), list.text());
+        assert_eq!(restored.parent(), None);
+    }
+
+    #[test]
+    fn malformed_input() {
+        let bytes = serialize(&build_tree());
+
+        assert_eq!(
+            deserialize::<RawLanguage>(&bytes[..bytes.len() - 1]),
+            Err(DeserializeError::UnexpectedEnd)
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            deserialize::<RawLanguage>(&trailing),
+            Err(DeserializeError::TrailingBytes)
+        );
+
+        // The kind of the root node is written right after the version and the node tag
+        let mut kind = bytes.clone();
+        kind[2] = RawLanguageKind::__LAST as u8;
+        assert_eq!(
+            deserialize::<RawLanguage>(&kind),
+            Err(DeserializeError::InvalidKind(
+                RawLanguageKind::__LAST as u16
+            ))
+        );
+
+        let mut version = bytes;
+        version[0] = 0;
+        assert_eq!(
+            deserialize::<RawLanguage>(&version),
+            Err(DeserializeError::UnsupportedVersion(0))
+        );
+    }
+}
@@ -24,6 +24,7 @@ mod utility_types;
 #[allow(unsafe_code)]
 mod arc;
 mod ast;
+pub mod binary;
 mod cow_mut;
 pub mod raw_language;
 #[cfg(feature = "serde")]
@@ -46,7 +47,7 @@ pub use crate::{
     syntax_node_text::SyntaxNodeText,
     syntax_token_text::SyntaxTokenText,
     tree_builder::{Checkpoint, TreeBuilder},
-    utility_types::{Direction, NodeOrToken, TokenAtOffset, WalkEvent},
+    utility_types::{Direction, NodeOrToken, TokenAtOffset, Utf16Range, WalkEvent},
 };
 
 pub(crate) use crate::green::{GreenNode, GreenNodeData, GreenToken, GreenTokenData};
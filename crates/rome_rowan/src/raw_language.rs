@@ -58,6 +58,10 @@ impl SyntaxKind for RawLanguageKind {
 
         unsafe { std::mem::transmute::<u16, RawLanguageKind>(raw.0) }
     }
+
+    fn is_valid_raw(raw: RawSyntaxKind) -> bool {
+        raw.0 < RawLanguageKind::__LAST as u16
+    }
 }
 
 #[doc(hidden)]
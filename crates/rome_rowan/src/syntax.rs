@@ -37,6 +37,9 @@ pub trait SyntaxKind: fmt::Debug + PartialEq + Copy {
 
     /// Creates a syntax kind from a raw kind.
     fn from_raw(raw: RawSyntaxKind) -> Self;
+
+    /// Returns `true` if `raw` is a kind of this language that [SyntaxKind::from_raw] can convert.
+    fn is_valid_raw(raw: RawSyntaxKind) -> bool;
 }
 
 pub trait Language: Sized + Clone + Copy + fmt::Debug + Eq + Ord + std::hash::Hash {
@@ -109,9 +112,9 @@ impl<L: Language> IntoIterator for SyntaxList<L> {
 mod tests {
     use text_size::TextRange;
 
-    use crate::raw_language::{RawLanguageKind, RawSyntaxTreeBuilder};
+    use crate::raw_language::{RawLanguage, RawLanguageKind, RawSyntaxTreeBuilder};
     use crate::syntax::TriviaPiece;
-    use crate::Direction;
+    use crate::{Direction, SyntaxNode, SyntaxToken, TokenAtOffset, Utf16Range, WalkEvent};
 
     #[test]
     fn empty_list() {
@@ -490,4 +493,346 @@ mod tests {
         assert_eq!("/**/", pieces_rev[0].text());
         assert_eq!("\n\t ", pieces_rev[1].text());
     }
+
+    #[test]
+    pub fn syntax_trivia_split_at() {
+        use crate::*;
+        let node = RawSyntaxTreeBuilder::wrap_with_node(RawLanguageKind::ROOT, |builder| {
+            builder.token_with_trivia(
+                RawLanguageKind::LET_TOKEN,
+                "/* a */\n/* b */ let",
+                &[
+                    TriviaPiece::multi_line_comment(7),
+                    TriviaPiece::newline(1),
+                    TriviaPiece::multi_line_comment(7),
+                    TriviaPiece::whitespace(1),
+                ],
+                &[],
+            );
+        });
+
+        let trivia = node.first_leading_trivia().unwrap();
+        let texts = |pieces: &[SyntaxTriviaPiece<RawLanguage>]| {
+            pieces
+                .iter()
+                .map(|piece| piece.text().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let cases: [(u32, &[&str], &[&str]); 5] = [
+            (0, &[], &["/* a */", "\n", "/* b */", " "]),
+            (7, &["/* a */"], &["\n", "/* b */", " "]),
+            (8, &["/* a */", "\n"], &["/* b */", " "]),
+            // Offset in the middle of the second comment
+            (11, &["/* a */", "\n"], &["/* b */", " "]),
+            (16, &["/* a */", "\n", "/* b */", " "], &[]),
+        ];
+
+        for (offset, expected_before, expected_after) in cases {
+            let (before, after) = trivia.split_at(TextSize::from(offset));
+
+            assert_eq!(texts(&before), expected_before, "offset {offset}");
+            assert_eq!(texts(&after), expected_after, "offset {offset}");
+        }
+    }
+
+    /// ```
+    /// 0: SEPARATED_EXPRESSION_LIST@0..5
+    ///     0: LITERAL_EXPRESSION@0..2
+    ///         0: NUMBER_TOKEN@0..2 "1" [] [Whitespace(" ")]
+    ///     1: COMMA_TOKEN@2..4 "," [] [Whitespace(" ")]
+    ///     2: LITERAL_EXPRESSION@4..5
+    ///         0: NUMBER_TOKEN@4..5 "2" [] []
+    /// ```
+    fn separated_list() -> SyntaxNode<RawLanguage> {
+        let mut builder = RawSyntaxTreeBuilder::new();
+        builder.start_node(RawLanguageKind::SEPARATED_EXPRESSION_LIST);
+
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.token_with_trivia(
+            RawLanguageKind::NUMBER_TOKEN,
+            "1 ",
+            &[],
+            &[TriviaPiece::whitespace(1)],
+        );
+        builder.finish_node();
+
+        builder.token_with_trivia(
+            RawLanguageKind::COMMA_TOKEN,
+            ", ",
+            &[],
+            &[TriviaPiece::whitespace(1)],
+        );
+
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.token(RawLanguageKind::NUMBER_TOKEN, "2");
+        builder.finish_node();
+
+        builder.finish_node();
+        builder.finish()
+    }
+
+    fn ranges(node: &SyntaxNode<RawLanguage>) -> Vec<(RawLanguageKind, TextRange)> {
+        node.descendants_with_tokens(Direction::Next)
+            .map(|element| (element.kind(), element.text_range()))
+            .collect()
+    }
+
+    #[test]
+    fn replace_child_node() {
+        let root = separated_list();
+        let first = root.children().next().unwrap();
+
+        let mut builder = RawSyntaxTreeBuilder::new();
+        builder.start_node(RawLanguageKind::LITERAL_EXPRESSION);
+        builder.token(RawLanguageKind::NUMBER_TOKEN, "42");
+        builder.finish_node();
+        let next = builder.finish();
+
+        let root = root.replace_child(first.into(), next.into()).unwrap();
+
+        assert_eq!(root.text(), "42, 2");
+        assert_eq!(root.parent(), None);
+        assert_eq!(
+            ranges(&root),
+            vec![
+                (
+                    RawLanguageKind::SEPARATED_EXPRESSION_LIST,
+                    TextRange::new(0.into(), 5.into())
+                ),
+                (
+                    RawLanguageKind::LITERAL_EXPRESSION,
+                    TextRange::new(0.into(), 2.into())
+                ),
+                (
+                    RawLanguageKind::NUMBER_TOKEN,
+                    TextRange::new(0.into(), 2.into())
+                ),
+                (
+                    RawLanguageKind::COMMA_TOKEN,
+                    TextRange::new(2.into(), 4.into())
+                ),
+                (
+                    RawLanguageKind::LITERAL_EXPRESSION,
+                    TextRange::new(4.into(), 5.into())
+                ),
+                (
+                    RawLanguageKind::NUMBER_TOKEN,
+                    TextRange::new(4.into(), 5.into())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_child_token() {
+        let root = separated_list();
+
+        // Direct child of the root
+        let comma = root
+            .children_with_tokens()
+            .nth(1)
+            .and_then(|e| e.into_token())
+            .unwrap();
+        let next = SyntaxToken::new_detached(
+            RawLanguageKind::COMMA_TOKEN,
+            ",   ",
+            [],
+            [TriviaPiece::whitespace(3)],
+        );
+
+        let root = root.replace_child(comma.into(), next.into()).unwrap();
+
+        assert_eq!(root.text(), "1 ,   2");
+        assert_eq!(
+            root.last_child().unwrap().text_range(),
+            TextRange::new(6.into(), 7.into())
+        );
+
+        // Token of a descendant node
+        let two = root.last_token().unwrap();
+        let next = SyntaxToken::new_detached(RawLanguageKind::NUMBER_TOKEN, "3", [], []);
+
+        let root = root.replace_child(two.into(), next.into()).unwrap();
+
+        assert_eq!(root.text(), "1 ,   3");
+        assert_eq!(root.children().count(), 2);
+        assert_eq!(
+            root.last_child().unwrap().kind(),
+            RawLanguageKind::LITERAL_EXPRESSION
+        );
+    }
+
+    #[test]
+    fn replace_child_not_a_descendant() {
+        let root = separated_list();
+        let other = separated_list();
+
+        let other_first = other.children().next().unwrap();
+        let next = other.children().nth(1).unwrap();
+
+        assert_eq!(
+            root.clone()
+                .replace_child(other_first.into(), next.clone().into()),
+            None
+        );
+        assert_eq!(root.clone().replace_child(root.into(), next.into()), None);
+    }
+
+    #[test]
+    fn token_at_offset() {
+        let root = separated_list();
+
+        let token_texts = |offset: u32| {
+            root.token_at_offset(offset.into())
+                .map(|token| token.text_trimmed().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Between two tokens
+        assert!(matches!(
+            root.token_at_offset(2.into()),
+            TokenAtOffset::Between(_, _)
+        ));
+        assert_eq!(token_texts(2), vec!["1", ","]);
+        assert_eq!(token_texts(4), vec![",", "2"]);
+
+        // Inside of a token, including its trivia
+        assert!(matches!(
+            root.token_at_offset(3.into()),
+            TokenAtOffset::Single(_)
+        ));
+        assert_eq!(token_texts(3), vec![","]);
+        assert_eq!(token_texts(1), vec!["1"]);
+
+        // Start and end of the tree
+        assert_eq!(token_texts(0), vec!["1"]);
+        assert_eq!(token_texts(5), vec!["2"]);
+    }
+
+    #[test]
+    fn covering_element() {
+        let root = separated_list();
+
+        // Inside of a token
+        let element = root.covering_element(TextRange::new(4.into(), 5.into()));
+        assert_eq!(element.kind(), RawLanguageKind::NUMBER_TOKEN);
+        assert_eq!(element.to_string(), "2");
+
+        let element = root.covering_element(TextRange::new(2.into(), 3.into()));
+        assert_eq!(element.kind(), RawLanguageKind::COMMA_TOKEN);
+
+        // Spans the first element and the separator
+        let element = root.covering_element(TextRange::new(1.into(), 3.into()));
+        assert_eq!(element.kind(), RawLanguageKind::SEPARATED_EXPRESSION_LIST);
+        assert_eq!(element.into_node(), Some(root.clone()));
+
+        // Empty range at the boundary between two tokens, either token can be returned
+        let element = root.covering_element(TextRange::empty(2.into()));
+        assert!(matches!(
+            element.kind(),
+            RawLanguageKind::NUMBER_TOKEN | RawLanguageKind::COMMA_TOKEN
+        ));
+    }
+
+    #[test]
+    fn preorder_skip_subtree() {
+        let root = separated_list();
+
+        let mut preorder = root.preorder();
+        let mut entered = Vec::new();
+
+        while let Some(event) = preorder.next() {
+            if let WalkEvent::Enter(node) = event {
+                entered.push(node.text_trimmed().to_string());
+
+                if node.kind() == RawLanguageKind::LITERAL_EXPRESSION {
+                    preorder.skip_subtree();
+                }
+            }
+        }
+
+        assert_eq!(entered, vec!["1 , 2", "1", "2"]);
+
+        let mut preorder = root.preorder_with_tokens(Direction::Next);
+        let mut events = Vec::new();
+
+        while let Some(event) = preorder.next() {
+            match event {
+                WalkEvent::Enter(element) => {
+                    if element.kind() == RawLanguageKind::LITERAL_EXPRESSION {
+                        preorder.skip_subtree();
+                    }
+
+                    events.push(format!("enter {:?}", element.kind()));
+                }
+                WalkEvent::Leave(element) => events.push(format!("leave {:?}", element.kind())),
+            }
+        }
+
+        // The NUMBER_TOKEN inside of the literals aren't visited
+        assert_eq!(
+            events,
+            vec![
+                "enter SEPARATED_EXPRESSION_LIST",
+                "enter LITERAL_EXPRESSION",
+                "leave LITERAL_EXPRESSION",
+                "enter COMMA_TOKEN",
+                "leave COMMA_TOKEN",
+                "enter LITERAL_EXPRESSION",
+                "leave LITERAL_EXPRESSION",
+                "leave SEPARATED_EXPRESSION_LIST",
+            ]
+        );
+    }
+
+    #[test]
+    fn utf16_lengths() {
+        let mut builder = RawSyntaxTreeBuilder::new();
+        builder.start_node(RawLanguageKind::ROOT);
+        builder.token_with_trivia(
+            RawLanguageKind::STRING_TOKEN,
+            "'a😀' ",
+            &[],
+            &[TriviaPiece::whitespace(1)],
+        );
+        builder.token(RawLanguageKind::STRING_TOKEN, "é");
+        builder.finish_node();
+
+        let root = builder.finish();
+        let first = root.first_token().unwrap();
+        let last = root.last_token().unwrap();
+
+        assert_eq!(
+            first.text_trimmed_range(),
+            TextRange::new(0.into(), 7.into())
+        );
+        assert_eq!(first.text_utf16_len(), 6);
+        assert_eq!(last.text_utf16_len(), 1);
+
+        // `😀'`
+        assert_eq!(
+            root.utf16_range(TextRange::new(2.into(), 7.into())),
+            Some(Utf16Range { start: 2, end: 5 })
+        );
+        // `é`
+        assert_eq!(
+            root.utf16_range(TextRange::new(8.into(), 10.into())),
+            Some(Utf16Range { start: 6, end: 7 })
+        );
+        // Spans both tokens
+        assert_eq!(
+            root.utf16_range(TextRange::new(0.into(), 10.into())),
+            Some(Utf16Range { start: 0, end: 7 })
+        );
+        assert_eq!(
+            root.utf16_range(TextRange::empty(8.into())),
+            Some(Utf16Range { start: 6, end: 6 })
+        );
+
+        // Inside of the surrogate pair
+        assert_eq!(root.utf16_range(TextRange::new(3.into(), 6.into())), None);
+        // Outside of the node
+        assert_eq!(root.utf16_range(TextRange::new(8.into(), 11.into())), None);
+    }
 }
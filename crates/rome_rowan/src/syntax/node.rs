@@ -1,9 +1,10 @@
 use crate::green::GreenElement;
 use crate::syntax::element::SyntaxElement;
+use crate::syntax::token::utf16_len;
 use crate::syntax::SyntaxTrivia;
 use crate::{
     cursor, Direction, GreenNode, Language, NodeOrToken, SyntaxKind, SyntaxList, SyntaxNodeText,
-    SyntaxToken, TokenAtOffset, WalkEvent,
+    SyntaxToken, TokenAtOffset, Utf16Range, WalkEvent,
 };
 #[cfg(feature = "serde")]
 use serde_crate::Serialize;
@@ -47,7 +48,7 @@ impl<L: Language> SyntaxNode<L> {
         )))
     }
 
-    fn green_node(&self) -> GreenNode {
+    pub(crate) fn green_node(&self) -> GreenNode {
         self.raw.green().to_owned()
     }
 
@@ -383,6 +384,66 @@ impl<L: Language> SyntaxNode<L> {
         NodeOrToken::from(self.raw.covering_element(range))
     }
 
+    /// Converts the byte `range` of the text of this node to a range in UTF-16 code units,
+    /// relative to the start of this node.
+    ///
+    /// Returns [None] if `range` isn't contained in the node or if it doesn't start
+    /// and end on character boundaries.
+    ///
+    /// ```
+    /// use rome_rowan::raw_language::{RawLanguage, RawLanguageKind, RawSyntaxTreeBuilder};
+    /// use rome_rowan::*;
+    /// let node = RawSyntaxTreeBuilder::wrap_with_node(RawLanguageKind::ROOT, |builder| {
+    ///     builder.token(RawLanguageKind::STRING_TOKEN, "'😀'");
+    ///     builder.token(RawLanguageKind::PLUS_TOKEN, "+");
+    /// });
+    ///
+    /// // The `+` token
+    /// let range = TextRange::new(TextSize::from(6), TextSize::from(7));
+    /// assert_eq!(node.utf16_range(range), Some(Utf16Range { start: 4, end: 5 }));
+    /// ```
+    pub fn utf16_range(&self, range: TextRange) -> Option<Utf16Range> {
+        let node_range = self.text_range();
+
+        if !node_range.contains_range(range) {
+            return None;
+        }
+
+        if range.is_empty() && range.start() == node_range.start() {
+            return Some(Utf16Range::default());
+        }
+
+        let mut token_offset = 0;
+        let mut start = None;
+
+        for token in self.descendants_tokens(Direction::Next) {
+            let token_range = token.text_range();
+            let text = token.text();
+
+            // Returns the UTF-16 offset of `offset` if it's inside of the token
+            let to_utf16 = |offset: TextSize| {
+                let relative = usize::from(offset - token_range.start());
+                text.get(..relative)
+                    .map(|prefix| token_offset + utf16_len(prefix))
+            };
+
+            if start.is_none() && token_range.contains_inclusive(range.start()) {
+                start = Some(to_utf16(range.start())?);
+            }
+
+            if let Some(start) = start {
+                if token_range.contains_inclusive(range.end()) {
+                    let end = to_utf16(range.end())?;
+                    return Some(Utf16Range { start, end });
+                }
+            }
+
+            token_offset += token.text_utf16_len();
+        }
+
+        None
+    }
+
     /// Finds a [`SyntaxElement`] which intersects with a given `range`. If
     /// there are several intersecting elements, any one can be returned.
     ///
@@ -487,6 +548,27 @@ impl<L: Language> SyntaxNode<L> {
         self.first_token_has_comments() || self.last_token_has_comments()
     }
 
+    /// Whether the node contains comments between its first and last token: the comments
+    /// leading or trailing the node itself aren't considered.
+    ///
+    /// This is the case for `/* comment */` in `a, /* comment */ b` but not for `// comment` in
+    /// `// comment\na, b`.
+    pub fn has_inner_comments(&self) -> bool {
+        let (first, last) = match (self.first_token(), self.last_token()) {
+            (Some(first), Some(last)) if first != last => (first, last),
+            _ => return false,
+        };
+
+        if first.has_trailing_comments() || last.has_leading_comments() {
+            return true;
+        }
+
+        self.descendants_tokens(Direction::Next)
+            .skip(1)
+            .take_while(|token| token != &last)
+            .any(|token| token.has_leading_comments() || token.has_trailing_comments())
+    }
+
     /// Whether the node contains trailing comments.
     pub fn has_trailing_comments(&self) -> bool {
         self.last_token()
@@ -663,6 +745,9 @@ pub struct Preorder<L: Language> {
 }
 
 impl<L: Language> Preorder<L> {
+    /// Skips the descendants of the node of the last [WalkEvent::Enter] event
+    /// returned by the iterator: the next event will be the [WalkEvent::Leave]
+    /// event of that node
     pub fn skip_subtree(&mut self) {
         self.raw.skip_subtree()
     }
@@ -681,6 +766,9 @@ pub struct PreorderWithTokens<L: Language> {
 }
 
 impl<L: Language> PreorderWithTokens<L> {
+    /// Skips the descendants of the element of the last [WalkEvent::Enter]
+    /// event returned by the iterator: the next event will be the
+    /// [WalkEvent::Leave] event of that element
     pub fn skip_subtree(&mut self) {
         self.raw.skip_subtree()
     }
@@ -91,6 +91,25 @@ impl<L: Language> SyntaxToken<L> {
         self.raw.text()
     }
 
+    /// Returns the length of the text of the token, including all trivia, in UTF-16 code units.
+    ///
+    /// Characters outside of the basic multilingual plane take two code units.
+    ///
+    /// ```
+    /// use rome_rowan::raw_language::{RawLanguage, RawLanguageKind, RawSyntaxTreeBuilder};
+    /// use rome_rowan::*;
+    /// let token = RawSyntaxTreeBuilder::wrap_with_node(RawLanguageKind::ROOT, |builder| {
+    ///     builder.token(RawLanguageKind::STRING_TOKEN, "'é😀'");
+    /// })
+    /// .first_token()
+    /// .unwrap();
+    /// assert_eq!(token.text().len(), 8);
+    /// assert_eq!(token.text_utf16_len(), 5);
+    /// ```
+    pub fn text_utf16_len(&self) -> u32 {
+        utf16_len(self.text())
+    }
+
     /// Returns the text of a token, including all trivia as an owned value.
     ///  
     /// ```
@@ -247,6 +266,54 @@ impl<L: Language> SyntaxToken<L> {
         }
     }
 
+    /// Return a new version of this token with its kind replaced with `kind` and its trimmed
+    /// text replaced with `text`, keeping its leading and trailing trivia
+    ///
+    /// ```
+    /// use rome_rowan::raw_language::{RawLanguage, RawLanguageKind, RawSyntaxTreeBuilder};
+    /// use rome_rowan::*;
+    /// let token = RawSyntaxTreeBuilder::wrap_with_node(RawLanguageKind::ROOT, |builder| {
+    ///     builder.token_with_trivia(
+    ///         RawLanguageKind::EQUAL_TOKEN,
+    ///         "\t= ",
+    ///         &[TriviaPiece::whitespace(1)],
+    ///         &[TriviaPiece::whitespace(1)],
+    ///     );
+    /// })
+    /// .first_token()
+    /// .unwrap();
+    ///
+    /// let token = token.with_kind(RawLanguageKind::PLUS_TOKEN, "+");
+    /// assert_eq!(token.kind(), RawLanguageKind::PLUS_TOKEN);
+    /// assert_eq!(token.text_trimmed(), "+");
+    /// assert_eq!(token.text(), "\t+ ");
+    /// ```
+    #[must_use = "syntax elements are immutable, the result of update methods must be propagated to have any effect"]
+    pub fn with_kind(self, kind: L::Kind, text: &str) -> Self {
+        let mut full_text = String::new();
+        for piece in self.leading_trivia().pieces() {
+            full_text.push_str(piece.text());
+        }
+
+        full_text.push_str(text);
+
+        for piece in self.trailing_trivia().pieces() {
+            full_text.push_str(piece.text());
+        }
+
+        let green_token = self.green_token();
+
+        Self {
+            raw: cursor::SyntaxToken::new_detached(GreenToken::with_trivia(
+                kind.to_raw(),
+                &full_text,
+                green_token.leading_trivia().clone(),
+                green_token.trailing_trivia().clone(),
+            )),
+            _p: PhantomData,
+        }
+    }
+
     /// Returns the token leading trivia.
     ///
     /// ```
@@ -361,3 +428,8 @@ impl<L: Language> From<cursor::SyntaxToken> for SyntaxToken<L> {
         }
     }
 }
+
+/// Returns the length of `text` in UTF-16 code units
+pub(crate) fn utf16_len(text: &str) -> u32 {
+    text.chars().map(|c| c.len_utf16() as u32).sum()
+}
@@ -615,6 +615,36 @@ impl<L: Language> SyntaxTrivia<L> {
     pub fn text_range(&self) -> TextRange {
         self.raw.text_range()
     }
+
+    /// Splits the pieces of this trivia into the pieces that end before or at `offset` and the
+    /// pieces that end after it. A piece containing `offset` is never split and is part of the
+    /// second list. `offset` is a position in the source text, like the ranges of the pieces.
+    ///
+    /// This is useful to move comments along with the nodes they belong to, for example when
+    /// reordering import statements or object members.
+    ///
+    /// ```
+    /// use rome_rowan::raw_language::{RawLanguageKind, RawSyntaxTreeBuilder};
+    /// use rome_rowan::{TextSize, TriviaPiece};
+    /// let node = RawSyntaxTreeBuilder::wrap_with_node(RawLanguageKind::ROOT, |builder| {
+    ///     builder.token_with_trivia(
+    ///         RawLanguageKind::LET_TOKEN,
+    ///         "/**/\nlet",
+    ///         &[TriviaPiece::multi_line_comment(4), TriviaPiece::newline(1)],
+    ///         &[],
+    ///     );
+    /// });
+    /// let (before, after) = node.first_leading_trivia().unwrap().split_at(TextSize::from(4));
+    /// assert_eq!(vec!["/**/"], before.iter().map(|piece| piece.text()).collect::<Vec<_>>());
+    /// assert_eq!(vec!["\n"], after.iter().map(|piece| piece.text()).collect::<Vec<_>>());
+    /// ```
+    pub fn split_at(
+        &self,
+        offset: TextSize,
+    ) -> (Vec<SyntaxTriviaPiece<L>>, Vec<SyntaxTriviaPiece<L>>) {
+        self.pieces()
+            .partition(|piece| piece.text_range().end() <= offset)
+    }
 }
 
 fn print_debug_str<S: AsRef<str>>(text: S, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -54,6 +54,23 @@ impl<N: fmt::Display, T: fmt::Display> fmt::Display for NodeOrToken<N, T> {
     }
 }
 
+/// A range of text measured in UTF-16 code units, as returned by [SyntaxNode::utf16_range](crate::SyntaxNode::utf16_range)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Utf16Range {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Utf16Range {
+    pub fn len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
     Next,
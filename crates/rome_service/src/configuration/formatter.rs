@@ -49,7 +49,7 @@ impl From<&FormatterConfiguration> for FormatSettings {
             enabled: conf.enabled,
             indent_style: Some(indent_style),
             line_width: Some(conf.line_width),
-            format_with_errors: false,
+            format_with_errors: conf.format_with_errors,
         }
     }
 }
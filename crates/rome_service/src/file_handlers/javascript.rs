@@ -6,17 +6,25 @@ use rome_js_analyze::analyze;
 use rome_js_formatter::context::QuoteStyle;
 use rome_js_formatter::{context::JsFormatContext, format_node};
 use rome_js_parser::Parse;
-use rome_js_syntax::{JsAnyRoot, JsLanguage, SourceType, TextRange, TextSize, TokenAtOffset};
-use rome_rowan::AstNode;
+use rome_js_syntax::{
+    JsAnyImportClause, JsAnyModuleItem, JsAnyRoot, JsImport, JsLanguage, JsModule, LanguageVariant,
+    SourceType, TextRange, TextSize, TokenAtOffset,
+};
+use rome_rowan::{AstNode, SyntaxResult};
+use rome_text_edit::{TextEdit, TextEditBuilder};
 
-use crate::workspace::FixFileResult;
+use crate::workspace::{FixFileMode, FixFileResult};
 use crate::{
-    settings::{FormatSettings, Language, LanguageSettings, LanguagesSettings, SettingsHandle},
+    settings::{
+        FormatSettings, Language, LanguageSettings, LanguagesSettings, SettingsHandle,
+        WorkspaceSettings,
+    },
     workspace::server::AnyParse,
     RomeError,
 };
 
 use super::{ExtensionHandler, Mime};
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -40,21 +48,59 @@ impl Language for JsLanguage {
         editor: IndentStyle,
         path: &RomePath,
     ) -> JsFormatContext {
-        JsFormatContext::new(path.as_path().try_into().unwrap_or_default())
-            .with_indent_style(
-                language
-                    .indent_style
-                    .or(global.indent_style)
-                    .unwrap_or(editor),
-            )
-            .with_line_width(
-                language
-                    .line_width
-                    .or(global.line_width)
-                    .unwrap_or_default(),
-            )
-            .with_quote_style(language.quote_style.unwrap_or_default())
+        format_context(
+            global,
+            language,
+            editor,
+            path.as_path().try_into().unwrap_or_default(),
+        )
+    }
+}
+
+/// Resolve the formatting context for a document of the given [SourceType]
+fn format_context(
+    global: &FormatSettings,
+    language: &JsFormatSettings,
+    editor: IndentStyle,
+    source_type: SourceType,
+) -> JsFormatContext {
+    JsFormatContext::new(source_type)
+        .with_indent_style(
+            language
+                .indent_style
+                .or(global.indent_style)
+                .unwrap_or(editor),
+        )
+        .with_line_width(
+            language
+                .line_width
+                .or(global.line_width)
+                .unwrap_or_default(),
+        )
+        .with_quote_style(language.quote_style.unwrap_or_default())
+}
+
+/// Format `source` as a standalone document of type `source_type`, without
+/// opening it in the workspace
+pub(crate) fn format_source(
+    source: &str,
+    source_type: SourceType,
+    settings: &WorkspaceSettings,
+) -> Result<Printed, RomeError> {
+    let parse = rome_js_parser::parse(source, 0, source_type);
+    if !settings.format.format_with_errors && parse.has_errors() {
+        return Err(RomeError::FormatWithErrorsDisabled);
     }
+
+    let context = format_context(
+        &settings.format,
+        &settings.languages.javascript.format,
+        IndentStyle::default(),
+        source_type,
+    );
+
+    let formatted = format_node(context, &parse.syntax())?;
+    Ok(formatted.print())
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -64,6 +110,7 @@ impl ExtensionHandler for JsFileHandler {
     fn capabilities(&self) -> super::Capabilities {
         super::Capabilities {
             parse: Some(parse),
+            reparse: Some(reparse),
             debug_print: Some(debug_print),
             lint: Some(lint),
             format: Some(format),
@@ -71,11 +118,12 @@ impl ExtensionHandler for JsFileHandler {
             fix_all: Some(fix_all),
             format_range: Some(format_range),
             format_on_type: Some(format_on_type),
+            organize_imports: Some(organize_imports),
         }
     }
 
-    fn language(&self) -> super::Language {
-        super::Language::JavaScript
+    fn language(&self) -> Option<super::Language> {
+        Some(super::Language::Js)
     }
 
     fn mime(&self) -> super::Mime {
@@ -90,13 +138,40 @@ impl ExtensionHandler for JsFileHandler {
 fn parse(rome_path: &RomePath, text: &str) -> AnyParse {
     let file_id = rome_path.file_id();
 
-    let source_type =
+    let mut source_type =
         SourceType::try_from(rome_path.as_path()).unwrap_or_else(|_| SourceType::js_module());
 
+    // The extension of some JavaScript files, like `.cjs`, doesn't enable JSX
+    if source_type.language().is_javascript()
+        && source_type.variant().is_standard()
+        && SourceType::infer_jsx(text)
+    {
+        source_type = source_type.with_variant(LanguageVariant::Jsx);
+    }
+
     let parse = rome_js_parser::parse(text, file_id, source_type);
     AnyParse::from(parse)
 }
 
+fn reparse(
+    _rome_path: &RomePath,
+    parse: AnyParse,
+    range: TextRange,
+    text: &str,
+) -> Option<AnyParse> {
+    if !parse.diagnostics.is_empty() {
+        return None;
+    }
+
+    let root = rome_js_parser::reparse_token(&parse.syntax::<JsLanguage>(), range, text)?;
+
+    Some(AnyParse {
+        // SAFETY: the reparsed node is the root of the tree
+        root: root.as_send().unwrap(),
+        diagnostics: Vec::new(),
+    })
+}
+
 impl<T> From<Parse<T>> for AnyParse
 where
     T: AstNode,
@@ -170,7 +245,11 @@ fn code_actions(
     actions
 }
 
-fn fix_all(rome_path: &RomePath, parse: AnyParse) -> FixFileResult {
+/// Applies the fixes of the file one at a time until no more fixes can be
+/// applied. The safe fixes are always applied before the unsafe ones: an
+/// unsafe fix is only applied when the tree has no safe fix left, and only if
+/// `fix_file_mode` is [FixFileMode::SafeAndUnsafeFixes]
+fn fix_all(rome_path: &RomePath, parse: AnyParse, fix_file_mode: FixFileMode) -> FixFileResult {
     let mut tree: JsAnyRoot = parse.tree();
     let mut rules = Vec::new();
 
@@ -182,17 +261,26 @@ fn fix_all(rome_path: &RomePath, parse: AnyParse) -> FixFileResult {
     let file_id = rome_path.file_id();
 
     loop {
+        let mut unsafe_action = None;
+
         let action = analyze(file_id, &tree, filter, |signal| {
             if let Some(action) = signal.action() {
-                if action.applicability == Applicability::Always {
-                    return ControlFlow::Break(action);
+                match action.applicability {
+                    Applicability::Always => return ControlFlow::Break(action),
+                    Applicability::MaybeIncorrect
+                        if fix_file_mode == FixFileMode::SafeAndUnsafeFixes
+                            && unsafe_action.is_none() =>
+                    {
+                        unsafe_action = Some(action);
+                    }
+                    _ => {}
                 }
             }
 
             ControlFlow::Continue(())
         });
 
-        match action {
+        match action.or(unsafe_action) {
             Some(action) => {
                 tree = action.root;
                 rules.push((action.rule_name, action.original_range));
@@ -260,3 +348,150 @@ fn format_on_type(
     let printed = rome_js_formatter::format_sub_tree(context, &root_node)?;
     Ok(printed)
 }
+
+/// Sorts every contiguous block of import statements of a module. Comments
+/// in the leading trivia of an import move along with the import, except for
+/// the comments leading the block
+fn organize_imports(_rome_path: &RomePath, parse: AnyParse) -> TextEdit {
+    let mut builder = TextEdit::builder();
+
+    let module = match JsModule::cast(parse.syntax()) {
+        Some(module) => module,
+        None => return builder.finish(),
+    };
+
+    let mut block = Vec::new();
+
+    for item in module.items() {
+        match item {
+            JsAnyModuleItem::JsImport(import) => block.push(import),
+            _ => organize_import_block(&mut builder, std::mem::take(&mut block)),
+        }
+    }
+
+    organize_import_block(&mut builder, block);
+
+    builder.finish()
+}
+
+/// Group of an import statement, the groups are sorted in declaration order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ImportGroup {
+    /// `import "polyfill";`
+    ///
+    /// Side-effect imports keep their original order among themselves, since
+    /// running them in a different order could change the behavior of the module
+    SideEffect,
+    /// `import a from "a";`
+    External,
+    /// `import b from "./b";`
+    Relative,
+}
+
+fn import_sort_key(import: &JsImport) -> SyntaxResult<(ImportGroup, String)> {
+    let clause = import.import_clause()?;
+    let token = clause.source()?.value_token()?;
+    let specifier = token
+        .text_trimmed()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string();
+
+    if matches!(clause, JsAnyImportClause::JsImportBareClause(_)) {
+        // The specifier isn't part of the key so that the sort keeps the side-effect
+        // imports in their original order
+        return Ok((ImportGroup::SideEffect, String::new()));
+    }
+
+    let group = if specifier.starts_with('.') {
+        ImportGroup::Relative
+    } else {
+        ImportGroup::External
+    };
+
+    Ok((group, specifier))
+}
+
+/// Import of a block, with its leading comments split from the whitespace
+/// that separates it from the previous item
+struct ImportEntry {
+    key: (ImportGroup, String),
+    separator: String,
+    comments: String,
+    text: String,
+}
+
+fn organize_import_block(builder: &mut TextEditBuilder, imports: Vec<JsImport>) {
+    let (first, last) = match (imports.first(), imports.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return,
+    };
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut entries: Vec<ImportEntry> = Vec::with_capacity(imports.len());
+    let mut header = String::new();
+
+    for (index, import) in imports.iter().enumerate() {
+        // Leave the block untouched if any of its imports is missing its source
+        let key = match import_sort_key(import) {
+            Ok(key) => key,
+            Err(_) => return,
+        };
+
+        let syntax = import.syntax();
+        let full = syntax.to_string();
+        let leading_len =
+            usize::from(syntax.text_trimmed_range().start() - syntax.text_range().start());
+        let (leading, text) = full.split_at(leading_len);
+        let mut comments = leading.trim_start();
+        let separator = &leading[..leading.len() - comments.len()];
+
+        // The comments leading the block, like a file header or a license, stay at the
+        // top of the block instead of moving with the first import
+        if index == 0 {
+            header.push_str(comments);
+            comments = "";
+        }
+
+        match seen.get(&syntax.text_trimmed().to_string()) {
+            // Duplicates are removed, but their comments move to the import that's kept
+            Some(&kept) => entries[kept].comments.push_str(comments),
+            None => {
+                seen.insert(syntax.text_trimmed().to_string(), entries.len());
+                entries.push(ImportEntry {
+                    key,
+                    separator: separator.to_string(),
+                    comments: comments.to_string(),
+                    text: text.to_string(),
+                });
+            }
+        }
+    }
+
+    let mut sorted: Vec<&ImportEntry> = entries.iter().collect();
+    sorted.sort_by(|left, right| left.key.cmp(&right.key));
+
+    // Every position keeps its original separator so that blank lines and line endings
+    // are preserved
+    let mut organized = String::new();
+    for (index, (position, entry)) in entries.iter().zip(&sorted).enumerate() {
+        organized.push_str(&position.separator);
+        if index == 0 {
+            organized.push_str(&header);
+        }
+        organized.push_str(&entry.comments);
+        organized.push_str(&entry.text);
+    }
+
+    let original = imports
+        .iter()
+        .map(|import| import.syntax().to_string())
+        .collect::<String>();
+
+    if organized != original {
+        let range = TextRange::new(
+            first.syntax().text_range().start(),
+            last.syntax().text_range().end(),
+        );
+        builder.replace(range, organized);
+    }
+}
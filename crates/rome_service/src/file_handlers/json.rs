@@ -6,6 +6,7 @@ impl ExtensionHandler for JsonFileHandler {
     fn capabilities(&self) -> super::Capabilities {
         super::Capabilities {
             parse: None,
+            reparse: None,
             debug_print: None,
             format: None,
             lint: None,
@@ -13,11 +14,12 @@ impl ExtensionHandler for JsonFileHandler {
             fix_all: None,
             format_range: None,
             format_on_type: None,
+            organize_imports: None,
         }
     }
 
-    fn language(&self) -> super::Language {
-        super::Language::Json
+    fn language(&self) -> Option<super::Language> {
+        Some(super::Language::Json)
     }
 
     fn mime(&self) -> super::Mime {
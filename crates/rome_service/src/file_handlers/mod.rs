@@ -1,14 +1,13 @@
-use std::ffi::OsStr;
-
 use rome_analyze::{AnalyzerAction, RuleCategories};
 use rome_diagnostics::Diagnostic;
 use rome_formatter::{IndentStyle, Printed};
 use rome_fs::RomePath;
 use rome_js_syntax::{JsLanguage, TextRange, TextSize};
+use rome_text_edit::TextEdit;
 
 use crate::{
     settings::SettingsHandle,
-    workspace::{server::AnyParse, FixFileResult},
+    workspace::{server::AnyParse, FixFileMode, FixFileResult},
     RomeError,
 };
 
@@ -18,36 +17,55 @@ mod javascript;
 mod json;
 mod unknown;
 
+pub(crate) use javascript::format_source;
 pub use javascript::JsFormatSettings;
 
 /// Supported languages by Rome
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum Language {
-    /// JavaScript, TypeScript, JSX, TSX
-    JavaScript,
+    /// JavaScript
+    Js,
+    /// JavaScript with JSX syntax
+    Jsx,
+    /// TypeScript
+    Ts,
+    /// TypeScript with JSX syntax
+    Tsx,
     /// JSON
     Json,
-    /// Any language that is not supported
-    Unknown,
-}
-
-impl From<&str> for Language {
-    fn from(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "js" | "ts" | "jsx" | "tsx" | "mjs" | "cjs" | "cts" | "mts" => Language::JavaScript,
-            "json" => Language::Json,
-            _ => Language::Unknown,
-        }
-    }
+    /// CSS
+    Css,
 }
 
-impl From<&OsStr> for Language {
-    fn from(s: &OsStr) -> Self {
-        match s.to_str().unwrap() {
-            "js" | "ts" | "jsx" | "tsx" | "mjs" | "cjs" | "cts" | "mts" => Language::JavaScript,
-            "json" => Language::Json,
-            _ => Language::Unknown,
-        }
+/// Central registry mapping file extensions to a [Language]
+///
+/// New languages get supported by adding their extensions to [LanguageRegistry::EXTENSIONS]
+pub(crate) struct LanguageRegistry;
+
+impl LanguageRegistry {
+    /// File extensions associated with each language
+    const EXTENSIONS: &'static [(&'static str, Language)] = &[
+        ("js", Language::Js),
+        ("mjs", Language::Js),
+        ("cjs", Language::Js),
+        ("jsx", Language::Jsx),
+        ("ts", Language::Ts),
+        ("mts", Language::Ts),
+        ("cts", Language::Ts),
+        ("tsx", Language::Tsx),
+        ("json", Language::Json),
+        ("css", Language::Css),
+    ];
+
+    /// Returns the [Language] of the file at `path` from its extension, or [None] if the extension
+    /// isn't associated with any language
+    pub(crate) fn language_for(path: &RomePath) -> Option<Language> {
+        let extension = path.extension()?.to_str()?;
+
+        Self::EXTENSIONS
+            .iter()
+            .find(|(language_extension, _)| language_extension.eq_ignore_ascii_case(extension))
+            .map(|(_, language)| *language)
     }
 }
 
@@ -72,18 +90,21 @@ impl std::fmt::Display for Mime {
 }
 
 type Parse = fn(&RomePath, &str) -> AnyParse;
+type Reparse = fn(&RomePath, AnyParse, TextRange, &str) -> Option<AnyParse>;
 type DebugPrint = fn(&RomePath, AnyParse) -> String;
 type Lint = fn(&RomePath, AnyParse, RuleCategories) -> Vec<Diagnostic>;
 type CodeActions = fn(&RomePath, AnyParse, TextRange) -> Vec<AnalyzerAction<JsLanguage>>;
-type FixAll = fn(&RomePath, AnyParse) -> FixFileResult;
+type FixAll = fn(&RomePath, AnyParse, FixFileMode) -> FixFileResult;
 type Format = fn(&RomePath, AnyParse, SettingsHandle<IndentStyle>) -> Result<Printed, RomeError>;
 type FormatRange =
     fn(&RomePath, AnyParse, SettingsHandle<IndentStyle>, TextRange) -> Result<Printed, RomeError>;
 type FormatOnType =
     fn(&RomePath, AnyParse, SettingsHandle<IndentStyle>, TextSize) -> Result<Printed, RomeError>;
+type OrganizeImports = fn(&RomePath, AnyParse) -> TextEdit;
 
 pub(crate) struct Capabilities {
     pub(crate) parse: Option<Parse>,
+    pub(crate) reparse: Option<Reparse>,
     pub(crate) debug_print: Option<DebugPrint>,
     pub(crate) lint: Option<Lint>,
     pub(crate) code_actions: Option<CodeActions>,
@@ -91,13 +112,16 @@ pub(crate) struct Capabilities {
     pub(crate) format: Option<Format>,
     pub(crate) format_range: Option<FormatRange>,
     pub(crate) format_on_type: Option<FormatOnType>,
+    pub(crate) organize_imports: Option<OrganizeImports>,
 }
 
 /// Main trait to use to add a new language to Rome
 pub(crate) trait ExtensionHandler {
     /// The language of the file. It can be a super language.
     /// For example, a ".js" file can have [Language::Ts]
-    fn language(&self) -> Language;
+    ///
+    /// Returns [None] if the file isn't written in a known language
+    fn language(&self) -> Option<Language>;
 
     /// MIME types used to identify a certain language
     fn mime(&self) -> Mime;
@@ -111,6 +135,7 @@ pub(crate) trait ExtensionHandler {
     fn capabilities(&self) -> Capabilities {
         Capabilities {
             parse: None,
+            reparse: None,
             debug_print: None,
             format: None,
             lint: None,
@@ -118,6 +143,7 @@ pub(crate) trait ExtensionHandler {
             fix_all: None,
             format_range: None,
             format_on_type: None,
+            organize_imports: None,
         }
     }
 
@@ -145,20 +171,42 @@ impl Features {
         }
     }
 
-    /// Return a [Language] from a string
-    fn get_language(rome_path: &RomePath) -> Language {
-        match rome_path.extension() {
-            Some(file_extension) => file_extension.into(),
-            None => Language::Unknown,
-        }
-    }
-
     /// Returns the [Capabilities] associated with a [RomePath]
     pub(crate) fn get_capabilities(&self, rome_path: &RomePath) -> Capabilities {
-        match Self::get_language(rome_path) {
-            Language::JavaScript => self.js.capabilities(),
-            Language::Json => self.json.capabilities(),
-            Language::Unknown => self.unknown.capabilities(),
+        match LanguageRegistry::language_for(rome_path) {
+            Some(Language::Js | Language::Jsx | Language::Ts | Language::Tsx) => {
+                self.js.capabilities()
+            }
+            Some(Language::Json) => self.json.capabilities(),
+            Some(Language::Css) | None => self.unknown.capabilities(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Language, LanguageRegistry};
+    use rome_fs::RomePath;
+
+    fn language_for(path: &str) -> Option<Language> {
+        LanguageRegistry::language_for(&RomePath::new(path, 0))
+    }
+
+    #[test]
+    fn language_from_extension() {
+        assert_eq!(language_for("file.js"), Some(Language::Js));
+        assert_eq!(language_for("file.cjs"), Some(Language::Js));
+        assert_eq!(language_for("file.jsx"), Some(Language::Jsx));
+        assert_eq!(language_for("file.d.ts"), Some(Language::Ts));
+        assert_eq!(language_for("file.TSX"), Some(Language::Tsx));
+        assert_eq!(language_for("file.json"), Some(Language::Json));
+        assert_eq!(language_for("file.css"), Some(Language::Css));
+    }
+
+    #[test]
+    fn unknown_extension() {
+        assert_eq!(language_for("file.rs"), None);
+        assert_eq!(language_for("file.js.map"), None);
+        assert_eq!(language_for("Makefile"), None);
+    }
+}
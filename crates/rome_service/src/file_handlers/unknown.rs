@@ -7,6 +7,7 @@ impl ExtensionHandler for UnknownFileHandler {
     fn capabilities(&self) -> super::Capabilities {
         super::Capabilities {
             parse: None,
+            reparse: None,
             debug_print: None,
             format: None,
             lint: None,
@@ -14,11 +15,12 @@ impl ExtensionHandler for UnknownFileHandler {
             fix_all: None,
             format_range: None,
             format_on_type: None,
+            organize_imports: None,
         }
     }
 
-    fn language(&self) -> super::Language {
-        super::Language::Unknown
+    fn language(&self) -> Option<super::Language> {
+        None
     }
 
     fn mime(&self) -> super::Mime {
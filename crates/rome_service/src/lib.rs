@@ -1,6 +1,8 @@
 use rome_console::{Console, EnvConsole};
 use rome_formatter::FormatError;
 use rome_fs::{FileSystem, OsFileSystem, RomePath};
+use rome_js_syntax::SourceType;
+use settings::WorkspaceSettings;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -8,6 +10,7 @@ use std::path::PathBuf;
 
 pub mod configuration;
 mod file_handlers;
+pub mod sarif;
 pub mod settings;
 pub mod workspace;
 
@@ -141,6 +144,31 @@ impl<'app> App<'app> {
     }
 }
 
+impl<'app> App<'app> {
+    /// Format `source` as a document of type `source_type` and return the
+    /// formatted code, without reading or writing the file being formatted.
+    /// This is intended for editors piping the content of a buffer to Rome.
+    ///
+    /// The formatter options are resolved from the configuration file of
+    /// the project if one exists, and `source` is returned unchanged if the
+    /// formatter is disabled in the configuration
+    pub fn format_stdin(&self, source: &str, source_type: SourceType) -> Result<String, RomeError> {
+        let configuration = load_config(&self.fs, ConfigurationType::Root)?;
+        let mut settings = WorkspaceSettings::default();
+
+        if let Some(configuration) = &configuration {
+            if configuration.is_formatter_disabled() {
+                return Ok(source.to_string());
+            }
+
+            settings.merge_with_configuration(configuration);
+        }
+
+        let printed = file_handlers::format_source(source, source_type, &settings)?;
+        Ok(printed.into_code())
+    }
+}
+
 /// Clone of [std::borrow::Cow] specialized for storing a trait object and
 /// holding a mutable reference in the `Borrowed` variant instead of requiring
 /// the inner type to implement [std::borrow::ToOwned]
@@ -149,6 +177,19 @@ pub enum DynRef<'app, T: ?Sized + 'app> {
     Borrowed(&'app mut T),
 }
 
+impl<'app, T: ?Sized + 'app> DynRef<'app, T> {
+    /// Returns a shared reference to the inner value
+    pub fn get(&self) -> &T {
+        self
+    }
+
+    /// Re-borrows the inner value as a [DynRef::Borrowed], without consuming
+    /// the ownership of an [DynRef::Owned] value
+    pub fn borrow_mut(&mut self) -> DynRef<'_, T> {
+        DynRef::Borrowed(&mut **self)
+    }
+}
+
 impl<'app, T: ?Sized + 'app> Deref for DynRef<'app, T> {
     type Target = T;
 
@@ -168,3 +209,61 @@ impl<'app, T: ?Sized + 'app> DerefMut for DynRef<'app, T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rome_console::BufferConsole;
+    use rome_fs::MemoryFileSystem;
+    use rome_js_syntax::SourceType;
+
+    use crate::{App, DynRef};
+
+    fn push(mut value: DynRef<String>, c: char) {
+        value.push(c);
+    }
+
+    #[test]
+    fn dyn_ref_owned() {
+        let mut value: DynRef<String> = DynRef::Owned(Box::new(String::from("a")));
+
+        push(value.borrow_mut(), 'b');
+        push(value.borrow_mut(), 'c');
+
+        assert!(matches!(value, DynRef::Owned(_)));
+        assert_eq!(value.get(), "abc");
+    }
+
+    #[test]
+    fn dyn_ref_borrowed() {
+        let mut string = String::from("a");
+        let mut value = DynRef::Borrowed(&mut string);
+
+        push(value.borrow_mut(), 'b');
+        assert_eq!(value.get(), "ab");
+
+        push(value, 'c');
+        assert_eq!(string, "abc");
+    }
+
+    #[test]
+    fn format_stdin() {
+        let mut fs = MemoryFileSystem::default();
+        fs.insert(
+            PathBuf::from("rome.json"),
+            r#"{ "root": true, "formatter": { "indentStyle": "space", "indentSize": 6 } }"#,
+        );
+
+        let app = App::with_filesystem_and_console(
+            DynRef::Owned(Box::new(fs)),
+            DynRef::Owned(Box::new(BufferConsole::default())),
+        );
+
+        let result = app
+            .format_stdin("function f() { return 1 }", SourceType::js_module())
+            .unwrap();
+
+        assert_eq!(result, "function f() {\n      return 1;\n}\n");
+    }
+}
@@ -0,0 +1,229 @@
+//! Conversion of diagnostics to the [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! format, allowing the results of the analyzer to be uploaded to services
+//! like GitHub code scanning
+
+use std::collections::BTreeSet;
+
+use rome_diagnostics::file::{FileId, Files};
+use rome_diagnostics::{Diagnostic, Severity};
+use rome_js_syntax::TextRange;
+use serde_json::{json, Value};
+
+use crate::workspace::json::print_markup;
+
+/// Convert a list of diagnostics to a SARIF log containing a single run.
+/// Rule codes are mapped to the SARIF rule ids, and `files` is used to
+/// resolve the path and line / column positions of each diagnostic
+pub fn to_sarif(files: &dyn Files, results: &[Diagnostic]) -> String {
+    let rules: BTreeSet<_> = results
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.as_deref())
+        .collect();
+
+    let rules: Vec<_> = rules.into_iter().map(|id| json!({ "id": id })).collect();
+
+    let results: Vec<_> = results
+        .iter()
+        .map(|diagnostic| {
+            let mut result = json!({
+                "level": level(diagnostic.severity),
+                "message": { "text": print_markup(&diagnostic.title) },
+            });
+
+            if let Some(code) = &diagnostic.code {
+                result["ruleId"] = json!(code);
+            }
+
+            if let Some(primary) = &diagnostic.primary {
+                result["locations"] =
+                    json!([location(files, primary.span.file, primary.span.range)]);
+            }
+
+            let related: Vec<_> = diagnostic
+                .children
+                .iter()
+                .enumerate()
+                .map(|(index, child)| {
+                    let mut related = location(files, child.span.file, child.span.range);
+                    related["id"] = json!(index);
+                    related["message"] = json!({ "text": print_markup(&child.msg) });
+                    related
+                })
+                .collect();
+
+            if !related.is_empty() {
+                result["relatedLocations"] = json!(related);
+            }
+
+            result
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [
+            {
+                "tool": {
+                    "driver": {
+                        "name": "Rome",
+                        "informationUri": "https://rome.tools",
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+                // The column numbers of `location` count characters, while SARIF defaults to
+                // UTF-16 code units
+                "columnKind": "unicodeCodePoints",
+            }
+        ]
+    });
+
+    // SAFETY: Serializing a `Value` never fails
+    serde_json::to_string(&log).unwrap()
+}
+
+/// Returns the SARIF level corresponding to a diagnostic severity
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+/// Create a SARIF location object for a range in a file. The region always
+/// holds the byte offsets of the range, and also holds the line and column
+/// numbers if the source text of the file is known. Columns are counted in
+/// Unicode code points
+fn location(files: &dyn Files, file_id: FileId, range: TextRange) -> Value {
+    let mut region = json!({
+        "byteOffset": u32::from(range.start()),
+        "byteLength": u32::from(range.len()),
+    });
+
+    if let Some(source) = files.source(file_id) {
+        if let (Ok(start), Ok(end)) = (source.location(range.start()), source.location(range.end()))
+        {
+            region["startLine"] = json!(start.line_number);
+            region["startColumn"] = json!(start.column_number);
+            region["endLine"] = json!(end.line_number);
+            region["endColumn"] = json!(end.column_number);
+        }
+    }
+
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": files.name(file_id).unwrap_or_default() },
+            "region": region,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rome_diagnostics::file::SimpleFiles;
+    use rome_diagnostics::Diagnostic;
+    use rome_js_syntax::{TextRange, TextSize};
+    use serde_json::{json, Value};
+
+    use super::to_sarif;
+
+    #[test]
+    fn single_diagnostic() {
+        let mut files = SimpleFiles::new();
+        let file_id = files.add(
+            String::from("src/test.js"),
+            String::from("try {}\ncatch (e) { e = 1; }\n"),
+        );
+
+        let diagnostic = Diagnostic::warning(
+            file_id,
+            "js/noCatchAssign",
+            "Do not reassign catch parameters.",
+        )
+        .primary(TextRange::at(TextSize::from(19), TextSize::from(1)), "")
+        .secondary(
+            TextRange::at(TextSize::from(14), TextSize::from(1)),
+            "The catch parameter is declared here",
+        );
+
+        let sarif = to_sarif(&files, &[diagnostic]);
+        let sarif: Value = serde_json::from_str(&sarif).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["runs"][0]["columnKind"], "unicodeCodePoints");
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"],
+            json!([{ "id": "js/noCatchAssign" }])
+        );
+
+        assert_eq!(
+            sarif["runs"][0]["results"],
+            json!([
+                {
+                    "ruleId": "js/noCatchAssign",
+                    "level": "warning",
+                    "message": { "text": "Do not reassign catch parameters." },
+                    "locations": [
+                        {
+                            "physicalLocation": {
+                                "artifactLocation": { "uri": "src/test.js" },
+                                "region": {
+                                    "byteOffset": 19,
+                                    "byteLength": 1,
+                                    "startLine": 2,
+                                    "startColumn": 13,
+                                    "endLine": 2,
+                                    "endColumn": 14
+                                }
+                            }
+                        }
+                    ],
+                    "relatedLocations": [
+                        {
+                            "id": 0,
+                            "message": { "text": "The catch parameter is declared here" },
+                            "physicalLocation": {
+                                "artifactLocation": { "uri": "src/test.js" },
+                                "region": {
+                                    "byteOffset": 14,
+                                    "byteLength": 1,
+                                    "startLine": 2,
+                                    "startColumn": 8,
+                                    "endLine": 2,
+                                    "endColumn": 9
+                                }
+                            }
+                        }
+                    ]
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn columns_count_code_points() {
+        let mut files = SimpleFiles::new();
+        // The emoji takes 4 bytes, 2 UTF-16 code units and a single code point
+        let file_id = files.add(String::from("src/test.js"), String::from("'😀'; x;\n"));
+
+        let diagnostic = Diagnostic::warning(file_id, "js/test", "Test")
+            .primary(TextRange::at(TextSize::from(8), TextSize::from(1)), "");
+
+        let sarif = to_sarif(&files, &[diagnostic]);
+        let sarif: Value = serde_json::from_str(&sarif).unwrap();
+
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"],
+            json!({
+                "byteOffset": 8,
+                "byteLength": 1,
+                "startLine": 1,
+                "startColumn": 6,
+                "endLine": 1,
+                "endColumn": 7
+            })
+        );
+    }
+}
@@ -58,11 +58,13 @@ use rome_diagnostics::Diagnostic;
 use rome_formatter::{IndentStyle, Printed};
 use rome_fs::RomePath;
 use rome_js_syntax::{JsLanguage, TextRange, TextSize};
+use rome_text_edit::TextEdit;
 
 use crate::{settings::WorkspaceSettings, RomeError};
 
 pub use rome_analyze::RuleCategories;
 
+pub(crate) mod json;
 pub(crate) mod server;
 
 pub struct SupportsFeatureParams {
@@ -126,8 +128,23 @@ pub struct FormatOnTypeParams {
     pub indent_style: IndentStyle,
 }
 
+/// Which fixes should be applied by [Workspace::fix_file]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixFileMode {
+    /// Only apply the fixes marked as [Applicability::Always](rome_diagnostics::Applicability::Always)
+    SafeFixes,
+    /// Also apply the fixes marked as [Applicability::MaybeIncorrect](rome_diagnostics::Applicability::MaybeIncorrect),
+    /// safe fixes are still applied first
+    SafeAndUnsafeFixes,
+}
+
 pub struct FixFileParams {
     pub path: RomePath,
+    pub fix_file_mode: FixFileMode,
+}
+
+pub struct OrganizeImportsParams {
+    pub path: RomePath,
 }
 
 pub struct FixFileResult {
@@ -162,6 +179,11 @@ pub trait Workspace: Send + Sync + RefUnwindSafe {
     fn pull_diagnostics(&self, params: PullDiagnosticsParams)
         -> Result<Vec<Diagnostic>, RomeError>;
 
+    /// Retrieves the list of diagnostics associated to a file, serialized as
+    /// a JSON array of objects with the `code`, `severity`, `file`, `range`,
+    /// `message` and `labels` fields
+    fn pull_diagnostics_json(&self, params: PullDiagnosticsParams) -> Result<String, RomeError>;
+
     /// Retrieves the list of code actions available for a given cursor
     /// position within a file
     fn pull_actions(
@@ -182,6 +204,11 @@ pub trait Workspace: Send + Sync + RefUnwindSafe {
 
     /// Return the content of the file with all safe code actions applied
     fn fix_file(&self, params: FixFileParams) -> Result<FixFileResult, RomeError>;
+
+    /// Returns the edits sorting the import statements of the file: side
+    /// effect imports come first, followed by the imports of external modules
+    /// and then the imports of relative paths, and duplicated imports are removed
+    fn organize_imports(&self, params: OrganizeImportsParams) -> Result<TextEdit, RomeError>;
 }
 
 /// Convenience function for constructing a server instance of [Workspace]
@@ -269,9 +296,16 @@ impl<'app, W: Workspace + ?Sized> FileGuard<'app, W> {
         })
     }
 
-    pub fn fix_file(&self) -> Result<FixFileResult, RomeError> {
+    pub fn fix_file(&self, fix_file_mode: FixFileMode) -> Result<FixFileResult, RomeError> {
         self.workspace.fix_file(FixFileParams {
             path: self.path.clone(),
+            fix_file_mode,
+        })
+    }
+
+    pub fn organize_imports(&self) -> Result<TextEdit, RomeError> {
+        self.workspace.organize_imports(OrganizeImportsParams {
+            path: self.path.clone(),
         })
     }
 }
@@ -0,0 +1,119 @@
+//! Serialization of diagnostics to JSON, used by [Workspace::pull_diagnostics_json](crate::Workspace::pull_diagnostics_json)
+//!
+//! The schema is intended to remain stable so that it can be consumed by
+//! external tools like CI integrations, it consists of an array of objects
+//! in the following shape:
+//!
+//! ```json
+//! {
+//!   "code": "js/noCatchAssign",
+//!   "severity": "warning",
+//!   "file": "test.js",
+//!   "range": { "start": 19, "end": 20 },
+//!   "message": "Do not reassign catch parameters.",
+//!   "labels": [
+//!     {
+//!       "severity": "note",
+//!       "range": { "start": 14, "end": 15 },
+//!       "message": "The catch parameter is declared here"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Ranges are byte offsets in the source text, `range` is `null` for
+//! diagnostics that aren't attached to a specific location in the file
+
+use rome_console::fmt::{Display, Formatter, Termcolor};
+use rome_console::MarkupBuf;
+use rome_diagnostics::termcolor::NoColor;
+use rome_diagnostics::{Diagnostic, Severity};
+use rome_fs::RomePath;
+use rome_js_syntax::TextRange;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    code: Option<&'a str>,
+    severity: &'static str,
+    file: String,
+    range: Option<JsonRange>,
+    message: String,
+    labels: Vec<JsonLabel>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    severity: &'static str,
+    range: JsonRange,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonRange {
+    start: u32,
+    end: u32,
+}
+
+impl From<TextRange> for JsonRange {
+    fn from(range: TextRange) -> Self {
+        Self {
+            start: range.start().into(),
+            end: range.end().into(),
+        }
+    }
+}
+
+/// Serialize the diagnostics emitted for the file at `path` to a JSON array
+pub(crate) fn diagnostics_to_json(path: &RomePath, diagnostics: &[Diagnostic]) -> String {
+    let file = path.display().to_string();
+
+    let diagnostics: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| JsonDiagnostic {
+            code: diagnostic.code.as_deref(),
+            severity: severity_name(diagnostic.severity),
+            file: file.clone(),
+            range: diagnostic
+                .primary
+                .as_ref()
+                .map(|primary| primary.span.range.into()),
+            message: print_markup(&diagnostic.title),
+            labels: diagnostic
+                .children
+                .iter()
+                .map(|child| JsonLabel {
+                    severity: severity_name(child.severity),
+                    range: child.span.range.into(),
+                    message: print_markup(&child.msg),
+                })
+                .collect(),
+        })
+        .collect();
+
+    // SAFETY: The JSON types above can always be serialized
+    serde_json::to_string(&diagnostics).unwrap()
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Help => "help",
+        Severity::Note => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+        Severity::Bug => "bug",
+    }
+}
+
+/// Convert a piece of markup into a String, stripping its styling and any
+/// leading or trailing whitespace
+pub(crate) fn print_markup(markup: &MarkupBuf) -> String {
+    let mut message = Termcolor(NoColor::new(Vec::new()));
+    Display::fmt(markup, &mut Formatter::new(&mut message))
+        // SAFETY: Writing to a memory buffer should never fail
+        .unwrap();
+
+    // SAFETY: Printing uncolored markup never generates non UTF-8 byte sequences
+    let message = String::from_utf8(message.0.into_inner()).unwrap();
+    message.trim().to_string()
+}
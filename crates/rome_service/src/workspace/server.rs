@@ -1,12 +1,22 @@
-use std::{any::type_name, panic::RefUnwindSafe, sync::RwLock};
+use std::{
+    any::type_name,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    panic::RefUnwindSafe,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
 
 use dashmap::{mapref::entry::Entry, DashMap};
 use rome_analyze::AnalyzerAction;
 use rome_diagnostics::{Diagnostic, Severity};
-use rome_formatter::Printed;
+use rome_formatter::{IndentStyle, Printed};
 use rome_fs::RomePath;
-use rome_js_syntax::JsLanguage;
+use rome_js_syntax::{JsLanguage, TextRange, TextSize};
 use rome_rowan::{AstNode, Language as RowanLanguage, SendNode, SyntaxNode};
+use rome_text_edit::TextEdit;
 
 use crate::{
     file_handlers::Features,
@@ -15,9 +25,10 @@ use crate::{
 };
 
 use super::{
-    ChangeFileParams, CloseFileParams, FeatureName, FixFileResult, FormatFileParams,
-    FormatOnTypeParams, FormatRangeParams, GetSyntaxTreeParams, OpenFileParams, PullActionsParams,
-    PullDiagnosticsParams, SupportsFeatureParams, UpdateSettingsParams,
+    json, ChangeFileParams, CloseFileParams, FeatureName, FixFileResult, FormatFileParams,
+    FormatOnTypeParams, FormatRangeParams, GetSyntaxTreeParams, OpenFileParams,
+    OrganizeImportsParams, PullActionsParams, PullDiagnosticsParams, SupportsFeatureParams,
+    UpdateSettingsParams,
 };
 
 pub(super) struct WorkspaceServer {
@@ -25,10 +36,15 @@ pub(super) struct WorkspaceServer {
     features: Features,
     /// global settings object for this workspace
     settings: RwLock<WorkspaceSettings>,
+    /// Incremented every time the settings change, while holding the write lock of `settings`
+    settings_generation: AtomicU64,
     /// Stores the document (text content + version number) associated with a URL
     documents: DashMap<RomePath, Document>,
     /// Stores the result of the parser (syntax tree + diagnostics) for a given URL
     syntax: DashMap<RomePath, AnyParse>,
+    /// Stores the result of the formatter for a given URL, along with the content and
+    /// the settings generation it was computed from. Cleared when the settings change
+    formatted: DashMap<RomePath, FormattedDocument>,
 }
 
 /// The `Workspace` object is long lived, so we want it to be able to cross
@@ -45,6 +61,25 @@ pub(crate) struct Document {
     pub(crate) version: i32,
 }
 
+/// Cache entry for a formatted file
+struct FormattedDocument {
+    /// Hash of the content of the document that was formatted
+    content_hash: u64,
+    /// Indent style requested by the editor when formatting the document
+    indent_style: IndentStyle,
+    /// Generation of the settings the document was formatted with
+    settings_generation: u64,
+    printed: Printed,
+}
+
+impl FormattedDocument {
+    fn content_hash(content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
 /// Language-independent cache entry for a parsed file
 ///
 /// This struct holds a handle to the root node of the parsed syntax tree,
@@ -101,8 +136,10 @@ impl WorkspaceServer {
         Self {
             features: Features::new(),
             settings: RwLock::default(),
+            settings_generation: AtomicU64::new(0),
             documents: DashMap::default(),
             syntax: DashMap::default(),
+            formatted: DashMap::default(),
         }
     }
 
@@ -132,6 +169,61 @@ impl WorkspaceServer {
             }
         }
     }
+
+    /// Formats the given file with `formatter`, or returns the cached result if neither
+    /// the content of the file nor the settings changed since it was last formatted
+    fn format_file_with<F>(
+        &self,
+        params: FormatFileParams,
+        formatter: F,
+    ) -> Result<Printed, RomeError>
+    where
+        F: FnOnce(&RomePath, AnyParse, SettingsHandle<IndentStyle>) -> Result<Printed, RomeError>,
+    {
+        let content_hash = {
+            let document = self
+                .documents
+                .get(&params.path)
+                .ok_or(RomeError::NotFound)?;
+            FormattedDocument::content_hash(&document.content)
+        };
+
+        let settings = self.settings(params.indent_style);
+        // Read while holding the settings lock so that it matches the settings used for formatting
+        let settings_generation = self.settings_generation.load(Ordering::Acquire);
+
+        if let Some(formatted) = self.formatted.get(&params.path) {
+            if formatted.content_hash == content_hash
+                && formatted.indent_style == params.indent_style
+                && formatted.settings_generation == settings_generation
+            {
+                return Ok(formatted.printed.clone());
+            }
+        }
+
+        let parse = self.get_parse(params.path.clone())?;
+
+        if !settings.as_ref().format.format_with_errors && parse.has_errors() {
+            return Err(RomeError::FormatWithErrorsDisabled);
+        }
+
+        let printed = formatter(&params.path, parse, settings)?;
+
+        // Don't cache the result if the settings changed while formatting the file
+        if self.settings_generation.load(Ordering::Acquire) == settings_generation {
+            self.formatted.insert(
+                params.path,
+                FormattedDocument {
+                    content_hash,
+                    indent_style: params.indent_style,
+                    settings_generation,
+                    printed: printed.clone(),
+                },
+            );
+        }
+
+        Ok(printed)
+    }
 }
 
 impl Workspace for WorkspaceServer {
@@ -152,6 +244,10 @@ impl Workspace for WorkspaceServer {
     fn update_settings(&self, params: UpdateSettingsParams) -> Result<(), RomeError> {
         let mut settings = self.settings.write().unwrap();
         *settings = params.settings;
+        self.settings_generation.fetch_add(1, Ordering::AcqRel);
+
+        // The formatting results depend on the settings
+        self.formatted.clear();
         Ok(())
     }
 
@@ -188,10 +284,27 @@ impl Workspace for WorkspaceServer {
             .ok_or(RomeError::NotFound)?;
 
         debug_assert!(params.version > document.version);
+
+        // Try to update the cached syntax tree in place instead of discarding it
+        let (range, replacement) = text_edit(&document.content, &params.content);
+        let reparsed = self.syntax.get(&params.path).and_then(|parse| {
+            let capabilities = self.features.get_capabilities(&params.path);
+            let reparse = capabilities.reparse?;
+            reparse(&params.path, parse.clone(), range, replacement)
+        });
+
         document.version = params.version;
         document.content = params.content;
 
-        self.syntax.remove(&params.path);
+        match reparsed {
+            Some(parse) => {
+                self.syntax.insert(params.path, parse);
+            }
+            None => {
+                self.syntax.remove(&params.path);
+            }
+        }
+
         Ok(())
     }
 
@@ -202,6 +315,7 @@ impl Workspace for WorkspaceServer {
             .ok_or(RomeError::NotFound)?;
 
         self.syntax.remove(&params.path);
+        self.formatted.remove(&params.path);
         Ok(())
     }
 
@@ -220,6 +334,13 @@ impl Workspace for WorkspaceServer {
         Ok(linter(&params.path, parse, params.categories))
     }
 
+    fn pull_diagnostics_json(&self, params: PullDiagnosticsParams) -> Result<String, RomeError> {
+        let path = params.path.clone();
+        let diagnostics = self.pull_diagnostics(params)?;
+
+        Ok(json::diagnostics_to_json(&path, &diagnostics))
+    }
+
     /// Retrieves the list of code actions available for a given cursor
     /// position within a file
     fn pull_actions(
@@ -238,20 +359,16 @@ impl Workspace for WorkspaceServer {
 
     /// Runs the given file through the formatter using the provided options
     /// and returns the resulting source code
+    ///
+    /// The result is cached until the content of the file or the settings change,
+    /// so that formatting an unchanged file again doesn't run the formatter
     fn format_file(&self, params: FormatFileParams) -> Result<Printed, RomeError> {
         let capabilities = self.features.get_capabilities(&params.path);
         let formatter = capabilities
             .format
             .ok_or_else(|| RomeError::SourceFileNotSupported(params.path.clone()))?;
 
-        let parse = self.get_parse(params.path.clone())?;
-        let settings = self.settings(params.indent_style);
-
-        if !settings.as_ref().format.format_with_errors && parse.has_errors() {
-            return Err(RomeError::FormatWithErrorsDisabled);
-        }
-
-        formatter(&params.path, parse, settings)
+        self.format_file_with(params, formatter)
     }
 
     fn format_range(&self, params: FormatRangeParams) -> Result<Printed, RomeError> {
@@ -294,6 +411,386 @@ impl Workspace for WorkspaceServer {
 
         let parse = self.get_parse(params.path.clone())?;
 
-        Ok(fix_all(&params.path, parse))
+        Ok(fix_all(&params.path, parse, params.fix_file_mode))
+    }
+
+    fn organize_imports(&self, params: OrganizeImportsParams) -> Result<TextEdit, RomeError> {
+        let capabilities = self.features.get_capabilities(&params.path);
+        let organize_imports = capabilities
+            .organize_imports
+            .ok_or_else(|| RomeError::SourceFileNotSupported(params.path.clone()))?;
+
+        let parse = self.get_parse(params.path.clone())?;
+
+        Ok(organize_imports(&params.path, parse))
+    }
+}
+
+/// Returns the range of `old` and the text replacing it that turn `old` into `new` with a
+/// single edit, by skipping the common prefix and suffix of the two strings
+fn text_edit<'a>(old: &str, new: &'a str) -> (TextRange, &'a str) {
+    let mut prefix = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    while !old.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let mut suffix = old
+        .bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    while !old.is_char_boundary(old.len() - suffix) {
+        suffix -= 1;
+    }
+
+    let range = TextRange::new(
+        TextSize::from(prefix as u32),
+        TextSize::from((old.len() - suffix) as u32),
+    );
+
+    (range, &new[prefix..new.len() - suffix])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rome_formatter::IndentStyle;
+    use rome_fs::RomePath;
+
+    use crate::settings::WorkspaceSettings;
+    use crate::workspace::{
+        ChangeFileParams, FixFileMode, FixFileParams, FormatFileParams, GetSyntaxTreeParams,
+        OpenFileParams, OrganizeImportsParams, PullDiagnosticsParams, RuleCategories,
+        UpdateSettingsParams,
+    };
+    use crate::{RomeError, Workspace};
+
+    use super::WorkspaceServer;
+
+    fn format_with_errors(format_with_errors: bool) -> Result<String, RomeError> {
+        let workspace = WorkspaceServer::new();
+
+        let mut settings = WorkspaceSettings::default();
+        settings.format.format_with_errors = format_with_errors;
+        workspace.update_settings(UpdateSettingsParams { settings })?;
+
+        let path = RomePath::new("test.js", 0);
+        workspace.open_file(OpenFileParams {
+            path: path.clone(),
+            content: String::from("let   a = 1;\nlet b = ;\n"),
+            version: 0,
+        })?;
+
+        let printed = workspace.format_file(FormatFileParams {
+            path,
+            indent_style: IndentStyle::default(),
+        })?;
+
+        Ok(printed.into_code())
+    }
+
+    #[test]
+    fn format_with_errors_disabled() {
+        let result = format_with_errors(false);
+
+        assert!(matches!(result, Err(RomeError::FormatWithErrorsDisabled)));
+    }
+
+    #[test]
+    fn format_with_errors_enabled() {
+        let code = format_with_errors(true).expect("formatting failed");
+
+        assert!(code.starts_with("let a = 1;\n"));
+    }
+
+    fn open_file(workspace: &WorkspaceServer, content: &str) -> RomePath {
+        let path = RomePath::new("test.js", 0);
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: String::from(content),
+                version: 0,
+            })
+            .unwrap();
+
+        path
+    }
+
+    #[test]
+    fn format_file_caches_result() {
+        let workspace = WorkspaceServer::new();
+        let path = open_file(&workspace, "let   a = 1;\n");
+        let formatter = workspace.features.get_capabilities(&path).format.unwrap();
+
+        let runs = Cell::new(0);
+        let format = |workspace: &WorkspaceServer| {
+            workspace
+                .format_file_with(
+                    FormatFileParams {
+                        path: path.clone(),
+                        indent_style: IndentStyle::default(),
+                    },
+                    |path: &RomePath, parse, settings| {
+                        runs.set(runs.get() + 1);
+                        formatter(path, parse, settings)
+                    },
+                )
+                .unwrap()
+                .into_code()
+        };
+
+        assert_eq!(format(&workspace), "let a = 1;\n");
+        assert_eq!(format(&workspace), "let a = 1;\n");
+        assert_eq!(runs.get(), 1);
+
+        // Changing the content invalidates the cached result
+        workspace
+            .change_file(ChangeFileParams {
+                path: path.clone(),
+                content: String::from("let   b = 2;\n"),
+                version: 1,
+            })
+            .unwrap();
+        assert_eq!(format(&workspace), "let b = 2;\n");
+        assert_eq!(runs.get(), 2);
+
+        // Changing the settings invalidates the cached result
+        workspace
+            .update_settings(UpdateSettingsParams {
+                settings: WorkspaceSettings::default(),
+            })
+            .unwrap();
+        assert_eq!(format(&workspace), "let b = 2;\n");
+        assert_eq!(format(&workspace), "let b = 2;\n");
+        assert_eq!(runs.get(), 3);
+    }
+
+    #[test]
+    fn format_file_discards_result_if_settings_change_while_formatting() {
+        let workspace = WorkspaceServer::new();
+        let path = open_file(&workspace, "let   a = 1;\n");
+        let formatter = workspace.features.get_capabilities(&path).format.unwrap();
+
+        let params = || FormatFileParams {
+            path: path.clone(),
+            indent_style: IndentStyle::default(),
+        };
+
+        workspace
+            .format_file_with(params(), |path: &RomePath, parse, settings| {
+                let printed = formatter(path, parse, settings);
+
+                // The settings handle has been dropped by the formatter at this point
+                let mut settings = WorkspaceSettings::default();
+                settings.format.indent_style = Some(IndentStyle::Space(4));
+                workspace.update_settings(UpdateSettingsParams { settings })?;
+
+                printed
+            })
+            .unwrap();
+
+        // The result formatted with the previous settings isn't cached
+        let runs = Cell::new(0);
+        workspace
+            .format_file_with(params(), |path: &RomePath, parse, settings| {
+                runs.set(runs.get() + 1);
+                formatter(path, parse, settings)
+            })
+            .unwrap();
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    fn change_file_matches_full_parse() {
+        let workspace = WorkspaceServer::new();
+        let path = open_file(&workspace, "const a = \"abc\";\nconst b = 'é';\n");
+
+        let edits = [
+            // Handled incrementally
+            "const a = \"axyzc\";\nconst b = 'é';\n",
+            "const a = \"axyzc\";\nconst b = 'è';\n",
+            // Requires a full parse
+            "const a = \"axyzc\" + 1;\nconst b = 'è';\n",
+            "const a = \"axyzc;\nconst b = 'è';\n",
+            "const a = \"axyzc\";\nconst b = 'è';\n",
+        ];
+
+        for (version, content) in edits.into_iter().enumerate() {
+            // Populate the cached syntax tree
+            workspace
+                .get_syntax_tree(GetSyntaxTreeParams { path: path.clone() })
+                .unwrap();
+
+            workspace
+                .change_file(ChangeFileParams {
+                    path: path.clone(),
+                    content: String::from(content),
+                    version: version as i32 + 1,
+                })
+                .unwrap();
+
+            let expected = WorkspaceServer::new();
+            let expected_path = open_file(&expected, content);
+
+            assert_eq!(
+                workspace
+                    .get_syntax_tree(GetSyntaxTreeParams { path: path.clone() })
+                    .unwrap(),
+                expected
+                    .get_syntax_tree(GetSyntaxTreeParams {
+                        path: expected_path
+                    })
+                    .unwrap()
+            );
+        }
+    }
+
+    fn fix_file(fix_file_mode: FixFileMode) -> (String, Vec<&'static str>) {
+        let workspace = WorkspaceServer::new();
+        // The unsafe `useWhile` fix of the loop covers the safe
+        // `useSingleVarDeclarator` fix of the declaration
+        let path = open_file(&workspace, "for (; x;) {\n\tlet a = 1, b = 2;\n}\n");
+
+        let result = workspace
+            .fix_file(FixFileParams {
+                path,
+                fix_file_mode,
+            })
+            .unwrap();
+
+        let rules = result.rules.into_iter().map(|(rule, _)| rule).collect();
+
+        (result.code, rules)
+    }
+
+    #[test]
+    fn fix_file_safe_fixes() {
+        let (code, rules) = fix_file(FixFileMode::SafeFixes);
+
+        assert_eq!(rules, ["useSingleVarDeclarator"]);
+        assert!(code.starts_with("for (; x;) {"));
+    }
+
+    #[test]
+    fn fix_file_prefers_safe_fixes() {
+        let (code, rules) = fix_file(FixFileMode::SafeAndUnsafeFixes);
+
+        assert_eq!(rules, ["useSingleVarDeclarator", "useWhile"]);
+        assert!(code.starts_with("while (x) {"));
+    }
+
+    fn organize_imports(content: &str) -> String {
+        let workspace = WorkspaceServer::new();
+        let path = open_file(&workspace, content);
+
+        let edit = workspace
+            .organize_imports(OrganizeImportsParams { path })
+            .unwrap();
+
+        let mut code = String::from(content);
+        edit.apply(&mut code);
+        code
+    }
+
+    #[test]
+    fn organize_imports_sorts_import_block() {
+        let code = organize_imports(
+            "import b from \"./b\";\nimport { a } from \"a\";\n// polyfills\nimport \"polyfill\";\nimport { a } from \"a\";\nimport c from \"../c\"; // parent\n\nlet x = 1;\n",
+        );
+
+        assert_eq!(
+            code,
+            "// polyfills\nimport \"polyfill\";\nimport { a } from \"a\";\nimport c from \"../c\"; // parent\nimport b from \"./b\";\n\nlet x = 1;\n"
+        );
+    }
+
+    #[test]
+    fn organize_imports_sorts_side_effect_imports_first() {
+        let code = organize_imports("import c from \"c\";\nimport \"b\";\nimport \"a\";\n");
+
+        assert_eq!(code, "import \"b\";\nimport \"a\";\nimport c from \"c\";\n");
+    }
+
+    #[test]
+    fn organize_imports_keeps_header_comments_in_place() {
+        let code = organize_imports(
+            "// Copyright header\n\nimport b from \"b\";\n// a\nimport a from \"a\";\n",
+        );
+
+        assert_eq!(
+            code,
+            "// Copyright header\n\n// a\nimport a from \"a\";\nimport b from \"b\";\n"
+        );
+    }
+
+    #[test]
+    fn organize_imports_preserves_separators() {
+        let code = organize_imports("import b from \"b\";\r\n\r\nimport a from \"a\";\r\n");
+
+        assert_eq!(code, "import a from \"a\";\r\n\r\nimport b from \"b\";\r\n");
+    }
+
+    #[test]
+    fn organize_imports_moves_comments_of_duplicates() {
+        let code =
+            organize_imports("// first\nimport a from \"a\";\n// second\nimport a from \"a\";\n");
+
+        assert_eq!(code, "// first\n// second\nimport a from \"a\";\n");
+    }
+
+    #[test]
+    fn organize_imports_sorted_block() {
+        let content = "import \"polyfill\";\nimport a from \"a\";\nimport b from \"./b\";\n";
+
+        assert_eq!(organize_imports(content), content);
+    }
+
+    #[test]
+    fn pull_diagnostics_json() {
+        let workspace = WorkspaceServer::new();
+        let path = open_file(&workspace, "try {} catch (e) { e = 1; }\n");
+
+        let json = workspace
+            .pull_diagnostics_json(PullDiagnosticsParams {
+                path,
+                categories: RuleCategories::LINT,
+            })
+            .unwrap();
+
+        let diagnostics: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let diagnostic = diagnostics
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|diagnostic| diagnostic["code"] == "js/noCatchAssign")
+            .expect("missing noCatchAssign diagnostic");
+
+        assert_eq!(
+            diagnostic,
+            &serde_json::json!({
+                "code": "js/noCatchAssign",
+                "severity": "warning",
+                "file": "test.js",
+                "range": { "start": 19, "end": 20 },
+                "message": "Do not reassign catch parameters.",
+                "labels": [
+                    {
+                        "severity": "note",
+                        "range": { "start": 14, "end": 15 },
+                        "message": "The catch parameter is declared here"
+                    }
+                ]
+            })
+        );
     }
 }